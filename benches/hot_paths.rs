@@ -0,0 +1,103 @@
+//! Measures hot paths of [`Tree`] across all implemented sizes.
+//!
+//! Run with `cargo bench --bench hot_paths --features bench-fixtures`, workloads come from
+//! `packed_tree::bench_fixtures`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use packed_tree::bench_fixtures::{combine_rule, scattered_indexes, terrain};
+use packed_tree::implemented_tree_sizes::{
+    TREE_1, TREE_128, TREE_16, TREE_2, TREE_32, TREE_4, TREE_64, TREE_8,
+};
+use packed_tree::{LayerPosition, Node, NodeIndex, NodePosition, Tree, TreeInterface};
+
+/// Amount of positions accessed in one iteration of access benchmarks.
+const ACCESSES: usize = 1024;
+
+fn bench_size<const SIZE: usize>(c: &mut Criterion)
+where
+    Tree<u32, SIZE>: TreeInterface,
+{
+    let mut group = c.benchmark_group(format!("hot_paths/{}", Tree::<u32, SIZE>::BIGGEST_ROW_SIZE));
+    // Building the biggest trees takes milliseconds, so fewer samples are enough.
+    group.sample_size(10);
+    let mut tree = terrain::<SIZE>();
+    let indexes = scattered_indexes::<Tree<u32, SIZE>>(ACCESSES);
+
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            indexes
+                .iter()
+                .filter(|index| matches!(tree.get(**index), Node::Filled(_)))
+                .count()
+        })
+    });
+    group.bench_function("set", |b| {
+        b.iter(|| {
+            for index in &indexes {
+                tree.set(*index, Node::Filled(2));
+            }
+        })
+    });
+
+    group.bench_function("index_to_position", |b| {
+        b.iter(|| {
+            indexes
+                .iter()
+                .map(|index| NodePosition::from(black_box(*index)).x)
+                .sum::<usize>()
+        })
+    });
+    let positions = indexes
+        .iter()
+        .map(|index| LayerPosition::from(*index))
+        .collect::<Vec<_>>();
+    group.bench_function("position_to_index", |b| {
+        b.iter(|| {
+            positions
+                .iter()
+                .map(|position| NodeIndex::from(black_box(*position)).raw())
+                .sum::<usize>()
+        })
+    });
+
+    group.bench_function("build", |b| b.iter(|| tree.build(combine_rule)));
+
+    #[cfg(feature = "unstable-io")]
+    {
+        let tree = terrain::<SIZE>();
+        let mut bytes = Vec::new();
+        group.bench_function("write_truncated", |b| {
+            b.iter(|| {
+                bytes.clear();
+                tree.write_truncated(&mut bytes, 0, |value, writer| {
+                    writer.extend_from_slice(&value.to_le_bytes());
+                    Ok(())
+                })
+            })
+        });
+        group.bench_function("read_truncated", |b| {
+            b.iter(|| {
+                Tree::<u32, SIZE>::read_truncated(&mut bytes.as_slice(), |reader| {
+                    let mut value = [0; 4];
+                    std::io::Read::read_exact(reader, &mut value)?;
+                    Ok(u32::from_le_bytes(value))
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+fn hot_paths(c: &mut Criterion) {
+    bench_size::<TREE_1>(c);
+    bench_size::<TREE_2>(c);
+    bench_size::<TREE_4>(c);
+    bench_size::<TREE_8>(c);
+    bench_size::<TREE_16>(c);
+    bench_size::<TREE_32>(c);
+    bench_size::<TREE_64>(c);
+    bench_size::<TREE_128>(c);
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
@@ -0,0 +1,23 @@
+//! Measures hierarchical traversals of the biggest tree.
+//!
+//! Run with `cargo bench --bench traversal --features bench-fixtures` and compare with
+//! `--features bench-fixtures,perf`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use packed_tree::bench_fixtures::terrain;
+use packed_tree::implemented_tree_sizes::TREE_128;
+
+fn traversal(c: &mut Criterion) {
+    let tree = terrain::<TREE_128>();
+    let mut group = c.benchmark_group("traversal");
+    // Every traversal visits millions of nodes, so fewer samples are enough.
+    group.sample_size(10);
+    group.bench_function("descend_filled", |b| {
+        b.iter(|| tree.descend_filled().count())
+    });
+    group.bench_function("surface", |b| b.iter(|| tree.surface().count()));
+    group.finish();
+}
+
+criterion_group!(benches, traversal);
+criterion_main!(benches);
@@ -1,15 +1,29 @@
 use std::fmt::Display;
 use std::marker::PhantomData;
-use std::ops::{Add, Index, IndexMut, Sub};
+use std::ops::{Add, Index, IndexMut, Range, RangeBounds, Sub};
 
-use crate::{LayerIndex, LayerPosition, TreeInterface};
+use crate::{
+    Ancestors, AxisMapping, CoordinateError, Depth, Direction, LayerIndex, LayerPosition,
+    NodeIndexRange, Offset, TreeInterface,
+};
+
+/// Integer in which [`NodeIndex`] is stored, [`u32`] with `u32-index` feature enabled.
+///
+/// [`u32`] halves size of collections of indexes and is big enough for all implemented tree sizes.
+#[cfg(feature = "u32-index")]
+pub type RawNodeIndex = u32;
+
+/// Integer in which [`NodeIndex`] is stored, [`u32`] with `u32-index` feature enabled.
+#[cfg(not(feature = "u32-index"))]
+pub type RawNodeIndex = usize;
 
 /// Absolute index of [`Node`](crate::Node) inside a [`Tree`](crate::Tree).
 ///
 /// This structure always expects to have valid data inside and in debug panics if that is not true.
+/// Index is stored as [`RawNodeIndex`], but [`usize`] is used in its whole interface.
 #[derive(Debug)]
 pub struct NodeIndex<T> {
-    index: usize,
+    index: RawNodeIndex,
     /// Associated [`Tree`](crate::Tree).
     boo: PhantomData<T>,
 }
@@ -17,10 +31,7 @@ pub struct NodeIndex<T> {
 /// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
 impl<T> Clone for NodeIndex<T> {
     fn clone(&self) -> Self {
-        Self {
-            index: self.index,
-            boo: PhantomData,
-        }
+        *self
     }
 }
 
@@ -38,7 +49,7 @@ impl<T> PartialEq for NodeIndex<T> {
 /// and comparison to [`usize`] is possible.
 impl<T> PartialEq<usize> for NodeIndex<T> {
     fn eq(&self, other: &usize) -> bool {
-        self.index == *other
+        self.get() == *other
     }
 }
 
@@ -53,7 +64,7 @@ impl<T> PartialOrd for NodeIndex<T> {
 /// and comparison to [`usize`] is possible.
 impl<T> PartialOrd<usize> for NodeIndex<T> {
     fn partial_cmp(&self, other: &usize) -> Option<std::cmp::Ordering> {
-        Some(self.index.cmp(other))
+        Some(self.get().cmp(other))
     }
 }
 
@@ -81,13 +92,8 @@ where
     T: TreeInterface,
 {
     fn from(value: LayerPosition<T>) -> Self {
-        let (mut index, depth) = LayerIndex::from(value).get_raw();
-
-        for row_size in &T::rows_sizes()[0..depth] {
-            let layer_size = row_size * row_size * row_size;
-            index += layer_size;
-        }
-        NodeIndex::new(index)
+        let (index, depth) = LayerIndex::from(value).get_raw();
+        NodeIndex::new(T::layer_start(depth) + index)
     }
 }
 
@@ -100,6 +106,16 @@ where
     }
 }
 
+/// Tuple of `(x, y, z, depth)` is converted the same way as [LayerPosition].
+impl<T> From<(usize, usize, usize, Depth<T>)> for NodeIndex<T>
+where
+    T: TreeInterface,
+{
+    fn from((x, y, z, depth): (usize, usize, usize, Depth<T>)) -> Self {
+        Self::from(LayerPosition::new(x, y, z, depth.into()))
+    }
+}
+
 impl<T, U> Index<NodeIndex<T>> for Vec<U>
 where
     T: TreeInterface,
@@ -107,7 +123,7 @@ where
     type Output = U;
 
     fn index(&self, index: NodeIndex<T>) -> &Self::Output {
-        &self[index.index]
+        &self[index.raw()]
     }
 }
 
@@ -116,7 +132,7 @@ where
     T: TreeInterface,
 {
     fn index_mut(&mut self, index: NodeIndex<T>) -> &mut Self::Output {
-        &mut self[index.index]
+        &mut self[index.raw()]
     }
 }
 
@@ -127,7 +143,7 @@ where
     type Output = U;
 
     fn index(&self, index: NodeIndex<T>) -> &Self::Output {
-        &self[index.index]
+        &self[index.raw()]
     }
 }
 
@@ -136,7 +152,7 @@ where
     T: TreeInterface,
 {
     fn index_mut(&mut self, index: NodeIndex<T>) -> &mut Self::Output {
-        &mut self[index.index]
+        &mut self[index.raw()]
     }
 }
 
@@ -147,7 +163,7 @@ where
     type Output = Self;
 
     fn add(mut self, rhs: NodeIndex<T>) -> Self::Output {
-        self.index += rhs.raw();
+        self.index += rhs.index;
         assert!(self.is_valid());
         self
     }
@@ -160,7 +176,7 @@ where
     type Output = Self;
 
     fn add(mut self, rhs: usize) -> Self::Output {
-        self.index += rhs;
+        self.index = (self.raw() + rhs) as RawNodeIndex;
         assert!(self.is_valid());
         self
     }
@@ -173,7 +189,7 @@ where
     type Output = Self;
 
     fn sub(mut self, rhs: NodeIndex<T>) -> Self::Output {
-        self.index -= rhs.raw();
+        self.index -= rhs.index;
         assert!(self.is_valid());
         self
     }
@@ -186,7 +202,7 @@ where
     type Output = Self;
 
     fn sub(mut self, rhs: usize) -> Self::Output {
-        self.index -= rhs;
+        self.index = (self.raw() - rhs) as RawNodeIndex;
         assert!(self.is_valid());
         self
     }
@@ -194,7 +210,16 @@ where
 
 impl<T> From<NodeIndex<T>> for usize {
     fn from(value: NodeIndex<T>) -> Self {
-        value.index
+        value.get()
+    }
+}
+
+impl<T> NodeIndex<T> {
+    /// Returns `index` as [`usize`], does not require `T` to implement [`TreeInterface`].
+    // The cast is unnecessary only without `u32-index` feature.
+    #[allow(clippy::unnecessary_cast)]
+    const fn get(self) -> usize {
+        self.index as usize
     }
 }
 
@@ -206,34 +231,49 @@ where
     ///
     /// Validity of provided `index` is checked only in debug mode. If provided index could be
     /// invalid, use [`new_checked`](NodeIndex::new_checked).
-    pub fn new(index: usize) -> Self {
+    pub const fn new(index: usize) -> Self {
         debug_assert!(Self::is_valid_index(index));
         Self {
-            index,
+            index: index as RawNodeIndex,
             boo: PhantomData,
         }
     }
 
-    /// Creates a new [NodeIndex] if provided `index` is valid, otherwise [`Err`] is returned.
-    #[allow(clippy::result_unit_err)]
-    pub fn new_checked(index: usize) -> Result<Self, ()> {
+    /// Creates a new [NodeIndex] if provided `index` is valid,
+    /// otherwise [`CoordinateError::InvalidIndex`] is returned.
+    pub const fn new_checked(index: usize) -> Result<Self, CoordinateError> {
         if !Self::is_valid_index(index) {
-            return Err(());
+            return Err(CoordinateError::InvalidIndex {
+                index,
+                size: T::SIZE,
+            });
         }
         Ok(Self {
-            index,
+            index: index as RawNodeIndex,
             boo: PhantomData,
         })
     }
 
     /// Returns `true` if `index` is less than [`tree size`](TreeParameters::SIZE).
-    pub fn is_valid_index(index: usize) -> bool {
+    pub const fn is_valid_index(index: usize) -> bool {
         index < T::SIZE
     }
 
     /// Returns `true` if `index` is less than [`tree size`](TreeParameters::SIZE).
-    pub fn is_valid(self) -> bool {
-        Self::is_valid_index(self.index)
+    pub const fn is_valid(self) -> bool {
+        Self::is_valid_index(self.raw())
+    }
+
+    /// Converts into another coordinate type if this index is valid, otherwise returns
+    /// [`CoordinateError`] describing the problem.
+    ///
+    /// [`From`] conversions expect valid data and check it only in debug mode,
+    /// use this for data which may be invalid, e.g. created by `new` in release mode.
+    pub fn checked_into<U>(self) -> Result<U, CoordinateError>
+    where
+        U: From<Self>,
+    {
+        Ok(Self::new_checked(self.raw())?.into())
     }
 
     /// Replaces the index inside with provided `index`
@@ -247,21 +287,135 @@ where
 
     /// Calculates depth of `index` inside associated [`Tree`](crate::Tree).
     pub fn depth(self) -> usize {
-        let mut depth = 0;
-        let mut layer_max_index = 0;
-        for row_size in T::rows_sizes() {
-            layer_max_index += row_size * row_size * row_size;
-            if self.index < layer_max_index {
-                break;
+        let mut layer_end = 0;
+        for depth in 0..T::DEPTH {
+            layer_end += T::layer_size(depth).get();
+            if self.raw() < layer_end {
+                return depth;
             }
-            depth += 1;
         }
-        depth
+        T::DEPTH
+    }
+
+    /// Returns index of descendant in `target_depth` in bottom front left corner of this node,
+    /// or [`None`] if `target_depth` is deeper than this node.
+    pub fn descendant_anchor(self, target_depth: usize) -> Option<Self> {
+        Some(
+            NodePosition::from(self)
+                .descendant_anchor(target_depth)?
+                .into(),
+        )
+    }
+
+    /// Returns ranges of `x`, `y` and `z` of leaves covered by this node.
+    pub fn leaf_region(self) -> [Range<usize>; 3] {
+        NodePosition::from(self).leaf_region()
+    }
+
+    /// Returns the deepest node which is an ancestor of both this node and `other`,
+    /// together with its [`Depth`]. A node counts as its own ancestor.
+    pub fn common_ancestor(self, other: Self) -> (Self, Depth<T>) {
+        let (a, b) = (NodePosition::from(self), NodePosition::from(other));
+        // Ancestors in `depth` are the same once coordinates do not differ in any lower bit.
+        let differing = (a.x ^ b.x) | (a.y ^ b.y) | (a.z ^ b.z);
+        let depth = (usize::BITS - differing.leading_zeros()) as usize;
+        let depth = depth.max(a.depth).max(b.depth);
+        // Depth of a valid position is always in bounds.
+        let ancestor = a.align_to(depth).unwrap();
+        (ancestor.into(), Depth::try_new(depth).unwrap())
+    }
+
+    /// Returns octant of this node inside of its parrent, i.e. `x | y << 1 | z << 2` of its
+    /// position inside of the parrent, or [`None`] for the root.
+    ///
+    /// Octants are ordered in the same way as [`Tree::children`](crate::Tree::children) returns them.
+    pub fn octant(self) -> Option<usize> {
+        LayerPosition::from(self).octant()
+    }
+
+    /// Returns index of child in `octant` of the same parrent, or [`None`] for the root.
+    ///
+    /// Expects `octant` to be less than 8.
+    pub fn sibling(self, octant: usize) -> Option<Self> {
+        debug_assert!(octant < 8);
+        let position = LayerPosition::from(self);
+        let current = position.octant()?;
+        let row_size = T::row_size(position.depth);
+        let axis = |octant: usize, bit: usize| (octant >> bit & 1) as isize;
+        let offset = (axis(octant, 0) - axis(current, 0))
+            + (axis(octant, 1) - axis(current, 1)) * row_size.get() as isize
+            + (axis(octant, 2) - axis(current, 2)) * (row_size.get() * row_size.get()) as isize;
+        Some(Self::new(self.raw().wrapping_add_signed(offset)))
+    }
+
+    /// Returns an iterator over ancestors of this index, from its parent up to the root.
+    pub fn ancestors(self) -> Ancestors<T> {
+        Ancestors::new(self)
+    }
+
+    /// Returns an iterator over indexes in `range`, so ranges of indexes can be looped over.
+    ///
+    /// Accepts any kind of range, so it can include the last node, i.e. the root.
+    pub fn range<R>(range: R) -> NodeIndexRange<T>
+    where
+        R: RangeBounds<Self>,
+    {
+        NodeIndexRange::new(range)
+    }
+
+    /// Returns an iterator over all indexes of associated [`Tree`](crate::Tree),
+    /// in the order in which nodes are stored.
+    pub fn iter_all() -> impl Iterator<Item = Self> {
+        (0..T::SIZE).map(Self::new)
     }
 
     /// Returs an `index` as [`usize`].
-    pub fn raw(self) -> usize {
-        self.index
+    pub const fn raw(self) -> usize {
+        self.get()
+    }
+}
+
+/// Coordinates which can be validated and converted into [NodeIndex],
+/// used by [`Tree::try_get`](crate::Tree::try_get) and [`Tree::try_get_mut`](crate::Tree::try_get_mut).
+pub trait TryIntoIndex<T> {
+    /// Returns [NodeIndex] of these coordinates if they are valid,
+    /// otherwise [`CoordinateError`] describing the problem.
+    fn try_into_index(self) -> Result<NodeIndex<T>, CoordinateError>;
+}
+
+impl<T> TryIntoIndex<T> for NodeIndex<T>
+where
+    T: TreeInterface,
+{
+    fn try_into_index(self) -> Result<NodeIndex<T>, CoordinateError> {
+        self.checked_into()
+    }
+}
+
+impl<T> TryIntoIndex<T> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    fn try_into_index(self) -> Result<NodeIndex<T>, CoordinateError> {
+        self.checked_into()
+    }
+}
+
+impl<T> TryIntoIndex<T> for LayerIndex<T>
+where
+    T: TreeInterface,
+{
+    fn try_into_index(self) -> Result<NodeIndex<T>, CoordinateError> {
+        self.checked_into()
+    }
+}
+
+impl<T> TryIntoIndex<T> for LayerPosition<T>
+where
+    T: TreeInterface,
+{
+    fn try_into_index(self) -> Result<NodeIndex<T>, CoordinateError> {
+        self.checked_into()
     }
 }
 
@@ -291,13 +445,7 @@ pub struct NodePosition<T> {
 /// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
 impl<T> Clone for NodePosition<T> {
     fn clone(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y,
-            z: self.z,
-            depth: self.depth,
-            boo: PhantomData,
-        }
+        *self
     }
 }
 
@@ -343,7 +491,7 @@ where
     T: TreeInterface,
 {
     fn from(value: LayerPosition<T>) -> Self {
-        let multiplier = T::BIGGEST_ROW_SIZE / T::row_size(value.depth);
+        let multiplier = T::BIGGEST_ROW_SIZE / T::row_size(value.depth).get();
 
         let x = value.x * multiplier;
         let y = value.y * multiplier;
@@ -369,7 +517,7 @@ where
     /// Creates a new [NodePosition].
     ///
     /// Validity of provided `position` and `depth` is checked only in debug mode.
-    pub fn new(x: usize, y: usize, z: usize, depth: usize) -> Self {
+    pub const fn new(x: usize, y: usize, z: usize, depth: usize) -> Self {
         debug_assert!(Self::is_valid_position(x, y, z, depth));
         Self {
             x,
@@ -380,28 +528,73 @@ where
         }
     }
 
+    /// Creates a new [NodePosition] if provided `position` and `depth` are valid,
+    /// otherwise [`CoordinateError`] describing the first violated limit is returned.
+    pub fn new_checked(
+        x: usize,
+        y: usize,
+        z: usize,
+        depth: usize,
+    ) -> Result<Self, CoordinateError> {
+        if depth > T::MAX_DEPTH_INDEX {
+            return Err(CoordinateError::InvalidDepth {
+                depth,
+                max: T::MAX_DEPTH_INDEX,
+            });
+        }
+        if x >= T::BIGGEST_ROW_SIZE || y >= T::BIGGEST_ROW_SIZE || z >= T::BIGGEST_ROW_SIZE {
+            return Err(CoordinateError::InvalidPosition {
+                position: [x, y, z],
+                depth,
+                row_size: T::BIGGEST_ROW_SIZE,
+            });
+        }
+        if !Self::is_valid_position(x, y, z, depth) {
+            return Err(CoordinateError::UnalignedPosition {
+                position: [x, y, z],
+                depth,
+                alignment: 2_usize.pow(depth as u32),
+            });
+        }
+        Ok(Self::new(x, y, z, depth))
+    }
+
     /// Returns `true` if `x`, `y`, `z` are less than
     /// [BIGGEST_ROW_SIZE](TreeParameters::BIGGEST_ROW_SIZE) of associated [`Tree`]
     /// and valid in provided `depth` and `depth` is less
     /// [MAX_DEPTH_INDEX](TreeParameters::MAX_DEPTH_INDEX) of associated [`Tree`].
-    pub fn is_valid_position(x: usize, y: usize, z: usize, depth: usize) -> bool {
+    pub const fn is_valid_position(x: usize, y: usize, z: usize, depth: usize) -> bool {
+        if depth > T::MAX_DEPTH_INDEX {
+            return false;
+        }
         let divisor = 2_usize.pow(depth as u32);
 
-        depth <= T::MAX_DEPTH_INDEX
-            && x % divisor == 0
+        x.is_multiple_of(divisor)
             && x < T::BIGGEST_ROW_SIZE
-            && y % divisor == 0
+            && y.is_multiple_of(divisor)
             && y < T::BIGGEST_ROW_SIZE
-            && z % divisor == 0
+            && z.is_multiple_of(divisor)
             && z < T::BIGGEST_ROW_SIZE
     }
 
     /// Returns `true` if call to [NodePosition::is_valid_position] on inner values
     /// is evaluated to `true`.
-    pub fn is_valid(self) -> bool {
+    pub const fn is_valid(self) -> bool {
         Self::is_valid_position(self.x, self.y, self.z, self.depth)
     }
 
+    /// Converts into another coordinate type if this position is valid, otherwise returns
+    /// [`CoordinateError`] describing the problem.
+    ///
+    /// [`From`] conversions expect valid data and check it only in debug mode,
+    /// use this for data which may be invalid, e.g. one with fields changed directly.
+    pub fn checked_into<U>(self) -> Result<U, CoordinateError>
+    where
+        U: From<Self>,
+    {
+        Ok(Self::new_checked(self.x, self.y, self.z, self.depth)?.into())
+    }
+
     /// Returns [NodePosition] of child in bottom front left corner of parrent node
     /// if exists, otherwise [`None`] is returned.
     pub fn child_position(mut self) -> Option<Self> {
@@ -411,6 +604,148 @@ where
         self.depth -= 1;
         Some(self)
     }
+
+    /// Returns [NodePosition] of descendant in `target_depth` in bottom front left corner of this
+    /// node, or [`None`] if `target_depth` is deeper than this node.
+    pub fn descendant_anchor(mut self, target_depth: usize) -> Option<Self> {
+        if target_depth > self.depth {
+            return None;
+        }
+        self.depth = target_depth;
+        Some(self)
+    }
+
+    /// Returns [NodePosition] of ancestor in `target_depth` containing this node, i.e. its
+    /// coordinates snapped to the bottom front left corner of that ancestor, or [`None`]
+    /// if `target_depth` is shallower than this node or is not a layer of the tree.
+    pub fn align_to(self, target_depth: usize) -> Option<Self> {
+        if target_depth < self.depth || target_depth > T::MAX_DEPTH_INDEX {
+            return None;
+        }
+        let mask = !((1 << target_depth) - 1);
+        Some(Self::new(
+            self.x & mask,
+            self.y & mask,
+            self.z & mask,
+            target_depth,
+        ))
+    }
+
+    /// Returns `true` if node on `other` is inside of this node, a node covers itself.
+    pub fn covers(self, other: Self) -> bool {
+        other.align_to(self.depth) == Some(self)
+    }
+
+    /// Returns ranges of `x`, `y` and `z` of leaves covered by this node.
+    pub fn leaf_region(self) -> [Range<usize>; 3] {
+        let extent = 1 << self.depth;
+        [
+            self.x..self.x + extent,
+            self.y..self.y + extent,
+            self.z..self.z + extent,
+        ]
+    }
+
+    /// Returns the lowest and the highest corner of box covered by this node in world space,
+    /// where `origin` is the bottom front left corner of the tree and `voxel_size` is length
+    /// of an edge of a leaf.
+    pub fn world_aabb(self, origin: [f32; 3], voxel_size: f32) -> ([f32; 3], [f32; 3]) {
+        let extent = (1 << self.depth) as f32 * voxel_size;
+        let coordinates = [self.x, self.y, self.z];
+        let min = [0, 1, 2].map(|i| origin[i] + coordinates[i] as f32 * voxel_size);
+        (min, min.map(|coordinate| coordinate + extent))
+    }
+
+    /// Returns an iterator over all positions in layer in `depth`, in the order in which nodes
+    /// are stored, i.e. `x` changes the fastest.
+    ///
+    /// Expects in-bounds `depth`.
+    pub fn iter_layer(depth: usize) -> impl Iterator<Item = Self> {
+        let step = 1 << depth;
+        (0..T::BIGGEST_ROW_SIZE).step_by(step).flat_map(move |z| {
+            (0..T::BIGGEST_ROW_SIZE).step_by(step).flat_map(move |y| {
+                (0..T::BIGGEST_ROW_SIZE)
+                    .step_by(step)
+                    .map(move |x| Self::new(x, y, z, depth))
+            })
+        })
+    }
+
+    /// Returns position of the neighbour in `direction` in the same layer, moved by the size
+    /// of one node, or [`None`] if there is no neighbour inside of the layer.
+    pub fn neighbor(self, direction: Direction) -> Option<Self> {
+        LayerPosition::from(self)
+            .neighbor(direction)
+            .map(Self::from)
+    }
+
+    /// Returns position of the neighbour in `direction` in the same layer, moved by the size
+    /// of one node and wrapping around the layer so a neighbour always exists.
+    pub fn wrapping_neighbor(self, direction: Direction) -> Self {
+        LayerPosition::from(self)
+            .wrapping_neighbor(direction)
+            .into()
+    }
+
+    /// Returns this position moved by `offset` in absolute coordinates, wrapping around the layer
+    /// on each axis instead of leaving it, same as [`Add<Offset>`](std::ops::Add) without bounds.
+    ///
+    /// Returns [`None`] if moved position is not aligned to nodes of the same layer.
+    pub fn wrapping_offset<O>(self, offset: O) -> Option<Self>
+    where
+        O: Into<Offset>,
+    {
+        let offset = offset.into();
+        let row_size = T::BIGGEST_ROW_SIZE as isize;
+        let [x, y, z] = [
+            (self.x, offset.dx),
+            (self.y, offset.dy),
+            (self.z, offset.dz),
+        ]
+        .map(|(coordinate, offset)| {
+            (coordinate as isize + offset % row_size).rem_euclid(row_size) as usize
+        });
+        Self::is_valid_position(x, y, z, self.depth).then(|| Self::new(x, y, z, self.depth))
+    }
+
+    /// Returns differences of `x`, `y` and `z` to `other` in leaves, regardless of depths.
+    fn differences(self, other: Self) -> [usize; 3] {
+        [
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        ]
+    }
+
+    /// Returns sum of differences of coordinates to `other`, measured in leaves,
+    /// so positions in different depths can be compared.
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.differences(other).iter().sum()
+    }
+
+    /// Returns the biggest difference of coordinates to `other`, measured in leaves,
+    /// so positions in different depths can be compared.
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        // There are always three differences.
+        *self.differences(other).iter().max().unwrap()
+    }
+
+    /// Returns sum of squared differences of coordinates to `other`, measured in leaves,
+    /// so positions in different depths can be compared.
+    pub fn squared_euclidean_distance(self, other: Self) -> usize {
+        self.differences(other).iter().map(|d| d * d).sum()
+    }
+
+    /// Returns position remapped by `mapping`, flipped coordinates are mirrored around
+    /// the center of the tree.
+    pub fn swizzle(self, mapping: AxisMapping) -> Self {
+        let [x, y, z] = mapping.map(
+            [self.x, self.y, self.z],
+            1 << self.depth,
+            T::BIGGEST_ROW_SIZE,
+        );
+        Self::new(x, y, z, self.depth)
+    }
 }
 
 #[cfg(test)]
@@ -418,7 +753,10 @@ pub(crate) mod node_index_tests {
 
     use std::ops::Add;
 
-    use crate::{LayerIndex, LayerPosition, NodeIndex, NodePosition, Tree};
+    use crate::implemented_tree_sizes::TREE_128;
+    use crate::{
+        CoordinateError, LayerIndex, LayerPosition, NodeIndex, NodePosition, RawNodeIndex, Tree,
+    };
 
     type TestTree = Tree<usize, 73>;
     type TestNodeIndex = NodeIndex<TestTree>;
@@ -426,6 +764,26 @@ pub(crate) mod node_index_tests {
     type TestLayerPosition = LayerPosition<TestTree>;
     type TestLayerIndex = LayerIndex<TestTree>;
 
+    #[test]
+    fn iter_all() {
+        let indexes = TestNodeIndex::iter_all().collect::<Vec<_>>();
+        assert_eq!(indexes.len(), 73);
+        assert_eq!(indexes[0], TestNodeIndex::new(0));
+        assert_eq!(indexes[72], TestNodeIndex::new(72));
+    }
+
+    #[test]
+    fn raw_node_index() {
+        assert_eq!(
+            std::mem::size_of::<TestNodeIndex>(),
+            std::mem::size_of::<RawNodeIndex>()
+        );
+        assert!(RawNodeIndex::try_from(TREE_128).is_ok());
+        let index = NodeIndex::<Tree<usize, TREE_128>>::new(TREE_128 - 1);
+        assert_eq!(index.raw(), TREE_128 - 1);
+        assert_eq!(index - 1, TREE_128 - 2);
+    }
+
     #[test]
     fn is_valid_index() {
         let index = TestNodeIndex::is_valid_index(0);
@@ -560,6 +918,25 @@ pub(crate) mod node_index_tests {
         arr[index];
     }
 
+    #[test]
+    fn new_const() {
+        const INDICES: [TestNodeIndex; 3] = [
+            TestNodeIndex::new(0),
+            TestNodeIndex::new(64),
+            TestNodeIndex::new(72),
+        ];
+        assert_eq!(INDICES[1].raw(), 64);
+        assert!(INDICES[2].is_valid());
+        assert_eq!(
+            TestNodeIndex::new_checked(73),
+            Err(CoordinateError::InvalidIndex {
+                index: 73,
+                size: 73
+            })
+        );
+        assert_eq!(TestNodeIndex::new_checked(72), Ok(TestNodeIndex::new(72)));
+    }
+
     #[test]
     fn copy() {
         let index = TestNodeIndex::new(0);
@@ -570,7 +947,10 @@ pub(crate) mod node_index_tests {
 
 #[cfg(test)]
 pub(crate) mod node_position_tests {
-    use crate::{LayerIndex, LayerPosition, NodeIndex, NodePosition, Tree};
+    use crate::{
+        CoordinateError, Direction, LayerIndex, LayerPosition, NodeIndex, NodePosition, Offset,
+        Tree,
+    };
 
     type TestTree = Tree<usize, 73>;
     type TestNodeIndex = NodeIndex<TestTree>;
@@ -578,6 +958,20 @@ pub(crate) mod node_position_tests {
     type TestLayerPosition = LayerPosition<TestTree>;
     type TestLayerIndex = LayerIndex<TestTree>;
 
+    #[test]
+    fn iter_layer() {
+        let positions = TestNodePosition::iter_layer(1).collect::<Vec<_>>();
+        assert_eq!(positions.len(), 8);
+        assert_eq!(positions[1], TestNodePosition::new(2, 0, 0, 1));
+        assert_eq!(positions[2], TestNodePosition::new(0, 2, 0, 1));
+        for (position, layer_position) in
+            positions.into_iter().zip(TestLayerPosition::iter_layer(1))
+        {
+            assert_eq!(position, TestNodePosition::from(layer_position));
+        }
+        assert_eq!(TestNodePosition::iter_layer(2).count(), 1);
+    }
+
     #[test]
     fn new() {
         TestNodePosition::new(0, 0, 0, 0);
@@ -595,6 +989,55 @@ pub(crate) mod node_position_tests {
         std::panic::catch_unwind(|| TestNodePosition::new(1, 0, 1, 2)).unwrap_err();
     }
 
+    #[test]
+    fn new_checked() {
+        assert_eq!(
+            TestNodePosition::new_checked(2, 2, 0, 1),
+            Ok(TestNodePosition::new(2, 2, 0, 1))
+        );
+        assert_eq!(
+            TestNodePosition::new_checked(0, 4, 0, 0),
+            Err(CoordinateError::InvalidPosition {
+                position: [0, 4, 0],
+                depth: 0,
+                row_size: 4
+            })
+        );
+        assert_eq!(
+            TestNodePosition::new_checked(0, 3, 0, 1),
+            Err(CoordinateError::UnalignedPosition {
+                position: [0, 3, 0],
+                depth: 1,
+                alignment: 2
+            })
+        );
+        assert_eq!(
+            TestNodePosition::new_checked(0, 0, 0, 3),
+            Err(CoordinateError::InvalidDepth { depth: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn new_const() {
+        const CHILD_OFFSETS: [TestNodePosition; 8] = [
+            TestNodePosition::new(0, 0, 0, 0),
+            TestNodePosition::new(1, 0, 0, 0),
+            TestNodePosition::new(0, 1, 0, 0),
+            TestNodePosition::new(1, 1, 0, 0),
+            TestNodePosition::new(0, 0, 1, 0),
+            TestNodePosition::new(1, 0, 1, 0),
+            TestNodePosition::new(0, 1, 1, 0),
+            TestNodePosition::new(1, 1, 1, 0),
+        ];
+        const VALIDITY: [bool; 2] = [
+            TestNodePosition::is_valid_position(2, 2, 2, 1),
+            TestNodePosition::is_valid_position(0, 0, 0, 3),
+        ];
+
+        assert_eq!(CHILD_OFFSETS[7], TestNodePosition::new(1, 1, 1, 0));
+        assert_eq!(VALIDITY, [true, false]);
+    }
+
     #[test]
     fn child_position() {
         let pos = TestNodePosition::new(0, 0, 0, 0);
@@ -631,6 +1074,42 @@ pub(crate) mod node_position_tests {
         );
     }
 
+    #[test]
+    fn common_ancestor() {
+        let (ancestor, depth) = TestNodeIndex::new(0).common_ancestor(TestNodeIndex::new(1));
+        assert_eq!(ancestor, TestNodeIndex::new(64));
+        assert_eq!(depth.get(), 1);
+
+        let (ancestor, depth) = TestNodeIndex::new(0).common_ancestor(TestNodeIndex::new(63));
+        assert_eq!(ancestor, TestNodeIndex::new(72));
+        assert_eq!(depth.get(), 2);
+
+        let (ancestor, _) = TestNodeIndex::new(0).common_ancestor(TestNodeIndex::new(0));
+        assert_eq!(ancestor, TestNodeIndex::new(0));
+        let (ancestor, _) = TestNodeIndex::new(65).common_ancestor(TestNodeIndex::new(3));
+        assert_eq!(ancestor, TestNodeIndex::new(65));
+        let (ancestor, _) = TestNodeIndex::new(66).common_ancestor(TestNodeIndex::new(21));
+        assert_eq!(ancestor, TestNodeIndex::new(72));
+    }
+
+    #[test]
+    fn descendant_anchor() {
+        let pos = TestNodePosition::new(2, 0, 2, 1);
+        assert_eq!(
+            pos.descendant_anchor(0),
+            Some(TestNodePosition::new(2, 0, 2, 0))
+        );
+        assert_eq!(pos.descendant_anchor(1), Some(pos));
+        assert_eq!(pos.descendant_anchor(2), None);
+        assert_eq!(pos.leaf_region(), [2..4, 0..2, 2..4]);
+
+        let root = TestNodeIndex::new(72);
+        assert_eq!(root.descendant_anchor(0), Some(TestNodeIndex::new(0)));
+        assert_eq!(root.descendant_anchor(1), Some(TestNodeIndex::new(64)));
+        assert_eq!(root.leaf_region(), [0..4, 0..4, 0..4]);
+        assert_eq!(TestNodeIndex::new(5).leaf_region(), [1..2, 1..2, 0..1]);
+    }
+
     #[test]
     fn from_node_index() {
         let index = TestNodeIndex::new(0);
@@ -765,4 +1244,122 @@ pub(crate) mod node_position_tests {
             TestNodePosition::new(0, 0, 0, 2)
         );
     }
+
+    #[test]
+    fn checked_into() {
+        let mut position = TestNodePosition::new(2, 0, 2, 1);
+        assert_eq!(
+            position.checked_into(),
+            Ok(TestLayerPosition::new(1, 0, 1, 1))
+        );
+        position.x = 1;
+        assert_eq!(
+            position.checked_into::<TestLayerPosition>(),
+            Err(CoordinateError::UnalignedPosition {
+                position: [1, 0, 2],
+                depth: 1,
+                alignment: 2
+            })
+        );
+        assert!(position.checked_into::<TestNodePosition>().is_err());
+        assert_eq!(
+            TestNodeIndex::new(72).checked_into(),
+            Ok(TestNodePosition::new(0, 0, 0, 2))
+        );
+    }
+
+    #[test]
+    fn wrapping_offset() {
+        let position = TestNodePosition::new(2, 0, 2, 1);
+        assert_eq!(
+            position.wrapping_offset(Offset::new(2, -2, 4)),
+            Some(TestNodePosition::new(0, 2, 2, 1))
+        );
+        assert_eq!(
+            position.wrapping_offset([-6, 0, 0]),
+            Some(TestNodePosition::new(0, 0, 2, 1))
+        );
+        // Result has to be aligned to nodes of the same layer.
+        assert_eq!(position.wrapping_offset([1, 0, 0]), None);
+        assert_eq!(
+            TestNodePosition::new(3, 0, 0, 0).wrapping_offset([1, -1, isize::MIN]),
+            Some(TestNodePosition::new(0, 3, 0, 0))
+        );
+    }
+
+    #[test]
+    fn wrapping_neighbor() {
+        let position = TestNodePosition::new(2, 0, 0, 1);
+        assert_eq!(
+            position.wrapping_neighbor(Direction::Right),
+            TestNodePosition::new(0, 0, 0, 1)
+        );
+        assert_eq!(
+            position.wrapping_neighbor(Direction::Bottom),
+            TestNodePosition::new(2, 2, 0, 1)
+        );
+    }
+
+    #[test]
+    fn align_to() {
+        let leaf = TestNodePosition::new(3, 2, 1, 0);
+        assert_eq!(leaf.align_to(0), Some(leaf));
+        assert_eq!(leaf.align_to(1), Some(TestNodePosition::new(2, 2, 0, 1)));
+        assert_eq!(leaf.align_to(2), Some(TestNodePosition::new(0, 0, 0, 2)));
+        assert_eq!(leaf.align_to(3), None);
+        assert_eq!(TestNodePosition::new(2, 2, 0, 1).align_to(0), None);
+
+        let node = TestNodePosition::new(2, 2, 0, 1);
+        assert!(node.covers(leaf));
+        assert!(node.covers(node));
+        assert!(!node.covers(TestNodePosition::new(1, 2, 1, 0)));
+        assert!(!leaf.covers(node));
+        assert!(TestNodePosition::new(0, 0, 0, 2).covers(leaf));
+    }
+
+    #[test]
+    fn distances() {
+        let a = TestNodePosition::new(1, 2, 3, 0);
+        let b = TestNodePosition::new(2, 0, 2, 1);
+        assert_eq!(a.manhattan_distance(b), 4);
+        assert_eq!(a.chebyshev_distance(b), 2);
+        assert_eq!(a.squared_euclidean_distance(b), 6);
+        assert_eq!(b.manhattan_distance(a), 4);
+        assert_eq!(a.manhattan_distance(a), 0);
+    }
+
+    #[test]
+    fn world_aabb() {
+        let origin = [-1.0, 0.0, 2.0];
+        assert_eq!(
+            TestNodePosition::new(1, 2, 3, 0).world_aabb(origin, 0.5),
+            ([-0.5, 1.0, 3.5], [0.0, 1.5, 4.0])
+        );
+        assert_eq!(
+            TestNodePosition::new(2, 0, 2, 1).world_aabb(origin, 0.5),
+            ([0.0, 0.0, 3.0], [1.0, 1.0, 4.0])
+        );
+        assert_eq!(
+            TestNodePosition::new(0, 0, 0, 2).world_aabb(origin, 0.5),
+            ([-1.0, 0.0, 2.0], [1.0, 2.0, 4.0])
+        );
+    }
+
+    #[test]
+    fn neighbor() {
+        let position = TestNodePosition::new(2, 0, 0, 1);
+        assert_eq!(
+            position.neighbor(Direction::Left),
+            Some(TestNodePosition::new(0, 0, 0, 1))
+        );
+        assert_eq!(
+            position.neighbor(Direction::Back),
+            Some(TestNodePosition::new(2, 0, 2, 1))
+        );
+        assert_eq!(position.neighbor(Direction::Right), None);
+        assert_eq!(
+            TestNodePosition::new(3, 3, 3, 0).neighbor(Direction::Top),
+            None
+        );
+    }
 }
@@ -0,0 +1,319 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::{
+    Children, CoarseTree, DescendFilled, EditScope, EmptyOctants, MicroGrid, Node, NodeIndex,
+    OctantPath, PersistentTree, PositionRange, QueryCache, Region, Surface, Tree, TreeInterface,
+};
+
+/// Read access to nodes of an octree with the layout of [`Tree`].
+///
+/// Algorithms generic over [`OctreeAccess`], such as [`visit`](crate::visit),
+/// [`descend_filled`] or [`iter_region`], can run on any storage which implements it.
+pub trait OctreeAccess {
+    /// Value stored in [`Filled`](Node::Filled) nodes.
+    type Value;
+    /// Tree parameters which describe the layout, usually [`Tree`] itself.
+    type Layout: TreeInterface;
+
+    /// Returns a reference to [`Node`] on `index`.
+    fn node(&self, index: NodeIndex<Self::Layout>) -> &Node<Self::Value>;
+
+    /// Returns a reference to [`Node`] addressed by `path` from the root.
+    ///
+    /// Expects `path` to be no longer than [MAX_DEPTH_INDEX](TreeInterface::MAX_DEPTH_INDEX).
+    fn node_at_path(&self, path: &OctantPath<Self::Layout>) -> &Node<Self::Value> {
+        self.node((*path).into())
+    }
+
+    /// Returns [`index`](NodeIndex) of the root.
+    fn root(&self) -> NodeIndex<Self::Layout> {
+        NodeIndex::new(Self::Layout::SIZE - 1)
+    }
+
+    /// Returns depth of node on `index`, the leaves are in depth 0.
+    fn depth(&self, index: NodeIndex<Self::Layout>) -> usize {
+        index.depth()
+    }
+
+    /// Returns an iterator over children of node on `index`, or [`None`] if it has no children.
+    fn children(&self, index: NodeIndex<Self::Layout>) -> Option<Children<Self::Layout>> {
+        Children::new(index)
+    }
+
+    /// Hints that `children` of a node are going to be read soon, does nothing by default.
+    fn prefetch_children(&self, children: &[NodeIndex<Self::Layout>; 8]) {
+        let _ = children;
+    }
+
+    /// Returns [`MicroGrid`] refining the leaf on `index`, storages without
+    /// [refinements](Tree::refine_leaf) return [`None`] by default.
+    fn refinement(&self, index: NodeIndex<Self::Layout>) -> Option<MicroGrid> {
+        let _ = index;
+        None
+    }
+
+    /// Returns `true` if any leaf is [refined](Tree::refine_leaf), `false` by default.
+    fn has_refinements(&self) -> bool {
+        false
+    }
+}
+
+/// Returns a depth-first iterator over all nodes of `access` which are not
+/// [`Empty`](Node::Empty), starting from the root.
+///
+/// Behaves the same as [`Tree::descend_filled`], but works with any storage implementing
+/// [`OctreeAccess`].
+pub fn descend_filled<A>(access: &A) -> DescendFilled<'_, A>
+where
+    A: OctreeAccess,
+{
+    DescendFilled::new(access)
+}
+
+/// Returns an iterator over values of [`Filled`](Node::Filled) leaves of `access` which share
+/// a face with an [`Empty`](Node::Empty) node or with the border of the layer.
+///
+/// Behaves the same as [`Tree::surface`], but works with any storage implementing
+/// [`OctreeAccess`].
+pub fn surface<A>(access: &A) -> Surface<'_, A>
+where
+    A: OctreeAccess,
+{
+    Surface::new(access)
+}
+
+/// Returns an iterator over the biggest [`Empty`](Node::Empty) nodes of `access` with `depth`
+/// at least `min_depth`.
+///
+/// Behaves the same as [`Tree::iter_empty_octants`], but works with any storage implementing
+/// [`OctreeAccess`].
+pub fn iter_empty_octants<A>(access: &A, min_depth: usize) -> EmptyOctants<'_, A>
+where
+    A: OctreeAccess,
+{
+    EmptyOctants::new(access, min_depth)
+}
+
+/// Returns an iterator over [`nodes`](Node) of `access` inside of an axis-aligned box
+/// of one layer.
+///
+/// Behaves the same as [`Tree::iter_region`], but works with any storage implementing
+/// [`OctreeAccess`].
+pub fn iter_region<A, R>(access: &A, range: R) -> Region<'_, A>
+where
+    A: OctreeAccess,
+    R: Into<PositionRange<A::Layout>>,
+{
+    Region::new(access, range.into())
+}
+
+impl<T, const SIZE: usize> OctreeAccess for Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    type Value = T;
+    type Layout = Self;
+
+    fn node(&self, index: NodeIndex<Self>) -> &Node<T> {
+        self.get(index)
+    }
+
+    fn prefetch_children(&self, children: &[NodeIndex<Self>; 8]) {
+        Tree::prefetch_children(self, children);
+    }
+
+    fn refinement(&self, index: NodeIndex<Self>) -> Option<MicroGrid> {
+        Tree::refinement(self, index)
+    }
+
+    fn has_refinements(&self) -> bool {
+        !self.refinements.is_empty()
+    }
+}
+
+impl<'a, T, const SIZE: usize> OctreeAccess for EditScope<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Value = T;
+    type Layout = Tree<T, SIZE>;
+
+    fn node(&self, index: NodeIndex<Tree<T, SIZE>>) -> &Node<T> {
+        self.get(index)
+    }
+
+    fn refinement(&self, index: NodeIndex<Tree<T, SIZE>>) -> Option<MicroGrid> {
+        Tree::refinement(self, index)
+    }
+
+    fn has_refinements(&self) -> bool {
+        !self.refinements.is_empty()
+    }
+}
+
+impl<T, K, V, const SIZE: usize> OctreeAccess for QueryCache<T, K, V, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+    K: Hash + Eq,
+{
+    type Value = T;
+    type Layout = Tree<T, SIZE>;
+
+    fn node(&self, index: NodeIndex<Tree<T, SIZE>>) -> &Node<T> {
+        self.tree().get(index)
+    }
+
+    fn refinement(&self, index: NodeIndex<Tree<T, SIZE>>) -> Option<MicroGrid> {
+        self.tree().refinement(index)
+    }
+
+    fn has_refinements(&self) -> bool {
+        !self.tree().refinements.is_empty()
+    }
+}
+
+impl<T, const SIZE: usize> OctreeAccess for PersistentTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone,
+{
+    type Value = T;
+    type Layout = Tree<T, SIZE>;
+
+    fn node(&self, index: NodeIndex<Tree<T, SIZE>>) -> &Node<T> {
+        self.get(index)
+    }
+
+    fn refinement(&self, index: NodeIndex<Tree<T, SIZE>>) -> Option<MicroGrid> {
+        PersistentTree::refinement(self, index)
+    }
+
+    fn has_refinements(&self) -> bool {
+        !self.refinements.is_empty()
+    }
+}
+
+/// Nodes shallower than [`min_depth`](CoarseTree::min_depth) are read from their ancestor
+/// in `min_depth`, which is treated as a leaf without children.
+impl<T, const SIZE: usize> OctreeAccess for CoarseTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Value = T;
+    type Layout = Tree<T, SIZE>;
+
+    fn node(&self, index: NodeIndex<Tree<T, SIZE>>) -> &Node<T> {
+        self.get_covering(index).1
+    }
+
+    fn children(&self, index: NodeIndex<Tree<T, SIZE>>) -> Option<Children<Tree<T, SIZE>>> {
+        if index.depth() <= self.min_depth() {
+            return None;
+        }
+        Children::new(index)
+    }
+}
+
+#[cfg(test)]
+mod access_tests {
+    use crate::{
+        descend_filled, downsample_region, iter_empty_octants, iter_region, may_contain_value,
+        surface, LayerPosition, Node, NodeIndex, OctantPath, PersistentTree, QueryCache, Tree,
+    };
+
+    use super::OctreeAccess;
+
+    type TestTree = Tree<usize, 73>;
+
+    fn count_filled<A: OctreeAccess>(access: &A) -> usize {
+        let mut stack = vec![access.root()];
+        let mut count = 0;
+        while let Some(index) = stack.pop() {
+            if let Node::Filled(_) = access.node(index) {
+                count += 1;
+            }
+            stack.extend(access.children(index).into_iter().flatten());
+        }
+        count
+    }
+
+    #[test]
+    fn octree_access() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(3), Node::Filled(3));
+        tree.set(NodeIndex::new(72), Node::Filled(72));
+        assert_eq!(tree.root(), NodeIndex::new(72));
+        assert_eq!(OctreeAccess::depth(&tree, NodeIndex::new(64)), 1);
+        assert_eq!(count_filled(&tree), 2);
+
+        let mut path = OctantPath::root();
+        assert_eq!(tree.node_at_path(&path), &Node::Filled(72));
+        path.push(1);
+        path.push(1);
+        assert_eq!(tree.node_at_path(&path), &Node::Filled(3));
+
+        let mut scope = tree.scoped_edit();
+        scope.set(NodeIndex::new(4), Node::Filled(4));
+        assert_eq!(count_filled(&scope), 3);
+        drop(scope);
+
+        let cache = QueryCache::<_, (), (), 73>::new(tree);
+        assert_eq!(count_filled(&cache), 2);
+    }
+
+    fn built_tree() -> TestTree {
+        let mut tree = TestTree::new();
+        for (x, y, z) in [(0, 0, 0), (1, 0, 0), (3, 2, 1), (2, 3, 3)] {
+            tree.set(LayerPosition::new(x, y, z, 0), Node::Filled(x + y + z));
+        }
+        tree.build(|nodes| {
+            if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+                Node::Empty
+            } else {
+                Node::Reduced
+            }
+        });
+        tree
+    }
+
+    #[test]
+    fn persistent_traversals() {
+        let tree = built_tree();
+        let persistent = PersistentTree::from_tree(&tree);
+
+        assert!(descend_filled(&persistent).eq(tree.descend_filled()));
+        assert!(surface(&persistent).eq(tree.surface()));
+        assert!(iter_empty_octants(&persistent, 1).eq(tree.iter_empty_octants(1)));
+        let range = LayerPosition::new(0, 0, 0, 0)..=LayerPosition::new(3, 1, 1, 0);
+        assert!(iter_region(&persistent, range.clone()).eq(tree.iter_region(range)));
+
+        let count =
+            |nodes: &[&Node<usize>]| nodes.iter().filter(|node| ***node != Node::Empty).count();
+        assert_eq!(
+            downsample_region(&persistent, [0..4, 0..4, 0..4], [2, 2, 2], count),
+            tree.downsample_region([0..4, 0..4, 0..4], [2, 2, 2], count)
+        );
+        assert!(may_contain_value(&persistent, &6, [2..4, 2..4, 0..2]));
+        assert!(!may_contain_value(&persistent, &6, [0..4, 0..4, 2..4]));
+        assert!(!may_contain_value(&persistent, &5, [0..4, 0..4, 0..4]));
+    }
+
+    #[test]
+    fn coarse_traversals() {
+        let tree = built_tree();
+        let coarse = tree.clone_coarse(1);
+
+        assert_eq!(coarse.node(NodeIndex::new(0)), tree.get(NodeIndex::new(64)));
+        assert!(coarse.children(NodeIndex::new(64)).is_none());
+        assert!(coarse.children(NodeIndex::new(72)).is_some());
+        assert!(descend_filled(&coarse).eq(tree
+            .descend_filled()
+            .filter(|(index, _)| index.depth() >= 1)));
+        assert!(iter_empty_octants(&coarse, 1).eq(tree.iter_empty_octants(1)));
+    }
+}
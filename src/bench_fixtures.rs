@@ -0,0 +1,79 @@
+//! Workloads used by benchmarks of this crate.
+//!
+//! They are public behind `bench-fixtures` feature, so changes made downstream can be measured
+//! against the same trees and positions. Fixtures are deterministic and not covered by semver
+//! compatibility.
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Combines children into [`Empty`](Node::Empty) or [`Filled`](Node::Filled) node if they are
+/// all the same, otherwise into [`Reduced`](Node::Reduced).
+pub fn combine_rule(nodes: &[&Node<u32>]) -> Node<u32> {
+    if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+        Node::Empty
+    } else if nodes.iter().all(|node| matches!(node, Node::Filled(_))) {
+        Node::Filled(1)
+    } else {
+        Node::Reduced
+    }
+}
+
+/// Returns a built [`Tree`] with leaves filled below a wavy surface,
+/// so it has both solid and empty regions.
+pub fn terrain<const SIZE: usize>() -> Tree<u32, SIZE>
+where
+    Tree<u32, SIZE>: TreeInterface,
+{
+    let mut tree = Tree::<u32, SIZE>::new();
+    let size = Tree::<u32, SIZE>::LEAF_ROW_LEN;
+    for index in 0..Tree::<u32, SIZE>::LEAF_LAYER_LEN {
+        let (x, y, z) = (index % size, index / size % size, index / size / size);
+        let height = size / 2 + (x * 7 + z * 13) % 17;
+        if y < height {
+            tree.set(NodeIndex::new(index), Node::Filled(1));
+        }
+    }
+    tree.build(combine_rule);
+    tree
+}
+
+/// Returns `count` pseudo random [indexes](NodeIndex) spread over the whole tree.
+///
+/// The same `count` always returns the same indexes.
+pub fn scattered_indexes<U>(count: usize) -> Vec<NodeIndex<U>>
+where
+    U: TreeInterface,
+{
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    (0..count)
+        .map(|_| {
+            // Linear congruential generator, quality of randomness does not matter here.
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            NodeIndex::new((state >> 33) as usize % U::SIZE)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod bench_fixtures_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    use super::{scattered_indexes, terrain};
+
+    type TestTree = Tree<u32, 4681>;
+
+    #[test]
+    fn fixtures() {
+        let tree = terrain::<4681>();
+        assert_eq!(tree.get(NodeIndex::new(0)), &Node::Filled(1));
+        assert_eq!(tree.get(NodeIndex::new(255)), &Node::Empty);
+        assert_eq!(tree.get(NodeIndex::new(4680)), &Node::Reduced);
+
+        let indexes = scattered_indexes::<TestTree>(100);
+        assert_eq!(indexes.len(), 100);
+        assert_eq!(indexes, scattered_indexes::<TestTree>(100));
+        assert!(indexes.iter().any(|index| index.raw() != indexes[0].raw()));
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt::Debug;
+
+use crate::{Node, Tree, TreeInterface};
+
+/// Values which can be linearly interpolated, used by [`Tree::lerp`] and [`Tree::blend_from`].
+pub trait Lerp {
+    /// Returns value between `a` and `b`, where `t` of `0.0` returns `a` and `1.0` returns `b`.
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl Lerp for f64 {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        a + (b - a) * t as f64
+    }
+}
+
+/// Implements [`Lerp`] for integers, interpolated values are rounded to the nearest integer.
+macro_rules! impl_lerp_for_integers {
+    ($($integer:ty),*) => {
+        $(
+            impl Lerp for $integer {
+                fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+                    (*a as f64 + (*b as f64 - *a as f64) * t as f64).round() as $integer
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp_for_integers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Returns `a` blended with `b` by `t`, [`Empty`](Node::Empty) nodes are treated as `empty`.
+///
+/// Two [`Empty`](Node::Empty) nodes stay empty and any [`Reduced`](Node::Reduced) node
+/// makes the result [`Reduced`](Node::Reduced).
+fn blend_nodes<T>(a: &Node<T>, b: &Node<T>, t: f32, empty: &T) -> Node<T>
+where
+    T: Lerp,
+{
+    match (a, b) {
+        (Node::Empty, Node::Empty) => Node::Empty,
+        (Node::Reduced, _) | (_, Node::Reduced) => Node::Reduced,
+        (Node::Filled(a), Node::Filled(b)) => Node::Filled(T::lerp(a, b, t)),
+        (Node::Filled(a), Node::Empty) => Node::Filled(T::lerp(a, empty, t)),
+        (Node::Empty, Node::Filled(b)) => Node::Filled(T::lerp(empty, b, t)),
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Returns a new [`Tree`] with every node blended from nodes of `a` and `b` on the same
+    /// position by `t`, where `t` of `0.0` returns nodes of `a` and `1.0` nodes of `b`.
+    ///
+    /// [`Empty`](Node::Empty) node blended with a [`Filled`](Node::Filled) one is treated as
+    /// holding `empty`. Two empty nodes stay empty and [`Reduced`](Node::Reduced) nodes make
+    /// the result reduced, so deeper layers may need to be [built](Tree::build) again.
+    pub fn lerp(a: &Self, b: &Self, t: f32, empty: &T) -> Self
+    where
+        T: Lerp + Clone,
+    {
+        let mut tree = Self::new();
+        for ((node, a), b) in tree
+            .stored
+            .iter_mut()
+            .zip(a.stored.iter())
+            .zip(b.stored.iter())
+        {
+            *node = blend_nodes(a, b, t, empty);
+        }
+        tree
+    }
+
+    /// Blends every node with node of `other` on the same position by `t`, in the same way as
+    /// [`lerp`](Tree::lerp) with this tree as `a`.
+    ///
+    /// Changes are not recorded as tombstones.
+    pub fn blend_from(&mut self, other: &Self, t: f32, empty: &T)
+    where
+        T: Lerp,
+    {
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+        for (node, other) in self.stored.iter_mut().zip(other.stored.iter()) {
+            *node = blend_nodes(node, other, t, empty);
+        }
+    }
+}
+
+#[cfg(test)]
+mod blend_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    use super::Lerp;
+
+    type TestTree = Tree<f32, 73>;
+
+    #[test]
+    fn lerp_values() {
+        assert_eq!(f32::lerp(&1.0, &3.0, 0.5), 2.0);
+        assert_eq!(f64::lerp(&1.0, &3.0, 0.25), 1.5);
+        assert_eq!(u8::lerp(&0, &255, 0.5), 128);
+        assert_eq!(i32::lerp(&10, &-10, 0.75), -5);
+    }
+
+    #[test]
+    fn lerp() {
+        let mut a = TestTree::new();
+        let mut b = TestTree::new();
+        a.set(NodeIndex::new(0), Node::Filled(2.0));
+        b.set(NodeIndex::new(0), Node::Filled(4.0));
+        a.set(NodeIndex::new(1), Node::Filled(2.0));
+        b.set(NodeIndex::new(2), Node::Filled(4.0));
+        a.set(NodeIndex::new(64), Node::Reduced);
+        b.set(NodeIndex::new(64), Node::Filled(1.0));
+
+        let blended = TestTree::lerp(&a, &b, 0.5, &0.0);
+        assert_eq!(blended.get(NodeIndex::new(0)), &Node::Filled(3.0));
+        assert_eq!(blended.get(NodeIndex::new(1)), &Node::Filled(1.0));
+        assert_eq!(blended.get(NodeIndex::new(2)), &Node::Filled(2.0));
+        assert_eq!(blended.get(NodeIndex::new(3)), &Node::Empty);
+        assert_eq!(blended.get(NodeIndex::new(64)), &Node::Reduced);
+
+        assert_eq!(TestTree::lerp(&a, &b, 0.0, &0.0), {
+            let mut a = a.clone();
+            a.set(NodeIndex::new(2), Node::Filled(0.0));
+            a
+        });
+
+        a.blend_from(&b, 0.5, &0.0);
+        assert_eq!(a, blended);
+    }
+}
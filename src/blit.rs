@@ -0,0 +1,157 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::{Node, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Copies nodes of the shallowest layer of `source` inside `region` into this [`Tree`],
+    /// mapping each of them by `map`.
+    ///
+    /// `region` holds ranges of `x`, `y` and `z` in `source` and node on `(x, y, z)` is copied
+    /// to `(x, y, z)` shifted by `offset`. Parts of `region` which are outside of either tree
+    /// are skipped. Only the shallowest layer is changed, upper layers need to be
+    /// [built](Tree::build) again. Copied nodes are not recorded as tombstones.
+    /// [Refinements](Tree::refine_leaf) of copied leaves replace those in this [`Tree`].
+    pub fn blit_from<U, F>(
+        &mut self,
+        source: &Tree<U, SIZE>,
+        region: [Range<usize>; 3],
+        offset: [isize; 3],
+        map: F,
+    ) where
+        F: Fn(&U) -> Node<T>,
+    {
+        let extent = Self::leaf_extent();
+        // Clips `region` on every axis to source coordinates which land inside this tree.
+        let [x, y, z] = [0, 1, 2].map(|axis| {
+            let range = &region[axis];
+            let size = extent[axis].end as isize;
+            let start = (range.start as isize).max(-offset[axis]).max(0);
+            let end = (range.end as isize).min(size - offset[axis]).min(size);
+            start..end.max(start)
+        });
+        if x.is_empty() {
+            return;
+        }
+        let len = (x.end - x.start) as usize;
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate(0);
+        }
+        self.invalidate_value_filters();
+        let row_size = Self::row_size(0);
+
+        for source_z in z {
+            for source_y in y.clone() {
+                let source_start =
+                    row_size.stride(source_y as usize, source_z as usize) + x.start as usize;
+                let destination_start = row_size.stride(
+                    (source_y + offset[1]) as usize,
+                    (source_z + offset[2]) as usize,
+                ) + (x.start + offset[0]) as usize;
+
+                let source_row = &source.stored[source_start..source_start + len];
+                let destination_row = &mut self.stored[destination_start..destination_start + len];
+                for (destination, source) in destination_row.iter_mut().zip(source_row) {
+                    *destination = match source {
+                        Node::Filled(value) => map(value),
+                        Node::Reduced => Node::Reduced,
+                        Node::Empty => Node::Empty,
+                    };
+                }
+                if !self.refinements.is_empty() || !source.refinements.is_empty() {
+                    for i in 0..len {
+                        match source.refinements.get(&(source_start + i)) {
+                            Some(grid) => self.refinements.insert(destination_start + i, *grid),
+                            None => self.refinements.remove(&(destination_start + i)),
+                        };
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod blit_tests {
+    use crate::{LayerPosition, MicroGrid, Node, Tree};
+
+    #[test]
+    fn blit_from() {
+        let mut source = Tree::<u8, 73>::new();
+        source.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        source.set(LayerPosition::new(1, 1, 1, 0), Node::Filled(2));
+        source.set(LayerPosition::new(3, 0, 0, 0), Node::Reduced);
+
+        let mut tree = Tree::<String, 73>::new();
+        tree.set(
+            LayerPosition::new(2, 2, 2, 0),
+            Node::Filled("kept".to_string()),
+        );
+        tree.blit_from(&source, [0..2, 0..2, 0..2], [2, 1, 0], |value| {
+            Node::Filled(value.to_string())
+        });
+
+        assert_eq!(
+            tree.get(LayerPosition::new(2, 1, 0, 0)),
+            &Node::Filled("1".to_string())
+        );
+        assert_eq!(
+            tree.get(LayerPosition::new(3, 2, 1, 0)),
+            &Node::Filled("2".to_string())
+        );
+        assert_eq!(
+            tree.get(LayerPosition::new(2, 2, 2, 0)),
+            &Node::Filled("kept".to_string())
+        );
+        assert_eq!(tree.filled().count(), 3);
+    }
+
+    #[test]
+    fn blit_from_clipped() {
+        let mut source = Tree::<u8, 73>::new();
+        source.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        source.set(LayerPosition::new(3, 3, 3, 0), Node::Filled(2));
+        source.set(LayerPosition::new(3, 0, 0, 0), Node::Reduced);
+
+        let mut tree = Tree::<u8, 73>::new();
+        tree.blit_from(&source, [0..10, 0..10, 0..10], [-3, 0, 0], |value| {
+            Node::Filled(value * 10)
+        });
+        assert_eq!(tree.get(LayerPosition::new(0, 0, 0, 0)), &Node::Reduced);
+        assert_eq!(tree.get(LayerPosition::new(0, 3, 3, 0)), &Node::Filled(20));
+        assert_eq!(tree.filled().count(), 1);
+
+        let mut tree = Tree::<u8, 73>::new();
+        tree.blit_from(&source, [0..4, 0..4, 0..4], [4, 0, 0], |value| {
+            Node::Filled(*value)
+        });
+        assert_eq!(tree, Tree::new());
+    }
+
+    #[test]
+    fn blit_from_refinements() {
+        let mut source = Tree::<u8, 73>::new();
+        source.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        source.refine_leaf(LayerPosition::new(0, 0, 0, 0), MicroGrid::new(1));
+
+        let mut tree = Tree::<u8, 73>::new();
+        tree.refine_leaf(LayerPosition::new(2, 0, 0, 0), MicroGrid::FULL);
+        tree.refine_leaf(LayerPosition::new(3, 3, 3, 0), MicroGrid::FULL);
+        tree.blit_from(&source, [0..2, 0..1, 0..1], [1, 0, 0], |value| {
+            Node::Filled(*value)
+        });
+        assert_eq!(
+            tree.refinement(LayerPosition::new(1, 0, 0, 0)),
+            Some(MicroGrid::new(1))
+        );
+        assert_eq!(tree.refinement(LayerPosition::new(2, 0, 0, 0)), None);
+        assert_eq!(
+            tree.refinement(LayerPosition::new(3, 3, 3, 0)),
+            Some(MicroGrid::FULL)
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::ops::Range;
+
+use crate::io::{
+    read_node, read_refinements, read_size, write_node, write_refinements, write_size,
+};
+use crate::{LayerPosition, MicroGrid, Node, NodeIndex, Tree, TreeInterface, OCTANT_OFFSETS};
+
+/// Raw indexes of refined leaves mapped to their [`MicroGrid`].
+type Refinements = BTreeMap<usize, MicroGrid>;
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Serializes [`Tree`] into `writer` split into blocks, one for the root and one for each
+    /// octant of the root, writing values by `encode`.
+    ///
+    /// Header holds length of every block, so blocks can be read independently by
+    /// [`read_octant`](Tree::read_octant). Every block also holds [refinements](Tree::refine_leaf)
+    /// of its leaves. With `rayon` feature blocks are encoded in parallel.
+    pub fn write_blocks<W, F>(&self, writer: &mut W, encode: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T, &mut Vec<u8>) -> io::Result<()> + Sync,
+        T: Sync,
+    {
+        let blocks = map_blocks(Self::octants().collect(), |octant| {
+            let mut block = Vec::new();
+            for position in Self::octant_positions(octant) {
+                write_node(&mut block, self.get(position), &encode)?;
+            }
+            let refinements = self
+                .refinements
+                .iter()
+                .filter(|(index, _)| Self::leaf_octant(**index) == octant)
+                .collect::<Vec<_>>();
+            write_refinements(&mut block, refinements)?;
+            Ok(block)
+        })
+        .into_iter()
+        .collect::<io::Result<Vec<_>>>()?;
+
+        let mut root = Vec::new();
+        write_node(&mut root, self.get(NodeIndex::new(SIZE - 1)), &encode)?;
+        // The root has a refinement only if it is also the only leaf.
+        let refinements = self.refinements.range(SIZE - 1..).collect::<Vec<_>>();
+        write_refinements(&mut root, refinements)?;
+
+        write_size::<_, SIZE>(writer)?;
+        writer.write_all(&[blocks.len() as u8])?;
+        for block in std::iter::once(&root).chain(&blocks) {
+            writer.write_all(&(block.len() as u64).to_le_bytes())?;
+        }
+        for block in std::iter::once(&root).chain(&blocks) {
+            writer.write_all(block)?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes [`Tree`] written by [`write_blocks`](Tree::write_blocks) from `reader`,
+    /// reading values by `decode`.
+    ///
+    /// With `rayon` feature blocks are decoded in parallel.
+    pub fn read_blocks<R, F>(reader: &mut R, decode: F) -> io::Result<Self>
+    where
+        R: Read,
+        F: Fn(&mut &[u8]) -> io::Result<T> + Sync,
+        T: Clone + Send,
+    {
+        let (lengths, root, refinements) = Self::read_header(reader, &decode)?;
+        let mut blocks = Vec::with_capacity(lengths.len());
+        for length in lengths {
+            let mut block = vec![0; length];
+            reader.read_exact(&mut block)?;
+            blocks.push(block);
+        }
+
+        let mut tree = Self::new();
+        tree.set(NodeIndex::new(SIZE - 1), root);
+        tree.refinements = refinements;
+        let blocks = map_blocks(
+            blocks.into_iter().enumerate().collect(),
+            |(octant, block)| Self::decode_block(octant, &block, &decode),
+        );
+        for (octant, block) in blocks.into_iter().enumerate() {
+            let (nodes, refinements) = block?;
+            for (position, node) in Self::octant_positions(octant).zip(nodes) {
+                tree.set(position, node);
+            }
+            tree.refinements.extend(refinements);
+        }
+        Ok(tree)
+    }
+
+    /// Deserializes the root and only nodes in `octant` of the root from [`Tree`] written by
+    /// [`write_blocks`](Tree::write_blocks), reading values by `decode`.
+    ///
+    /// Blocks of other octants are skipped and their nodes are [`Empty`](Node::Empty).
+    /// Octants are ordered in the same way as [`children`](Tree::children) returns them.
+    pub fn read_octant<R, F>(reader: &mut R, octant: usize, decode: F) -> io::Result<Self>
+    where
+        R: Read + Seek,
+        F: Fn(&mut &[u8]) -> io::Result<T>,
+        T: Clone,
+    {
+        let (lengths, root, refinements) = Self::read_header(reader, &decode)?;
+        if octant >= lengths.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "octant is not stored",
+            ));
+        }
+        let skipped: usize = lengths[..octant].iter().sum();
+        reader.seek(SeekFrom::Current(skipped as i64))?;
+        let mut block = vec![0; lengths[octant]];
+        reader.read_exact(&mut block)?;
+
+        let mut tree = Self::new();
+        tree.set(NodeIndex::new(SIZE - 1), root);
+        let (nodes, octant_refinements) = Self::decode_block(octant, &block, &decode)?;
+        for (position, node) in Self::octant_positions(octant).zip(nodes) {
+            tree.set(position, node);
+        }
+        tree.refinements = refinements;
+        tree.refinements.extend(octant_refinements);
+        Ok(tree)
+    }
+
+    /// Reads header written by [`write_blocks`](Tree::write_blocks), returning lengths of all
+    /// blocks, the root and its refinement.
+    fn read_header<R, F>(
+        reader: &mut R,
+        decode: F,
+    ) -> io::Result<(Vec<usize>, Node<T>, Refinements)>
+    where
+        R: Read,
+        F: Fn(&mut &[u8]) -> io::Result<T>,
+    {
+        read_size::<_, SIZE>(reader)?;
+        let mut count = [0];
+        reader.read_exact(&mut count)?;
+        if count[0] as usize != Self::octants().len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "amount of blocks does not match",
+            ));
+        }
+        // The root is stored as the first block.
+        let mut lengths = Vec::with_capacity(count[0] as usize + 1);
+        for _ in 0..=count[0] {
+            let mut length = [0; 8];
+            reader.read_exact(&mut length)?;
+            lengths.push(u64::from_le_bytes(length) as usize);
+        }
+
+        let mut root = vec![0; lengths.remove(0)];
+        reader.read_exact(&mut root)?;
+        let mut root = root.as_slice();
+        let node = read_node(&mut root, decode)?;
+        let refinements = read_refinements(&mut root, Self::LEAF_LAYER_LEN)?;
+        if refinements.keys().any(|index| *index != SIZE - 1) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "refined node is not in the block",
+            ));
+        }
+        if !root.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "block length does not match",
+            ));
+        }
+        Ok((lengths, node, refinements))
+    }
+
+    /// Decodes all nodes of `octant` and refinements of its leaves from `block`.
+    fn decode_block<F>(
+        octant: usize,
+        block: &[u8],
+        decode: F,
+    ) -> io::Result<(Vec<Node<T>>, Refinements)>
+    where
+        F: Fn(&mut &[u8]) -> io::Result<T>,
+    {
+        let mut reader = block;
+        let nodes = Self::octant_positions(octant)
+            .map(|_| read_node(&mut reader, &decode))
+            .collect::<io::Result<Vec<_>>>()?;
+        let refinements = read_refinements(&mut reader, Self::LEAF_LAYER_LEN)?;
+        if refinements
+            .keys()
+            .any(|index| Self::leaf_octant(*index) != octant)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "refined node is not in the block",
+            ));
+        }
+        if !reader.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "block length does not match",
+            ));
+        }
+        Ok((nodes, refinements))
+    }
+
+    /// Returns octants of the root, which is none if the root has no children.
+    fn octants() -> Range<usize> {
+        if Self::MAX_DEPTH_INDEX == 0 {
+            0..0
+        } else {
+            0..8
+        }
+    }
+
+    /// Returns octant of the root holding leaf on raw `index`.
+    ///
+    /// Expects the root to have children.
+    fn leaf_octant(index: usize) -> usize {
+        let half = Self::row_size(0).get() / 2;
+        let position = LayerPosition::<Self>::from(NodeIndex::new(index));
+        let offsets = [position.x, position.y, position.z].map(|coordinate| coordinate / half);
+        // Every combination of offsets is one of the octants.
+        OCTANT_OFFSETS
+            .iter()
+            .position(|octant| *octant == offsets)
+            .unwrap()
+    }
+
+    /// Returns positions of all nodes in `octant` of the root, from the shallowest layer.
+    fn octant_positions(octant: usize) -> impl Iterator<Item = LayerPosition<Self>> {
+        (0..Self::MAX_DEPTH_INDEX).flat_map(move |depth| {
+            let half = Self::row_size(depth).get() / 2;
+            let [x, y, z] = OCTANT_OFFSETS[octant].map(|offset| offset * half);
+            (z..z + half).flat_map(move |z| {
+                (y..y + half).flat_map(move |y| {
+                    (x..x + half).map(move |x| LayerPosition::new(x, y, z, depth))
+                })
+            })
+        })
+    }
+}
+
+/// Applies `f` to every item, in parallel when `rayon` feature is enabled.
+fn map_blocks<I, O, F>(items: Vec<I>, f: F) -> Vec<O>
+where
+    I: Send,
+    O: Send,
+    F: Fn(I) -> O + Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+#[cfg(test)]
+mod block_io_tests {
+    use std::io::{Cursor, Read, Write};
+
+    use crate::{LayerPosition, MicroGrid, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<u8, 585>;
+
+    fn encode(value: &u8, writer: &mut Vec<u8>) -> std::io::Result<()> {
+        writer.write_all(&[*value])
+    }
+
+    fn decode(reader: &mut &[u8]) -> std::io::Result<u8> {
+        let mut value = [0];
+        reader.read_exact(&mut value)?;
+        Ok(value[0])
+    }
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        tree.set(LayerPosition::new(7, 0, 0, 0), Node::Filled(2));
+        tree.set(LayerPosition::new(7, 7, 7, 0), Node::Reduced);
+        tree.set(LayerPosition::new(3, 3, 3, 1), Node::Filled(3));
+        tree.set(NodeIndex::new(584), Node::Filled(4));
+        tree
+    }
+
+    #[test]
+    fn read_blocks() {
+        let tree = tree();
+        let mut bytes = Vec::new();
+        tree.write_blocks(&mut bytes, encode).unwrap();
+        let read = TestTree::read_blocks(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+
+        let tree = Tree::<u8, 1>::new();
+        let mut bytes = Vec::new();
+        tree.write_blocks(&mut bytes, encode).unwrap();
+        let read = Tree::<u8, 1>::read_blocks(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn thread_count_independence() {
+        let tree = tree();
+        let write = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let mut bytes = Vec::new();
+            pool.install(|| tree.write_blocks(&mut bytes, encode).unwrap());
+            bytes
+        };
+        assert_eq!(write(1), write(4));
+    }
+
+    #[test]
+    fn read_octant() {
+        let tree = tree();
+        let mut bytes = Vec::new();
+        tree.write_blocks(&mut bytes, encode).unwrap();
+
+        let read = TestTree::read_octant(&mut Cursor::new(&bytes), 1, decode).unwrap();
+        assert_eq!(read.get(NodeIndex::new(584)), &Node::Filled(4));
+        assert_eq!(read.get(LayerPosition::new(7, 0, 0, 0)), &Node::Filled(2));
+        assert_eq!(read.get(LayerPosition::new(0, 0, 0, 0)), &Node::Empty);
+        assert_eq!(read.filled().count(), 2);
+
+        let read = TestTree::read_octant(&mut Cursor::new(&bytes), 7, decode).unwrap();
+        assert_eq!(read.get(LayerPosition::new(7, 7, 7, 0)), &Node::Reduced);
+        assert_eq!(read.get(LayerPosition::new(3, 3, 3, 1)), &Node::Filled(3));
+
+        assert!(TestTree::read_octant(&mut Cursor::new(&bytes), 8, decode).is_err());
+    }
+
+    #[test]
+    fn refinements() {
+        let mut tree = tree();
+        tree.refine_leaf(LayerPosition::new(0, 0, 0, 0), MicroGrid::new(1));
+        tree.refine_leaf(LayerPosition::new(7, 0, 0, 0), MicroGrid::new(2));
+        let mut bytes = Vec::new();
+        tree.write_blocks(&mut bytes, encode).unwrap();
+        let read = TestTree::read_blocks(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+
+        let read = TestTree::read_octant(&mut Cursor::new(&bytes), 1, decode).unwrap();
+        assert_eq!(
+            read.refinement(LayerPosition::new(7, 0, 0, 0)),
+            Some(MicroGrid::new(2))
+        );
+        assert_eq!(read.refinement(LayerPosition::new(0, 0, 0, 0)), None);
+
+        let mut tree = Tree::<u8, 1>::new();
+        tree.refine_leaf(NodeIndex::new(0), MicroGrid::FULL);
+        let mut bytes = Vec::new();
+        tree.write_blocks(&mut bytes, encode).unwrap();
+        let read = Tree::<u8, 1>::read_blocks(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+    }
+
+    #[test]
+    fn invalid_blocks() {
+        let mut bytes = Vec::new();
+        tree().write_blocks(&mut bytes, encode).unwrap();
+        assert!(Tree::<u8, 73>::read_blocks(&mut bytes.as_slice(), decode).is_err());
+
+        bytes.pop();
+        assert!(TestTree::read_blocks(&mut bytes.as_slice(), decode).is_err());
+    }
+}
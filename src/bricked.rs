@@ -0,0 +1,168 @@
+use std::fmt::Debug;
+
+use crate::{LayerPosition, Node, Tree, TreeInterface};
+
+/// Shallowest layer of [`Tree`] stored as cubic bricks with row size `BRICK`, where all nodes
+/// of one brick are stored next to each other.
+///
+/// Bricks are ordered the same way as nodes in a layer, i.e. `x` changes the fastest, and nodes
+/// inside of a brick as well. When the tree is smaller than `BRICK` the whole layer is one brick.
+#[derive(Debug, Clone)]
+pub struct BrickedLeaves<T, const SIZE: usize, const BRICK: usize> {
+    stored: Box<[Node<T>]>,
+}
+
+impl<T, const SIZE: usize, const BRICK: usize> BrickedLeaves<T, SIZE, BRICK>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    /// Row size of one brick, which is `BRICK` unless the tree is smaller.
+    pub const BRICK_ROW_SIZE: usize = {
+        assert!(BRICK.is_power_of_two());
+        if BRICK < Tree::<T, SIZE>::BIGGEST_ROW_SIZE {
+            BRICK
+        } else {
+            Tree::<T, SIZE>::BIGGEST_ROW_SIZE
+        }
+    };
+    /// Amount of nodes in one brick.
+    pub const BRICK_SIZE: usize =
+        Self::BRICK_ROW_SIZE * Self::BRICK_ROW_SIZE * Self::BRICK_ROW_SIZE;
+    /// Amount of bricks in one row of the layer.
+    const BRICKS_IN_ROW: usize = Tree::<T, SIZE>::BIGGEST_ROW_SIZE / Self::BRICK_ROW_SIZE;
+
+    /// Creates a new [`BrickedLeaves`] with all [`nodes`](Node) set to [`Empty`](Node::Empty).
+    pub fn new() -> Self {
+        Self {
+            stored: (0..Tree::<T, SIZE>::LEAF_LAYER_LEN)
+                .map(|_| Node::Empty)
+                .collect(),
+        }
+    }
+
+    /// Creates a new [`BrickedLeaves`] from the shallowest layer of `tree`.
+    pub fn from_tree(tree: &Tree<T, SIZE>) -> Self
+    where
+        T: Clone,
+    {
+        let mut bricked = Self::new();
+        for (position, node) in LayerPosition::iter_layer(0).zip(tree.stored.iter()) {
+            bricked.stored[Self::index(position)] = node.clone();
+        }
+        bricked
+    }
+
+    /// Writes all nodes into the shallowest layer of `tree`, which is stored flat.
+    ///
+    /// Upper layers of `tree` are not changed and need to be [built](Tree::build) again.
+    pub fn write_into(&self, tree: &mut Tree<T, SIZE>)
+    where
+        T: Clone,
+    {
+        tree.invalidate_layer_hashes();
+        tree.invalidate_value_filters();
+        for (position, node) in LayerPosition::iter_layer(0).zip(tree.stored.iter_mut()) {
+            *node = self.stored[Self::index(position)].clone();
+        }
+    }
+
+    /// Returns index of node on `position` in bricked storage.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn index(position: LayerPosition<Tree<T, SIZE>>) -> usize {
+        debug_assert_eq!(position.depth, 0);
+        let brick = Self::BRICK_ROW_SIZE;
+        let bricks = Self::BRICKS_IN_ROW;
+        let brick_index = position.x / brick
+            + (position.y / brick) * bricks
+            + (position.z / brick) * bricks * bricks;
+        let local = position.x % brick
+            + (position.y % brick) * brick
+            + (position.z % brick) * brick * brick;
+        brick_index * Self::BRICK_SIZE + local
+    }
+
+    /// Returns a reference to a [`Node`] on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn get(&self, position: LayerPosition<Tree<T, SIZE>>) -> &Node<T> {
+        &self.stored[Self::index(position)]
+    }
+
+    /// Sets the node on `position` to provided [`node`](Node)
+    /// and returns a [`Node`] previously stored on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn set(&mut self, position: LayerPosition<Tree<T, SIZE>>, node: Node<T>) -> Node<T> {
+        std::mem::replace(&mut self.stored[Self::index(position)], node)
+    }
+
+    /// Returns all nodes of brick on `brick_index`, bricks are ordered with `x` changing the fastest.
+    ///
+    /// Expects `brick_index` to be less than amount of bricks.
+    pub fn brick(&self, brick_index: usize) -> &[Node<T>] {
+        let start = brick_index * Self::BRICK_SIZE;
+        &self.stored[start..start + Self::BRICK_SIZE]
+    }
+}
+
+impl<T, const SIZE: usize, const BRICK: usize> Default for BrickedLeaves<T, SIZE, BRICK>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod bricked_tests {
+    use crate::{LayerPosition, Node, NodeIndex, NodesRaw, Tree};
+
+    use super::BrickedLeaves;
+
+    type TestTree = Tree<usize, 4681>;
+
+    #[test]
+    fn index() {
+        type Bricked = BrickedLeaves<usize, 4681, 4>;
+        assert_eq!(Bricked::BRICK_SIZE, 64);
+        assert_eq!(Bricked::index(LayerPosition::new(0, 0, 0, 0)), 0);
+        assert_eq!(Bricked::index(LayerPosition::new(3, 0, 0, 0)), 3);
+        assert_eq!(Bricked::index(LayerPosition::new(0, 1, 0, 0)), 4);
+        assert_eq!(Bricked::index(LayerPosition::new(4, 0, 0, 0)), 64);
+        assert_eq!(Bricked::index(LayerPosition::new(0, 4, 0, 0)), 256);
+        assert_eq!(Bricked::index(LayerPosition::new(15, 15, 15, 0)), 4095);
+
+        type Small = BrickedLeaves<usize, 73, 8>;
+        assert_eq!(Small::BRICK_ROW_SIZE, 4);
+        assert_eq!(Small::index(LayerPosition::new(1, 2, 3, 0)), 57);
+    }
+
+    #[test]
+    fn conversion() {
+        let mut nodes = NodesRaw::new();
+        for i in 0..4096 {
+            nodes.push(Node::Filled(i));
+        }
+        let tree = TestTree::from(nodes);
+
+        let mut bricked = BrickedLeaves::<usize, 4681, 4>::from_tree(&tree);
+        assert_eq!(
+            bricked.get(LayerPosition::new(5, 1, 0, 0)),
+            tree.get(LayerPosition::new(5, 1, 0, 0))
+        );
+        assert_eq!(bricked.brick(1)[0], Node::Filled(4));
+        assert_eq!(bricked.brick(1)[4], Node::Filled(20));
+
+        bricked.set(LayerPosition::new(15, 0, 0, 0), Node::Reduced);
+        let mut written = tree.clone();
+        bricked.write_into(&mut written);
+        assert_eq!(written.get(LayerPosition::new(15, 0, 0, 0)), &Node::Reduced);
+        written.set(LayerPosition::new(15, 0, 0, 0), Node::Filled(15));
+        assert_eq!(written, tree);
+        assert_eq!(written.get(NodeIndex::new(4680)), &Node::Empty);
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt::Debug;
+
+use crate::{LayerPosition, Node, NodeIndex, Tree, TreeInterface};
+
+/// Copy of layers of [`Tree`] in `min_depth` and deeper, created by [`Tree::clone_coarse`].
+///
+/// Positions are expressed in coordinates of [`Tree<T, SIZE>`](Tree), so nodes keep the same
+/// positions as in the original tree and shallower layers are not available.
+#[derive(Debug, Clone)]
+pub struct CoarseTree<T, const SIZE: usize> {
+    /// The shallowest stored layer.
+    min_depth: usize,
+    /// Nodes of all stored layers, in the order in which they are stored in [`Tree`].
+    nodes: Box<[Node<T>]>,
+}
+
+impl<T, const SIZE: usize> CoarseTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    /// Returns depth of the shallowest stored layer.
+    pub fn min_depth(&self) -> usize {
+        self.min_depth
+    }
+
+    /// Returns a reference to a [Node] on `position`,
+    /// or [`None`] if it is in a layer shallower than [`min_depth`](CoarseTree::min_depth).
+    pub fn get<P>(&self, position: P) -> Option<&Node<T>>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let index = position.into();
+        (index.depth() >= self.min_depth).then(|| &self.nodes[index.raw() - self.start()])
+    }
+
+    /// Returns [NodeIndex] and a reference to the stored [Node] covering `position`,
+    /// i.e. the node itself if it is stored, otherwise its ancestor in
+    /// [`min_depth`](CoarseTree::min_depth).
+    pub fn get_covering<P>(&self, position: P) -> (NodeIndex<Tree<T, SIZE>>, &Node<T>)
+    where
+        P: Into<LayerPosition<Tree<T, SIZE>>>,
+    {
+        let mut position = position.into();
+        if position.depth < self.min_depth {
+            let shift = self.min_depth - position.depth;
+            position = LayerPosition::new(
+                position.x >> shift,
+                position.y >> shift,
+                position.z >> shift,
+                self.min_depth,
+            );
+        }
+        let index = NodeIndex::from(position);
+        (index, &self.nodes[index.raw() - self.start()])
+    }
+
+    /// Returns an iterator over all stored nodes together with their [`indexes`](NodeIndex),
+    /// in the order in which they are stored.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeIndex<Tree<T, SIZE>>, &Node<T>)> {
+        let start = self.start();
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(move |(index, node)| (NodeIndex::new(start + index), node))
+    }
+
+    /// Returns raw index of the first stored node.
+    fn start(&self) -> usize {
+        SIZE - self.nodes.len()
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Clones only layers in `min_depth` and deeper into a [`CoarseTree`],
+    /// so a coarse representation can be created without cloning the whole tree.
+    ///
+    /// Expects in-bounds `min_depth`.
+    pub fn clone_coarse(&self, min_depth: usize) -> CoarseTree<T, SIZE>
+    where
+        T: Clone,
+    {
+        let start = Self::layer_start(min_depth);
+        CoarseTree {
+            min_depth,
+            nodes: self.stored[start..].into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod coarse_tests {
+    use crate::{LayerPosition, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn clone_coarse() {
+        let mut tree = TestTree::new();
+        for index in 0..73 {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+
+        let coarse = tree.clone_coarse(1);
+        assert_eq!(coarse.min_depth(), 1);
+        assert_eq!(coarse.iter().count(), 9);
+        assert!(coarse.iter().all(|(index, node)| tree.get(index) == node));
+        assert_eq!(
+            coarse.get(LayerPosition::new(1, 0, 1, 1)),
+            Some(&Node::Filled(69))
+        );
+        assert_eq!(coarse.get(NodeIndex::new(72)), Some(&Node::Filled(72)));
+        assert_eq!(coarse.get(NodeIndex::new(63)), None);
+
+        assert_eq!(
+            coarse.get_covering(LayerPosition::new(3, 1, 2, 0)),
+            (NodeIndex::new(69), &Node::Filled(69))
+        );
+        assert_eq!(
+            coarse.get_covering(LayerPosition::new(0, 0, 0, 2)),
+            (NodeIndex::new(72), &Node::Filled(72))
+        );
+
+        let root = tree.clone_coarse(2);
+        assert_eq!(root.iter().count(), 1);
+        assert_eq!(tree.clone_coarse(0).iter().count(), 73);
+    }
+}
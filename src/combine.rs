@@ -0,0 +1,67 @@
+use crate::{LayerPosition, Node};
+
+/// Data available to a combination rule when [building](crate::Tree::build_with_context)
+/// a parent [`Node`] from its children.
+///
+/// Children are ordered in the same way as [`Tree::children`](crate::Tree::children) returns them,
+/// position of a child in this order is called its octant.
+#[derive(Debug)]
+pub struct CombineContext<'a, T, U> {
+    position: LayerPosition<U>,
+    previous: &'a Node<T>,
+    children: [&'a Node<T>; 8],
+}
+
+impl<'a, T, U> CombineContext<'a, T, U> {
+    pub(crate) fn new(
+        position: LayerPosition<U>,
+        previous: &'a Node<T>,
+        children: [&'a Node<T>; 8],
+    ) -> Self {
+        Self {
+            position,
+            previous,
+            children,
+        }
+    }
+
+    /// Returns [`LayerPosition`] of the parent which is being combined.
+    pub fn position(&self) -> LayerPosition<U> {
+        self.position
+    }
+
+    /// Returns a [`Node`] which was stored in parent before this combination.
+    pub fn previous(&self) -> &'a Node<T> {
+        self.previous
+    }
+
+    /// Returns all children of the parent.
+    pub fn children(&self) -> &[&'a Node<T>; 8] {
+        &self.children
+    }
+
+    /// Returns a child in `octant`.
+    ///
+    /// Expects `octant` to be less than 8.
+    pub fn child(&self, octant: usize) -> &'a Node<T> {
+        debug_assert!(octant < 8);
+        self.children[octant]
+    }
+
+    /// Returns an iterator over children together with their octants.
+    pub fn octants(&self) -> impl Iterator<Item = (usize, &'a Node<T>)> + '_ {
+        self.children.iter().copied().enumerate()
+    }
+}
+
+/// Stateful combination rule used by [`Tree::build_with_reducer`](crate::Tree::build_with_reducer).
+///
+/// Unlike closures passed to [`build`](crate::Tree::build) a reducer can keep state between
+/// combinations, e.g. statistics of the built tree, and can be shared as a named type.
+pub trait Reducer<T, U> {
+    /// Returns a [`Node`] combined from `children` of the parent on `position`.
+    ///
+    /// Children are ordered in the same way as [`Tree::children`](crate::Tree::children)
+    /// returns them.
+    fn reduce(&mut self, children: &[&Node<T>; 8], position: LayerPosition<U>) -> Node<T>;
+}
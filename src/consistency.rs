@@ -0,0 +1,121 @@
+use std::fmt::Debug;
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug + PartialEq,
+{
+    /// Returns [`indexes`](NodeIndex) of all parrents whose stored [`Node`] differs from
+    /// the one produced by applying `combine_rule` to their children.
+    ///
+    /// `combine_rule` is the same rule which is passed to [`build`](Tree::build). Parrents are
+    /// checked against their currently stored children, so inconsistency does not propagate up.
+    pub fn check_consistency<F>(&self, combine_rule: F) -> Vec<NodeIndex<Self>>
+    where
+        F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+    {
+        Self::parrent_indexes()
+            .filter(|&index| self.combined(index, combine_rule) != *self.get(index))
+            .collect()
+    }
+
+    /// Sets every parrent which is inconsistent with its children to the [`Node`] produced
+    /// by `combine_rule` and returns [`indexes`](NodeIndex) of all changed parrents.
+    ///
+    /// Parrents are repaired from bottom up, so a change of a parrent is reflected in its
+    /// ancestors, consistent parrents are left untouched.
+    pub fn repair<F>(&mut self, combine_rule: F) -> Vec<NodeIndex<Self>>
+    where
+        F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+    {
+        let mut repaired = Vec::new();
+        for index in Self::parrent_indexes() {
+            let node = self.combined(index, combine_rule);
+            if node != *self.get(index) {
+                self.set(index, node);
+                repaired.push(index);
+            }
+        }
+        repaired
+    }
+
+    /// Returns a [`Node`] produced by applying `combine_rule` to children of node on `index`.
+    ///
+    /// Expects node on `index` to have children.
+    fn combined<F>(&self, index: NodeIndex<Self>, combine_rule: F) -> Node<T>
+    where
+        F: FnOnce(&[&Node<T>]) -> Node<T>,
+    {
+        // Only parrents are passed here, so they always have children.
+        let children = self.children(index).unwrap();
+        combine_rule(&children.map(|child| self.get(child)))
+    }
+
+    /// Returns indexes of all nodes which have children, ordered from bottom up.
+    fn parrent_indexes() -> impl Iterator<Item = NodeIndex<Self>> {
+        (Self::LEAF_LAYER_LEN..SIZE).map(NodeIndex::new)
+    }
+}
+
+#[cfg(test)]
+mod consistency_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+            Node::Empty
+        } else if nodes.iter().all(|node| matches!(node, Node::Filled(_))) {
+            Node::Filled(1)
+        } else {
+            Node::Reduced
+        }
+    }
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        for index in [0, 1, 4, 5, 16, 17, 20, 21] {
+            tree.set(NodeIndex::new(index), Node::Filled(1));
+        }
+        tree.build(combine_rule);
+        tree
+    }
+
+    #[test]
+    fn check_consistency() {
+        let mut tree = tree();
+        assert!(tree.check_consistency(combine_rule).is_empty());
+
+        tree.set(NodeIndex::new(0), Node::Empty);
+        tree.set(NodeIndex::new(66), Node::Filled(1));
+        assert_eq!(
+            tree.check_consistency(combine_rule),
+            vec![NodeIndex::new(64), NodeIndex::new(66)]
+        );
+    }
+
+    #[test]
+    fn repair() {
+        let mut tree = tree();
+        assert!(tree.repair(combine_rule).is_empty());
+
+        for index in [0, 1, 4, 5, 16, 17, 20, 21] {
+            tree.set(NodeIndex::new(index), Node::Empty);
+        }
+        tree.set(NodeIndex::new(66), Node::Filled(1));
+        // Root is repaired as well because its children changed.
+        assert_eq!(
+            tree.repair(combine_rule),
+            vec![NodeIndex::new(64), NodeIndex::new(66), NodeIndex::new(72)]
+        );
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Empty);
+        assert!(tree.check_consistency(combine_rule).is_empty());
+
+        let mut built = tree.clone();
+        built.build(combine_rule);
+        assert_eq!(tree, built);
+    }
+}
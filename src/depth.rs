@@ -0,0 +1,239 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::{CoordinateError, LayerSize, RowSize, TreeInterface};
+
+/// Depth of a layer inside a [`Tree`](crate::Tree).
+///
+/// Unlike plain [`usize`], [`Depth`] is bound to the associated [`Tree`](crate::Tree) and is checked
+/// when created, so it can not be used with a tree of other size by accident.
+#[derive(Debug)]
+pub struct Depth<T> {
+    depth: usize,
+    /// Associated [`Tree`](crate::Tree).
+    boo: PhantomData<T>,
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Clone for Depth<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// [`Copy`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Copy for Depth<T> {}
+
+/// [`PartialEq`] is implemented manually, so there is no requirement on `T` to also implement [`PartialEq`].
+impl<T> PartialEq for Depth<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth
+    }
+}
+
+/// [`Display`] shows the biggest row of associated [`Tree`](crate::Tree) and `depth`.
+impl<T> Display for Depth<T>
+where
+    T: TreeInterface,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Depth::<{}>( {} )", T::BIGGEST_ROW_SIZE, self.depth)
+    }
+}
+
+impl<T> From<Depth<T>> for usize {
+    fn from(value: Depth<T>) -> Self {
+        value.depth
+    }
+}
+
+/// Conversion is checked the same way as in [`Depth::try_new`].
+impl<T> TryFrom<usize> for Depth<T>
+where
+    T: TreeInterface,
+{
+    type Error = CoordinateError;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+impl<T> Depth<T>
+where
+    T: TreeInterface,
+{
+    /// Depth of the shallowest layer, which contains leaves.
+    pub const LEAF: Self = Self {
+        depth: 0,
+        boo: PhantomData,
+    };
+    /// Depth of the deepest layer, which contains only the root.
+    pub const MAX: Self = Self {
+        depth: T::MAX_DEPTH_INDEX,
+        boo: PhantomData,
+    };
+
+    /// Creates a new [Depth], can be used in const context.
+    ///
+    /// Panics if `depth` is greater than [MAX_DEPTH_INDEX](TreeInterface::MAX_DEPTH_INDEX)
+    /// of associated [`Tree`](crate::Tree).
+    pub const fn new(depth: usize) -> Self {
+        assert!(depth <= T::MAX_DEPTH_INDEX, "depth is out of bounds");
+        Self {
+            depth,
+            boo: PhantomData,
+        }
+    }
+
+    /// Creates a new [Depth], or returns [`CoordinateError::InvalidDepth`] if `depth` is greater
+    /// than [MAX_DEPTH_INDEX](TreeInterface::MAX_DEPTH_INDEX) of associated [`Tree`](crate::Tree).
+    pub fn try_new(depth: usize) -> Result<Self, CoordinateError> {
+        T::try_row_size(depth)?;
+        Ok(Self {
+            depth,
+            boo: PhantomData,
+        })
+    }
+
+    /// Creates a new [Depth], or returns [`CoordinateError::InvalidDepth`] if `depth` is greater
+    /// than [MAX_DEPTH_INDEX](TreeInterface::MAX_DEPTH_INDEX) of associated [`Tree`](crate::Tree).
+    ///
+    /// Same as [`Depth::try_new`], kept for consistency with other checked constructors.
+    pub fn new_checked(depth: usize) -> Result<Self, CoordinateError> {
+        Self::try_new(depth)
+    }
+
+    /// Returns an iterator over all depths of associated [`Tree`](crate::Tree),
+    /// from [`LEAF`](Depth::LEAF) to [`MAX`](Depth::MAX).
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..T::DEPTH).map(|depth| Self {
+            depth,
+            boo: PhantomData,
+        })
+    }
+
+    /// Returns the depth one layer deeper, i.e. closer to the root,
+    /// or [`None`] if this is [`MAX`](Depth::MAX).
+    pub fn deeper(self) -> Option<Self> {
+        Self::try_new(self.depth + 1).ok()
+    }
+
+    /// Returns the depth one layer shallower, i.e. closer to the leaves,
+    /// or [`None`] if this is [`LEAF`](Depth::LEAF).
+    pub fn shallower(self) -> Option<Self> {
+        let depth = self.depth.checked_sub(1)?;
+        Some(Self {
+            depth,
+            boo: PhantomData,
+        })
+    }
+
+    /// Returns depth as [`usize`].
+    pub fn get(self) -> usize {
+        self.depth
+    }
+
+    /// Returns a [RowSize] of the layer.
+    pub fn row_size(self) -> RowSize {
+        T::row_size(self.depth)
+    }
+
+    /// Returns a [LayerSize] of the layer.
+    pub fn layer_size(self) -> LayerSize {
+        T::layer_size(self.depth)
+    }
+}
+
+#[cfg(test)]
+mod depth_tests {
+    use crate::{CoordinateError, LayerSize, RowSize, Tree, TreeInterface};
+
+    use super::Depth;
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn try_new() {
+        let depth = Depth::<TestTree>::try_new(1).unwrap();
+        assert_eq!(depth.get(), 1);
+        assert_eq!(depth.row_size(), RowSize::new(2));
+        assert_eq!(depth.layer_size(), TestTree::layer_size(1));
+        assert_eq!(usize::from(depth), 1);
+
+        assert_eq!(
+            Depth::<TestTree>::try_new(3),
+            Err(CoordinateError::InvalidDepth { depth: 3, max: 2 })
+        );
+        assert!(Depth::<Tree<usize, 585>>::try_new(3).is_ok());
+    }
+
+    #[test]
+    fn new() {
+        const DEPTH: Depth<TestTree> = Depth::new(2);
+        assert_eq!(DEPTH, Depth::<TestTree>::MAX);
+        assert_eq!(Depth::<TestTree>::new(0), Depth::<TestTree>::LEAF);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_out_of_bounds() {
+        Depth::<TestTree>::new(3);
+    }
+
+    #[test]
+    fn all() {
+        let depths = Depth::<TestTree>::all().map(Depth::get).collect::<Vec<_>>();
+        assert_eq!(depths, vec![0, 1, 2]);
+        assert_eq!(Depth::<TestTree>::LEAF.get(), 0);
+        assert_eq!(Depth::<TestTree>::MAX.get(), 2);
+        assert_eq!(Depth::<Tree<usize, 585>>::MAX.get(), 3);
+    }
+
+    #[test]
+    fn deeper_shallower() {
+        let leaf = Depth::<TestTree>::LEAF;
+        assert_eq!(leaf.shallower(), None);
+        assert_eq!(leaf.deeper().map(Depth::get), Some(1));
+        assert_eq!(Depth::<TestTree>::MAX.deeper(), None);
+        assert_eq!(Depth::<TestTree>::MAX.shallower().map(Depth::get), Some(1));
+
+        assert_eq!(Depth::<TestTree>::try_from(2), Ok(Depth::<TestTree>::MAX));
+        assert!(Depth::<TestTree>::try_from(3).is_err());
+    }
+
+    #[test]
+    fn new_checked() {
+        assert_eq!(Depth::<TestTree>::new_checked(2).map(Depth::get), Ok(2));
+        assert_eq!(
+            Depth::<TestTree>::new_checked(3),
+            Err(CoordinateError::InvalidDepth { depth: 3, max: 2 })
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            CoordinateError::InvalidIndex {
+                index: 73,
+                size: 73
+            }
+            .to_string(),
+            "invalid index: expected less than 73, got 73"
+        );
+    }
+
+    #[test]
+    fn try_sizes() {
+        assert_eq!(TestTree::try_row_size(2), Ok(RowSize::new(1)));
+        assert_eq!(TestTree::try_layer_size(0).map(LayerSize::get), Ok(64));
+        assert_eq!(
+            TestTree::try_layer_size(5),
+            Err(CoordinateError::InvalidDepth { depth: 5, max: 2 })
+        );
+        assert_eq!(
+            CoordinateError::InvalidDepth { depth: 5, max: 2 }.to_string(),
+            "invalid depth: expected at most 2, got 5"
+        );
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+
+use crate::{LayerPosition, NodeIndex, NodePosition, Tree, TreeInterface};
+
+/// Amount of differing nodes which are described by [`DiffReport`] when displayed.
+const SHOWN_DIFFERENCES: usize = 16;
+
+/// Differences between two trees, created by [`Tree::diff_report`].
+///
+/// When displayed, describes the first differing nodes in all coordinate systems together
+/// with amount of differences in every layer, and leaves with differing
+/// [refinements](Tree::refine_leaf).
+#[derive(Debug)]
+pub struct DiffReport<'a, T, const SIZE: usize> {
+    left: &'a Tree<T, SIZE>,
+    right: &'a Tree<T, SIZE>,
+    /// Indexes of all differing nodes, in the order in which they are stored.
+    differing: Vec<NodeIndex<Tree<T, SIZE>>>,
+    /// Indexes of all leaves with differing refinements, in the order in which they are stored.
+    refined: Vec<NodeIndex<Tree<T, SIZE>>>,
+}
+
+impl<'a, T, const SIZE: usize> DiffReport<'a, T, SIZE> {
+    /// Creates a new [`DiffReport`] of `differing` nodes, refinements are compared here.
+    pub(crate) fn new(
+        left: &'a Tree<T, SIZE>,
+        right: &'a Tree<T, SIZE>,
+        differing: Vec<NodeIndex<Tree<T, SIZE>>>,
+    ) -> Self
+    where
+        Tree<T, SIZE>: TreeInterface,
+    {
+        let refined = left
+            .refinements
+            .keys()
+            .chain(right.refinements.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|index| left.refinements.get(index) != right.refinements.get(index))
+            .map(|index| NodeIndex::new(*index))
+            .collect();
+        Self {
+            left,
+            right,
+            differing,
+            refined,
+        }
+    }
+
+    /// Returns `true` if the trees hold the same nodes and [refinements](Tree::refine_leaf).
+    pub fn is_empty(&self) -> bool {
+        self.differing.is_empty() && self.refined.is_empty()
+    }
+
+    /// Returns amount of differing nodes.
+    pub fn len(&self) -> usize {
+        self.differing.len()
+    }
+
+    /// Returns [`indexes`](NodeIndex) of all differing nodes, in the order in which they are stored.
+    pub fn indexes(&self) -> &[NodeIndex<Tree<T, SIZE>>] {
+        &self.differing
+    }
+
+    /// Returns [`indexes`](NodeIndex) of all leaves with differing [refinements](Tree::refine_leaf),
+    /// in the order in which they are stored.
+    pub fn refined(&self) -> &[NodeIndex<Tree<T, SIZE>>] {
+        &self.refined
+    }
+}
+
+/// [`Display`] lists amount of differences in every layer and describes the first 16
+/// differing nodes and the first 16 leaves with differing refinements.
+impl<'a, T, const SIZE: usize> Display for DiffReport<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "trees are equal");
+        }
+        if !self.differing.is_empty() {
+            writeln!(f, "{} nodes differ", self.len())?;
+        }
+        let mut per_layer = vec![0; Tree::<T, SIZE>::DEPTH];
+        for index in &self.differing {
+            per_layer[index.depth()] += 1;
+        }
+        for (depth, differing) in per_layer.iter().enumerate() {
+            if *differing > 0 {
+                let size = Tree::<T, SIZE>::layer_size(depth).get();
+                writeln!(f, "  layer {depth}: {differing} of {size} nodes differ")?;
+            }
+        }
+        for &index in self.differing.iter().take(SHOWN_DIFFERENCES) {
+            let layer = LayerPosition::from(index);
+            let node = NodePosition::from(index);
+            writeln!(
+                f,
+                "  index {} (depth {}, layer position ({}, {}, {}), node position ({}, {}, {})): \
+                 {:?} != {:?}",
+                index.raw(),
+                layer.depth,
+                layer.x,
+                layer.y,
+                layer.z,
+                node.x,
+                node.y,
+                node.z,
+                self.left.get(index),
+                self.right.get(index),
+            )?;
+        }
+        if self.len() > SHOWN_DIFFERENCES {
+            writeln!(f, "  and {} more", self.len() - SHOWN_DIFFERENCES)?;
+        }
+        if self.refined.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "{} refinements differ", self.refined.len())?;
+        for &index in self.refined.iter().take(SHOWN_DIFFERENCES) {
+            let layer = LayerPosition::from(index);
+            writeln!(
+                f,
+                "  leaf {} ({}, {}, {}): {:?} != {:?}",
+                index.raw(),
+                layer.x,
+                layer.y,
+                layer.z,
+                self.left.refinement(index),
+                self.right.refinement(index),
+            )?;
+        }
+        if self.refined.len() > SHOWN_DIFFERENCES {
+            writeln!(f, "  and {} more", self.refined.len() - SHOWN_DIFFERENCES)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug + PartialEq,
+{
+    /// Compares all nodes and [refinements](Tree::refine_leaf) with those of `other`
+    /// and returns a [`DiffReport`] of the differences, which is more readable than debug
+    /// output of whole trees.
+    ///
+    /// Layers with equal known [layer hashes](Tree::enable_layer_hashes) in both trees
+    /// are skipped, so in the unlikely case of a hash collision a difference can be missed,
+    /// use [`exact_diff_report`](Tree::exact_diff_report) when the result has to be exact.
+    /// With `rayon` feature, `par_diff_report` compares layers in parallel.
+    pub fn diff_report<'a>(&'a self, other: &'a Self) -> DiffReport<'a, T, SIZE> {
+        self.diff_report_with(other, true)
+    }
+
+    /// Compares all nodes and [refinements](Tree::refine_leaf) with those of `other` in the same
+    /// way as [`diff_report`](Tree::diff_report), but never skips layers with equal
+    /// [layer hashes](Tree::enable_layer_hashes), so it finds every difference found by [`PartialEq`].
+    pub fn exact_diff_report<'a>(&'a self, other: &'a Self) -> DiffReport<'a, T, SIZE> {
+        self.diff_report_with(other, false)
+    }
+
+    /// Returns a [`DiffReport`] of differences from `other`, skipping layers with equal
+    /// known hashes if `trust_hashes` is `true`.
+    fn diff_report_with<'a>(
+        &'a self,
+        other: &'a Self,
+        trust_hashes: bool,
+    ) -> DiffReport<'a, T, SIZE> {
+        let mut differing = Vec::new();
+        for depth in 0..Self::DEPTH {
+            if trust_hashes && self.layer_hashes_match(other, depth) {
+                continue;
+            }
+            let layer = self.layer_range(depth);
+            differing.extend(
+                self.stored[layer.clone()]
+                    .iter()
+                    .zip(&other.stored[layer.clone()])
+                    .zip(layer)
+                    .filter(|((left, right), _)| left != right)
+                    .map(|(_, index)| NodeIndex::new(index)),
+            );
+        }
+        DiffReport::new(self, other, differing)
+    }
+
+    /// Returns range of raw indexes of nodes in layer in `depth`.
+    pub(crate) fn layer_range(&self, depth: usize) -> Range<usize> {
+        let start = Self::layer_start(depth);
+        start..start + Self::layer_size(depth).get()
+    }
+}
+
+/// Asserts that two [`trees`](crate::Tree) hold the same nodes and refinements, panicking with
+/// a [`DiffReport`](crate::DiffReport) otherwise.
+///
+/// Every node is compared, [layer hashes](crate::Tree::enable_layer_hashes) are not trusted,
+/// see [`Tree::exact_diff_report`](crate::Tree::exact_diff_report).
+#[macro_export]
+macro_rules! assert_trees_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let report = left.exact_diff_report(right);
+                if !report.is_empty() {
+                    panic!("assertion `left == right` failed\n{}", report);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod diff_report_tests {
+    use crate::{MicroGrid, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn diff_report() {
+        let mut tree = TestTree::new();
+        let other = tree.clone();
+        assert!(tree.diff_report(&other).is_empty());
+        assert_trees_eq!(tree, other);
+
+        tree.set(NodeIndex::new(42), Node::Filled(1));
+        tree.set(NodeIndex::new(71), Node::Reduced);
+        let report = tree.diff_report(&other);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report.indexes(), &[NodeIndex::new(42), NodeIndex::new(71)]);
+        let report = report.to_string();
+        assert!(report.starts_with("2 nodes differ\n"));
+        assert!(report.contains("layer 0: 1 of 64 nodes differ"));
+        assert!(report.contains(
+            "index 42 (depth 0, layer position (2, 2, 2), node position (2, 2, 2)): \
+             Filled(1) != Empty"
+        ));
+        assert!(report.contains(
+            "index 71 (depth 1, layer position (1, 1, 1), node position (2, 2, 2)): \
+             Reduced != Empty"
+        ));
+    }
+
+    #[test]
+    fn skip_hashed_layers() {
+        let mut tree = TestTree::new();
+        let mut other = tree.clone();
+        tree.enable_layer_hashes();
+        other.enable_layer_hashes();
+        tree.set(NodeIndex::new(3), Node::Filled(1));
+        other.set(NodeIndex::new(3), Node::Filled(1));
+        tree.set(NodeIndex::new(72), Node::Reduced);
+        assert_eq!(tree.diff_report(&other).indexes(), &[NodeIndex::new(72)]);
+
+        // Unknown hash of the layer falls back to comparing nodes.
+        *other.get_mut(NodeIndex::new(3)) = Node::Filled(2);
+        assert_eq!(
+            tree.diff_report(&other).indexes(),
+            &[NodeIndex::new(3), NodeIndex::new(72)]
+        );
+    }
+
+    #[test]
+    fn exact_diff_report() {
+        let mut tree = TestTree::new();
+        tree.enable_layer_hashes();
+        let mut other = tree.clone();
+        // Stands in for a hash collision, the layer hash of `other` stays equal.
+        other.stored[3] = Node::Filled(1);
+        assert_ne!(tree, other);
+        assert!(tree.diff_report(&other).is_empty());
+        assert_eq!(
+            tree.exact_diff_report(&other).indexes(),
+            &[NodeIndex::new(3)]
+        );
+        let result = std::panic::catch_unwind(|| assert_trees_eq!(tree, other));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn refinements() {
+        let mut tree = TestTree::new();
+        let mut other = tree.clone();
+        tree.refine_leaf(NodeIndex::new(0), MicroGrid::default());
+        other.refine_leaf(NodeIndex::new(5), MicroGrid::FULL);
+        tree.refine_leaf(NodeIndex::new(5), MicroGrid::FULL);
+        assert_ne!(tree, other);
+
+        let report = tree.diff_report(&other);
+        assert!(!report.is_empty());
+        assert_eq!(report.len(), 0);
+        assert_eq!(report.refined(), &[NodeIndex::new(0)]);
+        let report = report.to_string();
+        assert!(report.starts_with("1 refinements differ\n"));
+        assert!(report.contains("leaf 0 (0, 0, 0): Some(MicroGrid(0)) != None"));
+
+        other.refine_leaf(NodeIndex::new(0), MicroGrid::default());
+        assert_trees_eq!(tree, other);
+    }
+
+    #[test]
+    #[should_panic(expected = "1 refinements differ")]
+    fn assert_trees_eq_refinements() {
+        let mut tree = TestTree::new();
+        tree.refine_leaf(NodeIndex::new(0), MicroGrid::default());
+        assert_trees_eq!(tree, TestTree::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "1 nodes differ")]
+    fn assert_trees_eq() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(0), Node::Filled(1));
+        assert_trees_eq!(tree, TestTree::new());
+    }
+}
@@ -0,0 +1,74 @@
+/// Axis of a [`Tree`](crate::Tree).
+///
+/// `x` goes from left to right, `y` from bottom to top and `z` from front to back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    /// Axis from left to right.
+    X,
+    /// Axis from bottom to top.
+    Y,
+    /// Axis from front to back.
+    Z,
+}
+
+/// Direction towards one of six faces of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Towards lower `x`.
+    Left,
+    /// Towards higher `x`.
+    Right,
+    /// Towards lower `y`.
+    Bottom,
+    /// Towards higher `y`.
+    Top,
+    /// Towards lower `z`.
+    Front,
+    /// Towards higher `z`.
+    Back,
+}
+
+impl Direction {
+    /// All directions, ordered by [`Axis`] and from the lower coordinate to the higher one.
+    pub const ALL: [Direction; 6] = [
+        Direction::Left,
+        Direction::Right,
+        Direction::Bottom,
+        Direction::Top,
+        Direction::Front,
+        Direction::Back,
+    ];
+
+    /// Returns [`Axis`] along which this direction goes.
+    pub const fn axis(self) -> Axis {
+        match self {
+            Direction::Left | Direction::Right => Axis::X,
+            Direction::Bottom | Direction::Top => Axis::Y,
+            Direction::Front | Direction::Back => Axis::Z,
+        }
+    }
+
+    /// Returns change of `x`, `y` and `z` when moving one node in this direction.
+    pub const fn offset(self) -> [isize; 3] {
+        match self {
+            Direction::Left => [-1, 0, 0],
+            Direction::Right => [1, 0, 0],
+            Direction::Bottom => [0, -1, 0],
+            Direction::Top => [0, 1, 0],
+            Direction::Front => [0, 0, -1],
+            Direction::Back => [0, 0, 1],
+        }
+    }
+
+    /// Returns the opposite direction.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Bottom => Direction::Top,
+            Direction::Top => Direction::Bottom,
+            Direction::Front => Direction::Back,
+            Direction::Back => Direction::Front,
+        }
+    }
+}
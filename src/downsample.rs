@@ -0,0 +1,145 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::{LayerPosition, Node, OctreeAccess, PositionRange, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Summarizes `region` of the shallowest layer, given as a [`PositionRange`] or ranges
+    /// of `x`, `y` and `z` of leaves, into a dense grid with `resolution` cells on
+    /// `x`, `y` and `z`, returned in the order with `x` changing the fastest.
+    ///
+    /// Every cell is produced by `reduce` from nodes of the deepest layer whose nodes still fit
+    /// into the smallest cell, so big regions are read from upper layers instead of leaves.
+    /// Relies on upper layers being [built](Tree::build). Cells smaller than a leaf read
+    /// the leaf they start in. Expects `region` inside of the tree and non-zero `resolution`.
+    pub fn downsample_region<P, R, F>(&self, region: P, resolution: [usize; 3], reduce: F) -> Vec<R>
+    where
+        P: Into<PositionRange<Self>>,
+        F: FnMut(&[&Node<T>]) -> R,
+    {
+        downsample_region(self, region, resolution, reduce)
+    }
+}
+
+/// Summarizes `region` of the shallowest layer of `access` into a dense grid with `resolution`
+/// cells on `x`, `y` and `z`.
+///
+/// Behaves the same as [`Tree::downsample_region`], but works with any storage implementing
+/// [`OctreeAccess`].
+pub fn downsample_region<A, P, R, F>(
+    access: &A,
+    region: P,
+    resolution: [usize; 3],
+    mut reduce: F,
+) -> Vec<R>
+where
+    A: OctreeAccess,
+    P: Into<PositionRange<A::Layout>>,
+    F: FnMut(&[&Node<A::Value>]) -> R,
+{
+    let region = region.into().leaf_region();
+    debug_assert!(resolution.iter().all(|resolution| *resolution > 0));
+    let smallest_cell = (0..3)
+        .map(|axis| region[axis].len() / resolution[axis])
+        .min()
+        .unwrap_or(1)
+        .max(1);
+    let depth = (smallest_cell.ilog2() as usize).min(A::Layout::MAX_DEPTH_INDEX);
+
+    // Range of nodes in `depth` covered by every cell on every axis.
+    let cells = [0, 1, 2].map(|axis| {
+        let Range { start, end } = region[axis];
+        (0..resolution[axis])
+            .map(|cell| {
+                let cell_start = start + cell * (end - start) / resolution[axis];
+                let cell_end =
+                    (start + (cell + 1) * (end - start) / resolution[axis]).max(cell_start + 1);
+                (cell_start >> depth)..((cell_end - 1) >> depth) + 1
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut grid = Vec::with_capacity(resolution.iter().product());
+    let mut nodes = Vec::new();
+    for z in &cells[2] {
+        for y in &cells[1] {
+            for x in &cells[0] {
+                nodes.clear();
+                for node_z in z.clone() {
+                    for node_y in y.clone() {
+                        for node_x in x.clone() {
+                            let position = LayerPosition::new(node_x, node_y, node_z, depth);
+                            nodes.push(access.node(position.into()));
+                        }
+                    }
+                }
+                grid.push(reduce(&nodes));
+            }
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod downsample_tests {
+    use crate::implemented_tree_sizes::TREE_16;
+    use crate::{LayerPosition, Node, Tree};
+
+    type TestTree = Tree<usize, TREE_16>;
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        let filled = nodes
+            .iter()
+            .map(|node| match node {
+                Node::Filled(value) => *value,
+                _ => 0,
+            })
+            .sum();
+        if filled == 0 {
+            Node::Empty
+        } else {
+            Node::Filled(filled)
+        }
+    }
+
+    fn count_filled(nodes: &[&Node<usize>]) -> usize {
+        nodes
+            .iter()
+            .map(|node| match node {
+                Node::Filled(value) => *value,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn downsample_region() {
+        let mut tree = TestTree::new();
+        for (x, z) in [(0, 0), (1, 0), (5, 0), (15, 15), (8, 3)] {
+            tree.set(LayerPosition::new(x, 0, z, 0), Node::Filled(1));
+        }
+        tree.build(combine_rule);
+
+        // Cells of 4x16x4 leaves are read from nodes of 4x4x4 leaves.
+        let map = tree.downsample_region([0..16, 0..16, 0..16], [4, 1, 4], count_filled);
+        assert_eq!(map.len(), 16);
+        assert_eq!(map[0], 2);
+        assert_eq!(map[1], 1);
+        assert_eq!(map[2], 1);
+        assert_eq!(map[15], 1);
+        assert_eq!(map.iter().sum::<usize>(), 5);
+
+        let map = tree.downsample_region([0..8, 0..1, 0..8], [8, 1, 8], count_filled);
+        assert_eq!(map.len(), 64);
+        assert_eq!(&map[0..8], &[1, 1, 0, 0, 0, 1, 0, 0]);
+        assert_eq!(map.iter().sum::<usize>(), 3);
+
+        // Cells smaller than a leaf read the leaf they start in.
+        let map = tree.downsample_region([0..2, 0..1, 0..1], [4, 1, 1], count_filled);
+        assert_eq!(map, vec![1, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Debug;
+use std::ops::Deref;
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Temporary edit of [`Tree`] created by [`Tree::scoped_edit`].
+///
+/// Every change made through [`set`](EditScope::set) is recorded, when [`EditScope`] is dropped
+/// without being [committed](EditScope::commit) all changed nodes are restored to their original
+/// state. [`Tree`] can be read through [`EditScope`] as it dereferences to it.
+#[derive(Debug)]
+pub struct EditScope<'a, T, const SIZE: usize> {
+    tree: &'a mut Tree<T, SIZE>,
+    /// Raw indexes of changed nodes together with nodes they replaced, in order of changes.
+    journal: Vec<(usize, Node<T>)>,
+    /// Tombstone generations of changed nodes and last generations before each change,
+    /// in order of changes. Empty if tombstones are not recorded.
+    tombstones: Vec<(Option<u64>, u64)>,
+}
+
+impl<'a, T, const SIZE: usize> EditScope<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    /// Sets the node on `position` to provided [`node`](Node)
+    /// and returns a [`Node`] previously stored on `position`.
+    ///
+    /// Previous node is cloned and restored when this scope is not committed.
+    pub fn set<P>(&mut self, position: P, node: Node<T>) -> Node<T>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+        T: Clone,
+    {
+        let index = position.into();
+        if let Some(tombstones) = &self.tree.tombstones {
+            self.tombstones.push(tombstones.state(index.raw()));
+        }
+        let previous = self.tree.set(index, node);
+        self.journal.push((index.raw(), previous.clone()));
+        previous
+    }
+
+    /// Returns amount of recorded changes.
+    pub fn changes(&self) -> usize {
+        self.journal.len()
+    }
+
+    /// Keeps all changes made in this scope.
+    pub fn commit(mut self) {
+        self.journal.clear();
+        self.tombstones.clear();
+    }
+}
+
+impl<'a, T, const SIZE: usize> Deref for EditScope<'a, T, SIZE> {
+    type Target = Tree<T, SIZE>;
+
+    fn deref(&self) -> &Self::Target {
+        self.tree
+    }
+}
+
+/// Restores original nodes in reverse order of changes, so a node changed multiple times
+/// ends up in the state before the first change. Tombstones are restored the same way,
+/// so reverted changes never show up as removals.
+impl<'a, T, const SIZE: usize> Drop for EditScope<'a, T, SIZE> {
+    fn drop(&mut self) {
+        // Restored values are not tracked by value filters.
+        if !self.journal.is_empty() {
+            if let Some(filters) = &mut self.tree.value_filters {
+                filters.invalidate();
+            }
+        }
+        while let Some((index, mut node)) = self.journal.pop() {
+            std::mem::swap(&mut self.tree.stored[index], &mut node);
+            if let (Some(tombstones), Some((removed, generation))) =
+                (&mut self.tree.tombstones, self.tombstones.pop())
+            {
+                tombstones.restore(index, removed, generation);
+            }
+            if let Some(hashes) = &mut self.tree.hashes {
+                hashes.record(index, &node, &self.tree.stored[index]);
+            }
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Returns an [`EditScope`] which reverts all changes made through it when dropped,
+    /// unless it is [committed](EditScope::commit).
+    pub fn scoped_edit(&mut self) -> EditScope<'_, T, SIZE> {
+        EditScope {
+            tree: self,
+            journal: Vec::new(),
+            tombstones: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod edit_scope_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn revert() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(5), Node::Filled(5));
+        let original = tree.clone();
+
+        let mut scope = tree.scoped_edit();
+        assert_eq!(scope.set(NodeIndex::new(5), Node::Reduced), Node::Filled(5));
+        scope.set(NodeIndex::new(5), Node::Filled(10));
+        scope.set(NodeIndex::new(64), Node::Filled(64));
+        assert_eq!(scope.changes(), 3);
+        assert_eq!(scope.get(NodeIndex::new(5)), &Node::Filled(10));
+        drop(scope);
+
+        assert_eq!(tree, original);
+    }
+
+    #[test]
+    fn commit() {
+        let mut tree = TestTree::new();
+        let mut scope = tree.scoped_edit();
+        scope.set(NodeIndex::new(5), Node::Filled(5));
+        scope.commit();
+
+        assert_eq!(tree.get(NodeIndex::new(5)), &Node::Filled(5));
+    }
+
+    #[test]
+    fn tombstones() {
+        let mut tree = TestTree::new();
+        tree.enable_tombstones();
+        tree.set(NodeIndex::new(5), Node::Filled(5));
+
+        let mut scope = tree.scoped_edit();
+        scope.set(NodeIndex::new(5), Node::Empty);
+        assert_eq!(scope.removed_since(0), vec![NodeIndex::new(5)]);
+        drop(scope);
+
+        assert!(tree.removed_since(0).is_empty());
+    }
+
+    #[test]
+    fn cancelled_fill() {
+        let mut tree = TestTree::new();
+        tree.enable_tombstones();
+        tree.set(NodeIndex::new(6), Node::Filled(6));
+        tree.clear(NodeIndex::new(6));
+        let generation = tree.generation().unwrap();
+
+        let mut scope = tree.scoped_edit();
+        scope.set(NodeIndex::new(5), Node::Filled(1));
+        scope.set(NodeIndex::new(6), Node::Filled(6));
+        scope.set(NodeIndex::new(6), Node::Empty);
+        drop(scope);
+
+        assert_eq!(tree.get(NodeIndex::new(5)), &Node::Empty);
+        assert!(tree.removed_since(generation).is_empty());
+        assert_eq!(tree.removed_since(0), vec![NodeIndex::new(6)]);
+        assert_eq!(tree.generation(), Some(generation));
+    }
+}
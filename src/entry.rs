@@ -0,0 +1,110 @@
+use std::fmt::Debug;
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Slot of a single [`Node`] in [`Tree`] created by [`Tree::entry`].
+///
+/// Allows to fill an empty node or to change value of a filled one without looking it up again,
+/// e.g. `tree.entry(position).and_modify(|value| *value += 1).or_insert_with(|| 1)`.
+/// Changes are made the same way as through [`set`](Tree::set) and [`get_mut`](Tree::get_mut).
+#[derive(Debug)]
+pub struct Entry<'a, T, const SIZE: usize> {
+    tree: &'a mut Tree<T, SIZE>,
+    index: NodeIndex<Tree<T, SIZE>>,
+}
+
+impl<'a, T, const SIZE: usize> Entry<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    /// Returns [NodeIndex] of the node in this entry.
+    pub fn index(&self) -> NodeIndex<Tree<T, SIZE>> {
+        self.index
+    }
+
+    /// Returns a reference to the node in this entry.
+    pub fn get(&self) -> &Node<T> {
+        self.tree.get(self.index)
+    }
+
+    /// Fills the node with result of `f` if it is [`Empty`](Node::Empty)
+    /// and returns a mutable reference to it.
+    ///
+    /// [`Reduced`](Node::Reduced) and [`Filled`](Node::Filled) nodes are left untouched.
+    pub fn or_insert_with<F>(self, f: F) -> &'a mut Node<T>
+    where
+        F: FnOnce() -> T,
+    {
+        if matches!(self.tree.get(self.index), Node::Empty) {
+            self.tree.set(self.index, Node::Filled(f()));
+        }
+        self.tree.get_mut(self.index)
+    }
+
+    /// Applies `f` to value of the node if it is [`Filled`](Node::Filled) and returns the entry back.
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut T),
+    {
+        if matches!(self.tree.get(self.index), Node::Filled(_)) {
+            if let Node::Filled(value) = self.tree.get_mut(self.index) {
+                f(value);
+            }
+        }
+        self
+    }
+
+    /// Replaces the node by [`Empty`](Node::Empty) and returns the node previously stored in it.
+    pub fn take(self) -> Node<T> {
+        self.tree.set(self.index, Node::Empty)
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Returns [`Entry`] of the node on `position` for in-place changes.
+    ///
+    /// [NodeIndex] is expected to be always valid.
+    pub fn entry<P>(&mut self, position: P) -> Entry<'_, T, SIZE>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        Entry {
+            tree: self,
+            index: position.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod entry_tests {
+    use crate::{LayerPosition, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn entry() {
+        let mut tree = TestTree::new();
+        let position = LayerPosition::new(3, 1, 2, 0);
+        for _ in 0..3 {
+            tree.entry(position)
+                .and_modify(|value| *value += 1)
+                .or_insert_with(|| 1);
+        }
+        assert_eq!(tree.get(position), &Node::Filled(3));
+        assert_eq!(tree.entry(position).index(), NodeIndex::new(39));
+
+        tree.set(NodeIndex::new(64), Node::Reduced);
+        let entry = tree.entry(NodeIndex::new(64));
+        assert_eq!(entry.get(), &Node::Reduced);
+        assert_eq!(entry.or_insert_with(|| 5), &mut Node::Reduced);
+
+        assert_eq!(tree.entry(position).take(), Node::Filled(3));
+        assert_eq!(tree.get(position), &Node::Empty);
+        assert_eq!(tree.entry(position).take(), Node::Empty);
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt::Display;
+
+/// Errors which can occur when constructing a [`Tree`](crate::Tree) or its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeError {
+    /// Amount of provided nodes does not match the amount required by [`Tree`](crate::Tree).
+    InvalidNodesLength {
+        /// Required amount of nodes, either exact or maximal depending on the operation.
+        expected: usize,
+        /// Provided amount of nodes.
+        actual: usize,
+    },
+    /// Provided [`AxisMapping`](crate::AxisMapping) does not use every axis exactly once.
+    InvalidAxisMapping,
+}
+
+/// [`Display`] describes the error together with relevant values.
+impl Display for TreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TreeError::InvalidNodesLength { expected, actual } => write!(
+                f,
+                "invalid amount of nodes: expected {expected}, got {actual}"
+            ),
+            TreeError::InvalidAxisMapping => {
+                write!(
+                    f,
+                    "invalid axis mapping: every axis has to be used exactly once"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TreeError {}
+
+/// Errors which can occur when creating a coordinate, which is not valid in associated [`Tree`](crate::Tree).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoordinateError {
+    /// Provided index is out of bounds of the tree or of the layer.
+    InvalidIndex {
+        /// Provided index.
+        index: usize,
+        /// Amount of nodes in the tree or in the layer.
+        size: usize,
+    },
+    /// Provided depth is not a layer of [`Tree`](crate::Tree).
+    InvalidDepth {
+        /// Provided depth.
+        depth: usize,
+        /// Index of the deepest layer of [`Tree`](crate::Tree).
+        max: usize,
+    },
+    /// Provided position is outside of the layer.
+    InvalidPosition {
+        /// Provided `x`, `y` and `z`.
+        position: [usize; 3],
+        /// Provided depth.
+        depth: usize,
+        /// Row size which each coordinate has to be less than.
+        row_size: usize,
+    },
+    /// Provided [`NodePosition`](crate::NodePosition) is not aligned to a node in its depth.
+    UnalignedPosition {
+        /// Provided `x`, `y` and `z`.
+        position: [usize; 3],
+        /// Provided depth.
+        depth: usize,
+        /// Value which each coordinate has to be a multiple of.
+        alignment: usize,
+    },
+}
+
+/// [`Display`] describes the error together with relevant values.
+impl Display for CoordinateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoordinateError::InvalidIndex { index, size } => {
+                write!(f, "invalid index: expected less than {size}, got {index}")
+            }
+            CoordinateError::InvalidDepth { depth, max } => {
+                write!(f, "invalid depth: expected at most {max}, got {depth}")
+            }
+            CoordinateError::InvalidPosition {
+                position: [x, y, z],
+                depth,
+                row_size,
+            } => write!(
+                f,
+                "invalid position ( x: {x}, y: {y}, z: {z} ) in depth {depth}: \
+                 expected coordinates less than {row_size}"
+            ),
+            CoordinateError::UnalignedPosition {
+                position: [x, y, z],
+                depth,
+                alignment,
+            } => write!(
+                f,
+                "unaligned position ( x: {x}, y: {y}, z: {z} ) in depth {depth}: \
+                 expected coordinates to be multiples of {alignment}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CoordinateError {}
+
+/// Problem found by [`Tree::from_nodes_lossy`](crate::Tree::from_nodes_lossy),
+/// the node it concerns was skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportIssue {
+    /// Position of the node is not valid in [`Tree`](crate::Tree).
+    InvalidPosition {
+        /// Order of the node in the input.
+        item: usize,
+        /// Reason why the position is not valid.
+        error: CoordinateError,
+    },
+    /// Position of the node was already set by an earlier node of the input.
+    DuplicatePosition {
+        /// Order of the node in the input.
+        item: usize,
+        /// Order of the earlier node which was kept.
+        first: usize,
+    },
+}
+
+/// [`Display`] describes the issue together with relevant values.
+impl Display for ImportIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImportIssue::InvalidPosition { item, error } => {
+                write!(f, "node {item} skipped: {error}")
+            }
+            ImportIssue::DuplicatePosition { item, first } => {
+                write!(
+                    f,
+                    "node {item} skipped: position already set by node {first}"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::fmt::Debug;
+
+use crate::{Children, Direction, Node, NodeIndex, Tree, TreeInterface};
+
+/// Coverage of one face of a node by [`Filled`](Node::Filled) leaves,
+/// returned by [`Tree::face_coverage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaceCoverage {
+    /// Amount of leaves touching the face which are filled.
+    pub covered: usize,
+    /// Amount of leaves touching the face.
+    pub area: usize,
+}
+
+impl FaceCoverage {
+    /// Returns covered part of the face, between `0.0` and `1.0`.
+    pub fn ratio(self) -> f32 {
+        self.covered as f32 / self.area as f32
+    }
+
+    /// Returns `true` if the whole face is covered.
+    pub fn is_full(self) -> bool {
+        self.covered == self.area
+    }
+
+    /// Returns `true` if no part of the face is covered.
+    pub fn is_empty(self) -> bool {
+        self.covered == 0
+    }
+}
+
+/// Amounts of filled leaves touching every face of every parrent, ordered by [`Direction::ALL`].
+pub(crate) type FaceCoverages = Vec<[u32; 6]>;
+
+/// Returns index of `direction` in [`Direction::ALL`].
+fn direction_index(direction: Direction) -> usize {
+    direction as usize
+}
+
+/// Returns `true` if child in `octant` touches the face of its parrent in `direction`.
+fn touches(octant: usize, direction: Direction) -> bool {
+    let bit = match direction {
+        Direction::Left | Direction::Right => octant & 1,
+        Direction::Bottom | Direction::Top => (octant >> 1) & 1,
+        Direction::Front | Direction::Back => octant >> 2,
+    };
+    let high = matches!(
+        direction,
+        Direction::Right | Direction::Top | Direction::Back
+    );
+    (bit == 1) == high
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Starts computing coverage of faces of every parrent during [`build`](Tree::build),
+    /// [`build_with_context`](Tree::build_with_context),
+    /// [`build_with_reducer`](Tree::build_with_reducer) and when
+    /// [`IncrementalBuild`](crate::IncrementalBuild) finishes.
+    ///
+    /// Coverage reflects leaves at the time of the last build, same as deeper layers.
+    /// Trees created by [`build_streaming`](Tree::build_streaming) start with it disabled.
+    /// Does nothing if face coverage is already computed.
+    pub fn enable_face_coverage(&mut self) {
+        if self.face_coverage.is_none() {
+            self.face_coverage = Some(Box::default());
+        }
+    }
+
+    /// Stops computing face coverage and forgets the computed one.
+    pub fn disable_face_coverage(&mut self) {
+        self.face_coverage = None;
+    }
+
+    /// Returns how much of face of node on `position` in `direction` is covered
+    /// by [`Filled`](Node::Filled) leaves.
+    ///
+    /// Leaves cover their faces fully when filled. Returns [`None`] for deeper nodes if face
+    /// coverage is not [enabled](Tree::enable_face_coverage) or the tree was not built since.
+    pub fn face_coverage<P>(&self, position: P, direction: Direction) -> Option<FaceCoverage>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        let index: NodeIndex<Self> = position.into();
+        let depth = index.depth();
+        if depth == 0 {
+            let covered = usize::from(matches!(self.stored[index], Node::Filled(_)));
+            return Some(FaceCoverage { covered, area: 1 });
+        }
+        let coverages = self.face_coverage.as_deref()?;
+        let covered = coverages.get(index.raw() - Self::LEAF_LAYER_LEN)?;
+        Some(FaceCoverage {
+            covered: covered[direction_index(direction)] as usize,
+            area: 1 << (2 * depth),
+        })
+    }
+
+    /// Recomputes face coverage of all parrents from leaves, if it is enabled.
+    pub(crate) fn refresh_face_coverage(&mut self) {
+        let Some(coverages) = self.face_coverage.as_deref_mut() else {
+            return;
+        };
+        coverages.clear();
+        coverages.resize(SIZE - Self::LEAF_LAYER_LEN, [0; 6]);
+        for raw in Self::LEAF_LAYER_LEN..SIZE {
+            let index = NodeIndex::<Self>::new(raw);
+            let Some(children) = Children::new(index) else {
+                continue;
+            };
+            let mut covered = [0; 6];
+            for (octant, child) in children.enumerate() {
+                let child_covered = if child.depth() == 0 {
+                    [u32::from(matches!(self.stored[child], Node::Filled(_))); 6]
+                } else {
+                    coverages[child.raw() - Self::LEAF_LAYER_LEN]
+                };
+                for direction in Direction::ALL {
+                    if touches(octant, direction) {
+                        let i = direction_index(direction);
+                        covered[i] += child_covered[i];
+                    }
+                }
+            }
+            coverages[raw - Self::LEAF_LAYER_LEN] = covered;
+        }
+    }
+}
+
+#[cfg(test)]
+mod face_coverage_tests {
+    use crate::{Direction, LayerPosition, Node, Tree};
+
+    use super::FaceCoverage;
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn face_coverage() {
+        let mut tree = TestTree::new();
+        tree.enable_face_coverage();
+        assert_eq!(
+            tree.face_coverage(LayerPosition::new(0, 0, 0, 2), Direction::Left),
+            None
+        );
+
+        // Fill the whole left column of leaves and one leaf on the right.
+        for y in 0..4 {
+            for z in 0..4 {
+                tree.set(LayerPosition::new(0, y, z, 0), Node::Filled(1));
+            }
+        }
+        tree.set(LayerPosition::new(3, 0, 0, 0), Node::Filled(1));
+        tree.build(|_| Node::Reduced);
+
+        let root = LayerPosition::new(0, 0, 0, 2);
+        let left = tree.face_coverage(root, Direction::Left).unwrap();
+        assert!(left.is_full());
+        assert_eq!(
+            left,
+            FaceCoverage {
+                covered: 16,
+                area: 16
+            }
+        );
+        let right = tree.face_coverage(root, Direction::Right).unwrap();
+        assert_eq!(right.covered, 1);
+        assert_eq!(right.ratio(), 1.0 / 16.0);
+        assert_eq!(
+            tree.face_coverage(root, Direction::Bottom).unwrap().covered,
+            5
+        );
+
+        let parrent = LayerPosition::new(1, 0, 0, 1);
+        assert!(tree
+            .face_coverage(parrent, Direction::Left)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            tree.face_coverage(parrent, Direction::Right),
+            Some(FaceCoverage {
+                covered: 1,
+                area: 4
+            })
+        );
+        assert_eq!(
+            tree.face_coverage(LayerPosition::new(3, 0, 0, 0), Direction::Top),
+            Some(FaceCoverage {
+                covered: 1,
+                area: 1
+            })
+        );
+
+        tree.disable_face_coverage();
+        assert_eq!(tree.face_coverage(root, Direction::Left), None);
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Long running operation on [`Tree`] which can be split into small steps and resumed,
+/// processed by [`Tree::process_incrementally`].
+///
+/// Task keeps its own state between steps, so it is passed by mutable reference.
+pub trait TreeTask<T, const SIZE: usize> {
+    /// Processes one step of the task and returns whether there is any work left.
+    fn step(&mut self, tree: &mut Tree<T, SIZE>) -> Progress;
+}
+
+/// Limit of work done by one call of [`Tree::process_incrementally`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Stop when time spent processing exceeds the duration.
+    Duration(Duration),
+    /// Stop after the amount of steps.
+    Steps(usize),
+}
+
+/// State of a [`TreeTask`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    /// There is still work left.
+    Pending,
+    /// Task is finished.
+    Done,
+}
+
+/// [`TreeTask`] which [builds](Tree::build) [`Tree`] one row of parrents along `x` per step,
+/// so time does not have to be checked after every parrent.
+///
+/// [Face coverage](Tree::enable_face_coverage) is refreshed by the last step.
+#[derive(Debug)]
+pub struct IncrementalBuild<T, F, const SIZE: usize> {
+    combine_rule: F,
+    /// Raw index of the next parrent to build.
+    next: usize,
+    /// Associated [`Tree`].
+    boo: PhantomData<Tree<T, SIZE>>,
+}
+
+impl<T, F, const SIZE: usize> IncrementalBuild<T, F, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+{
+    /// Creates a new [`IncrementalBuild`] with the same `combine_rule` as is passed
+    /// to [`build`](Tree::build).
+    pub fn new(combine_rule: F) -> Self {
+        Self {
+            combine_rule,
+            next: Tree::<T, SIZE>::LEAF_LAYER_LEN,
+            boo: PhantomData,
+        }
+    }
+}
+
+impl<T, F, const SIZE: usize> TreeTask<T, SIZE> for IncrementalBuild<T, F, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+    F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+{
+    fn step(&mut self, tree: &mut Tree<T, SIZE>) -> Progress {
+        if self.next >= SIZE {
+            return Progress::Done;
+        }
+        let depth = NodeIndex::<Tree<T, SIZE>>::new(self.next).depth();
+        let row_size = Tree::<T, SIZE>::row_size(depth).get();
+        let start = Tree::<T, SIZE>::layer_start(depth);
+        let row_end = start + ((self.next - start) / row_size + 1) * row_size;
+        // Layers are stored from bottom up, so children are always built before their parrent.
+        for raw in self.next..row_end {
+            let index = NodeIndex::new(raw);
+            if let Some(children) = tree.children(index) {
+                let node = (self.combine_rule)(&children.map(|child| tree.get(child)));
+                tree.set(index, node);
+            }
+        }
+        self.next = row_end;
+        if self.next >= SIZE {
+            tree.refresh_face_coverage();
+            Progress::Done
+        } else {
+            Progress::Pending
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Processes steps of `task` until it is done or `budget` is exhausted, so long operations
+    /// can be spread over multiple calls. Returns [`Progress`] of `task` after the last step.
+    ///
+    /// At least one step is processed on every call, time is checked after each step.
+    pub fn process_incrementally<K>(&mut self, task: &mut K, budget: Budget) -> Progress
+    where
+        K: TreeTask<T, SIZE>,
+    {
+        let start = Instant::now();
+        let mut steps = 0;
+        loop {
+            if task.step(self) == Progress::Done {
+                return Progress::Done;
+            }
+            steps += 1;
+            let exhausted = match budget {
+                Budget::Duration(duration) => start.elapsed() >= duration,
+                Budget::Steps(max) => steps >= max,
+            };
+            if exhausted {
+                return Progress::Pending;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod incremental_tests {
+    use std::time::Duration;
+
+    use crate::{Direction, LayerPosition, Node, NodeIndex, Tree};
+
+    use super::{Budget, IncrementalBuild, Progress};
+
+    type TestTree = Tree<usize, 73>;
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+            Node::Empty
+        } else {
+            Node::Reduced
+        }
+    }
+
+    #[test]
+    fn process_incrementally() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(42), Node::Filled(1));
+        let mut built = tree.clone();
+        built.build(combine_rule);
+
+        let mut task = IncrementalBuild::new(combine_rule);
+        // Layer in depth 1 has 4 rows of 2 parrents.
+        for _ in 0..2 {
+            assert_eq!(
+                tree.process_incrementally(&mut task, Budget::Steps(2)),
+                Progress::Pending
+            );
+        }
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Reduced);
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Empty);
+        assert_eq!(
+            tree.process_incrementally(&mut task, Budget::Steps(4)),
+            Progress::Done
+        );
+        assert_eq!(tree, built);
+        assert_eq!(
+            tree.process_incrementally(&mut task, Budget::Steps(4)),
+            Progress::Done
+        );
+
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(42), Node::Filled(1));
+        let mut task = IncrementalBuild::new(combine_rule);
+        while tree.process_incrementally(&mut task, Budget::Duration(Duration::ZERO))
+            == Progress::Pending
+        {}
+        assert_eq!(tree, built);
+    }
+
+    #[test]
+    fn face_coverage() {
+        let mut tree = TestTree::new();
+        tree.enable_face_coverage();
+        tree.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        let mut built = tree.clone();
+        built.build(combine_rule);
+
+        let mut task = IncrementalBuild::new(combine_rule);
+        tree.process_incrementally(&mut task, Budget::Steps(usize::MAX));
+        let root = LayerPosition::new(0, 0, 0, 2);
+        assert_eq!(
+            tree.face_coverage(root, Direction::Left),
+            built.face_coverage(root, Direction::Left)
+        );
+        assert_eq!(
+            tree.face_coverage(root, Direction::Left)
+                .map(|coverage| coverage.covered),
+            Some(1)
+        );
+    }
+}
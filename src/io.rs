@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::io::{self, Read, Write};
+
+use crate::{MicroGrid, Node, NodeIndex, Tree, TreeInterface};
+
+// Tags written before every serialized [`Node`].
+const EMPTY_TAG: u8 = 0;
+const REDUCED_TAG: u8 = 1;
+const FILLED_TAG: u8 = 2;
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Serializes [`Tree`] into `writer`, storing all layers with depth at least
+    /// `max_detail_depth` and below it only children of [`Reduced`](Node::Reduced) nodes.
+    ///
+    /// Children of [`Filled`](Node::Filled) and [`Empty`](Node::Empty) nodes in detailed layers are
+    /// treated as homogeneous and are reconstructed from their parrent by
+    /// [`read_truncated`](Tree::read_truncated), so with `max_detail_depth` of 0 the tree is
+    /// stored losslessly. [Refinements](Tree::refine_leaf) are stored only for stored leaves.
+    /// Values are written by `encode`.
+    ///
+    /// Returns [`InvalidInput`](io::ErrorKind::InvalidInput) error if `max_detail_depth` is greater
+    /// than [MAX_DEPTH_INDEX](TreeInterface::MAX_DEPTH_INDEX).
+    pub fn write_truncated<W, F>(
+        &self,
+        writer: &mut W,
+        max_detail_depth: usize,
+        mut encode: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&T, &mut W) -> io::Result<()>,
+    {
+        if max_detail_depth > Self::MAX_DEPTH_INDEX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "max detail depth is out of bounds",
+            ));
+        }
+        write_size::<_, SIZE>(writer)?;
+        writer.write_all(&[max_detail_depth as u8])?;
+
+        let mut written = vec![false; SIZE];
+        for depth in (0..Self::DEPTH).rev() {
+            for index in Self::layer_indexes(depth) {
+                if !self.is_stored(index, max_detail_depth, |parrent| written[parrent.raw()]) {
+                    continue;
+                }
+                written[index.raw()] = true;
+                write_node(writer, self.get(index), &mut encode)?;
+            }
+        }
+        let refinements = self
+            .refinements
+            .iter()
+            .filter(|(index, _)| written[**index])
+            .collect::<Vec<_>>();
+        write_refinements(writer, refinements)
+    }
+
+    /// Deserializes [`Tree`] written by [`write_truncated`](Tree::write_truncated) from `reader`,
+    /// reading values by `decode`.
+    ///
+    /// Nodes which were not stored are copied from their parrent.
+    pub fn read_truncated<R, F>(reader: &mut R, mut decode: F) -> io::Result<Self>
+    where
+        R: Read,
+        F: FnMut(&mut R) -> io::Result<T>,
+        T: Clone,
+    {
+        read_size::<_, SIZE>(reader)?;
+        let mut max_detail_depth = [0];
+        reader.read_exact(&mut max_detail_depth)?;
+        let max_detail_depth = max_detail_depth[0] as usize;
+        if max_detail_depth > Self::MAX_DEPTH_INDEX {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "max detail depth is out of bounds",
+            ));
+        }
+
+        let mut tree = Self::new();
+        for depth in (0..Self::DEPTH).rev() {
+            for index in Self::layer_indexes(depth) {
+                let stored = tree.is_stored(index, max_detail_depth, |parrent| {
+                    matches!(tree.get(parrent), Node::Reduced)
+                });
+                let node = if stored {
+                    read_node(reader, &mut decode)?
+                } else {
+                    match tree.parrent(index).map(|parrent| tree.get(parrent)) {
+                        Some(Node::Filled(value)) => Node::Filled(value.clone()),
+                        _ => Node::Empty,
+                    }
+                };
+                tree.set(index, node);
+            }
+        }
+        tree.refinements = read_refinements(reader, Self::LEAF_LAYER_LEN)?;
+        Ok(tree)
+    }
+
+    /// Returns `true` if node on `index` is serialized, `parrent_detailed` decides
+    /// whether parrent of a node below `max_detail_depth` has its children serialized.
+    fn is_stored<F>(
+        &self,
+        index: NodeIndex<Self>,
+        max_detail_depth: usize,
+        parrent_detailed: F,
+    ) -> bool
+    where
+        F: FnOnce(NodeIndex<Self>) -> bool,
+    {
+        if index.depth() >= max_detail_depth {
+            return true;
+        }
+        match self.parrent(index) {
+            Some(parrent) => {
+                parrent_detailed(parrent) && matches!(self.get(parrent), Node::Reduced)
+            }
+            None => true,
+        }
+    }
+
+    /// Returns all indexes in layer in `depth`.
+    fn layer_indexes(depth: usize) -> impl Iterator<Item = NodeIndex<Self>> {
+        let start = Self::layer_start(depth);
+        (start..start + Self::layer_size(depth).get()).map(NodeIndex::new)
+    }
+}
+
+/// Writes `SIZE` of serialized [`Tree`] into `writer`.
+pub(crate) fn write_size<W, const SIZE: usize>(writer: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    writer.write_all(&(SIZE as u64).to_le_bytes())
+}
+
+/// Reads size of serialized [`Tree`] from `reader` and checks it is equal to `SIZE`.
+pub(crate) fn read_size<R, const SIZE: usize>(reader: &mut R) -> io::Result<()>
+where
+    R: Read,
+{
+    let mut size = [0; 8];
+    reader.read_exact(&mut size)?;
+    if u64::from_le_bytes(size) != SIZE as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "tree size does not match",
+        ));
+    }
+    Ok(())
+}
+
+/// Writes amount of `refinements` into `writer`, followed by raw index of every refined leaf
+/// and bits of its [`MicroGrid`].
+pub(crate) fn write_refinements<'a, W, I>(writer: &mut W, refinements: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = (&'a usize, &'a MicroGrid)>,
+    I::IntoIter: ExactSizeIterator,
+{
+    let refinements = refinements.into_iter();
+    writer.write_all(&(refinements.len() as u64).to_le_bytes())?;
+    for (index, grid) in refinements {
+        writer.write_all(&(*index as u64).to_le_bytes())?;
+        writer.write_all(&grid.bits().to_le_bytes())?;
+    }
+    Ok(())
+}
+
+/// Reads refinements written by [`write_refinements`] from `reader` and checks every
+/// refined index is one of the first `leaves` indexes.
+pub(crate) fn read_refinements<R>(
+    reader: &mut R,
+    leaves: usize,
+) -> io::Result<BTreeMap<usize, MicroGrid>>
+where
+    R: Read,
+{
+    let mut buffer = [0; 8];
+    reader.read_exact(&mut buffer)?;
+    let count = u64::from_le_bytes(buffer);
+    let mut refinements = BTreeMap::new();
+    for _ in 0..count {
+        reader.read_exact(&mut buffer)?;
+        let index = u64::from_le_bytes(buffer) as usize;
+        if index >= leaves {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "refined node is not a leaf",
+            ));
+        }
+        reader.read_exact(&mut buffer)?;
+        refinements.insert(index, MicroGrid::new(u64::from_le_bytes(buffer)));
+    }
+    Ok(refinements)
+}
+
+/// Writes tag of `node` into `writer`, followed by its value written by `encode` if it is
+/// [`Filled`](Node::Filled).
+pub(crate) fn write_node<T, W, F>(writer: &mut W, node: &Node<T>, mut encode: F) -> io::Result<()>
+where
+    W: Write,
+    F: FnMut(&T, &mut W) -> io::Result<()>,
+{
+    match node {
+        Node::Empty => writer.write_all(&[EMPTY_TAG]),
+        Node::Reduced => writer.write_all(&[REDUCED_TAG]),
+        Node::Filled(value) => {
+            writer.write_all(&[FILLED_TAG])?;
+            encode(value, writer)
+        }
+    }
+}
+
+/// Reads [`Node`] written by [`write_node`] from `reader`, reading its value by `decode`.
+pub(crate) fn read_node<T, R, F>(reader: &mut R, mut decode: F) -> io::Result<Node<T>>
+where
+    R: Read,
+    F: FnMut(&mut R) -> io::Result<T>,
+{
+    let mut tag = [0];
+    reader.read_exact(&mut tag)?;
+    match tag[0] {
+        EMPTY_TAG => Ok(Node::Empty),
+        REDUCED_TAG => Ok(Node::Reduced),
+        FILLED_TAG => Ok(Node::Filled(decode(reader)?)),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid node tag",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod io_tests {
+    use std::io::{ErrorKind, Read, Write};
+
+    use crate::{MicroGrid, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<u8, 73>;
+
+    fn encode(value: &u8, writer: &mut Vec<u8>) -> std::io::Result<()> {
+        writer.write_all(&[*value])
+    }
+
+    fn decode(reader: &mut &[u8]) -> std::io::Result<u8> {
+        let mut value = [0];
+        reader.read_exact(&mut value)?;
+        Ok(value[0])
+    }
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        for i in 0..8 {
+            tree.set(NodeIndex::new(i), Node::Filled(i as u8));
+        }
+        tree.set(NodeIndex::new(20), Node::Filled(20));
+        tree.set(NodeIndex::new(64), Node::Filled(64));
+        tree.set(NodeIndex::new(65), Node::Reduced);
+        tree.set(NodeIndex::new(72), Node::Reduced);
+        tree
+    }
+
+    #[test]
+    fn lossless() {
+        let tree = tree();
+        let mut bytes = Vec::new();
+        tree.write_truncated(&mut bytes, 0, encode).unwrap();
+        let read = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+    }
+
+    #[test]
+    fn refinements() {
+        let mut tree = tree();
+        tree.refine_leaf(NodeIndex::new(2), MicroGrid::new(0b110));
+        tree.refine_leaf(NodeIndex::new(0), MicroGrid::FULL);
+        let mut bytes = Vec::new();
+        tree.write_truncated(&mut bytes, 0, encode).unwrap();
+        let read = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read, tree);
+
+        // Leaf 0 is copied from its filled parrent, so its refinement is not stored.
+        bytes.clear();
+        tree.write_truncated(&mut bytes, 1, encode).unwrap();
+        let read = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap();
+        assert_eq!(read.refinement(NodeIndex::new(0)), None);
+        assert_eq!(
+            read.refinement(NodeIndex::new(2)),
+            Some(MicroGrid::new(0b110))
+        );
+
+        let len = bytes.len();
+        bytes[len - 16..len - 8].copy_from_slice(&64u64.to_le_bytes());
+        let error = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn truncated() {
+        let tree = tree();
+        let mut lossless = Vec::new();
+        tree.write_truncated(&mut lossless, 0, encode).unwrap();
+        let mut bytes = Vec::new();
+        tree.write_truncated(&mut bytes, 1, encode).unwrap();
+        assert!(bytes.len() < lossless.len());
+
+        let read = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap();
+        // Children of filled node are copied from it.
+        for index in [0, 1, 4, 5, 16, 17, 20, 21] {
+            assert_eq!(read.get(NodeIndex::new(index)), &Node::Filled(64));
+        }
+        // Children of reduced node are stored.
+        assert_eq!(read.get(NodeIndex::new(2)), &Node::Filled(2));
+        assert_eq!(read.get(NodeIndex::new(6)), &Node::Filled(6));
+        assert_eq!(read.get(NodeIndex::new(18)), &Node::Empty);
+        // Children of empty node are empty.
+        assert_eq!(read.get(NodeIndex::new(63)), &Node::Empty);
+        for index in 64..73 {
+            assert_eq!(
+                read.get(NodeIndex::new(index)),
+                tree.get(NodeIndex::new(index))
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_max_detail_depth() {
+        let tree = tree();
+        let mut bytes = Vec::new();
+        let error = tree.write_truncated(&mut bytes, 3, encode).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+        assert!(bytes.is_empty());
+
+        tree.write_truncated(&mut bytes, 2, encode).unwrap();
+        bytes[8] = 3;
+        let error = TestTree::read_truncated(&mut bytes.as_slice(), decode).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn invalid_size() {
+        let mut bytes = Vec::new();
+        Tree::<u8, 9>::new()
+            .write_truncated(&mut bytes, 0, encode)
+            .unwrap();
+        assert!(TestTree::read_truncated(&mut bytes.as_slice(), decode).is_err());
+    }
+}
@@ -0,0 +1,2022 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::ops::{Bound, Range, RangeBounds};
+use std::slice;
+
+use crate::{
+    Axis, Direction, LayerPosition, MicroGrid, Node, NodeIndex, NodePosition, OctreeAccess,
+    PositionRange, PositionRangeIter, RowSize, Tree, TreeInterface, OCTANT_OFFSETS,
+};
+
+/// Iterator over all [`nodes`](Node) of [`Tree`] together with their [`indexes`](NodeIndex),
+/// created by [`Tree::iter`].
+#[derive(Debug)]
+pub struct Iter<'a, T, const SIZE: usize> {
+    inner: Enumerate<slice::Iter<'a, Node<T>>>,
+}
+
+impl<'a, T, const SIZE: usize> Iter<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: nodes.iter().enumerate(),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for Iter<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, node) = self.inner.next()?;
+        Some((NodeIndex::new(index), node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, const SIZE: usize> DoubleEndedIterator for Iter<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, node) = self.inner.next_back()?;
+        Some((NodeIndex::new(index), node))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for Iter<'a, T, SIZE> where
+    Tree<T, SIZE>: TreeInterface
+{
+}
+
+/// Iterator over mutable references to all [`nodes`](Node) of [`Tree`] together with their
+/// [`indexes`](NodeIndex), created by [`Tree::iter_mut`].
+#[derive(Debug)]
+pub struct IterMut<'a, T, const SIZE: usize> {
+    inner: Enumerate<slice::IterMut<'a, Node<T>>>,
+}
+
+impl<'a, T, const SIZE: usize> IterMut<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a mut [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: nodes.iter_mut().enumerate(),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for IterMut<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a mut Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, node) = self.inner.next()?;
+        Some((NodeIndex::new(index), node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, const SIZE: usize> DoubleEndedIterator for IterMut<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, node) = self.inner.next_back()?;
+        Some((NodeIndex::new(index), node))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for IterMut<'a, T, SIZE> where
+    Tree<T, SIZE>: TreeInterface
+{
+}
+
+/// Iterator over values of [`Filled`](Node::Filled) nodes of [`Tree`]
+/// together with their [`indexes`](NodeIndex), created by [`Tree::filled`].
+#[derive(Debug)]
+pub struct Filled<'a, T, const SIZE: usize> {
+    inner: Iter<'a, T, SIZE>,
+}
+
+impl<'a, T, const SIZE: usize> Filled<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: Iter::new(nodes),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for Filled<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|(index, node)| match node {
+            Node::Filled(value) => Some((index, value)),
+            _ => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<'a, T, const SIZE: usize> DoubleEndedIterator for Filled<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .rev()
+            .find_map(|(index, node)| match node {
+                Node::Filled(value) => Some((index, value)),
+                _ => None,
+            })
+    }
+}
+
+/// Iterator over mutable references to values of [`Filled`](Node::Filled) nodes of [`Tree`]
+/// together with their [`indexes`](NodeIndex), created by [`Tree::filled_mut`].
+#[derive(Debug)]
+pub struct FilledMut<'a, T, const SIZE: usize> {
+    inner: IterMut<'a, T, SIZE>,
+}
+
+impl<'a, T, const SIZE: usize> FilledMut<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a mut [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: IterMut::new(nodes),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for FilledMut<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|(index, node)| match node {
+            Node::Filled(value) => Some((index, value)),
+            _ => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+impl<'a, T, const SIZE: usize> DoubleEndedIterator for FilledMut<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .by_ref()
+            .rev()
+            .find_map(|(index, node)| match node {
+                Node::Filled(value) => Some((index, value)),
+                _ => None,
+            })
+    }
+}
+
+/// Iterator moving values out of [`Filled`](Node::Filled) nodes of [`Tree`] together with their
+/// [`indexes`](NodeIndex), created by [`Tree::drain_filled`].
+///
+/// Every drained node is left [`Empty`](Node::Empty). When dropped, the rest of filled nodes
+/// is drained as well.
+#[derive(Debug)]
+pub struct DrainFilled<'a, T, const SIZE: usize> {
+    inner: Enumerate<slice::IterMut<'a, Node<T>>>,
+}
+
+impl<'a, T, const SIZE: usize> DrainFilled<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a mut [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: nodes.iter_mut().enumerate(),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for DrainFilled<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.find_map(|(index, node)| match node {
+            Node::Filled(_) => match std::mem::replace(node, Node::Empty) {
+                Node::Filled(value) => Some((NodeIndex::new(index), value)),
+                _ => unreachable!(),
+            },
+            _ => None,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.inner.size_hint().1)
+    }
+}
+
+/// Drains filled nodes which were not yet yielded.
+impl<'a, T, const SIZE: usize> Drop for DrainFilled<'a, T, SIZE> {
+    fn drop(&mut self) {
+        for (_, node) in &mut self.inner {
+            if let Node::Filled(_) = node {
+                *node = Node::Empty;
+            }
+        }
+    }
+}
+
+/// Iterator over all [`nodes`](Node) of [`Tree`] together with their [`positions`](NodePosition),
+/// in the order in which they are stored, created by [`Tree::enumerate_positions`].
+///
+/// Position is advanced incrementally instead of being converted from [`NodeIndex`] for each node.
+#[derive(Debug)]
+pub struct EnumeratePositions<'a, T, const SIZE: usize> {
+    inner: slice::Iter<'a, Node<T>>,
+    /// Position of the next node.
+    position: [usize; 3],
+    /// Layer of the next node.
+    depth: usize,
+}
+
+impl<'a, T, const SIZE: usize> EnumeratePositions<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a [Node<T>; SIZE]) -> Self {
+        Self {
+            inner: nodes.iter(),
+            position: [0; 3],
+            depth: 0,
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for EnumeratePositions<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodePosition<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.inner.next()?;
+        let [x, y, z] = self.position;
+        let position = NodePosition::new(x, y, z, self.depth);
+
+        // Nodes in `depth` are `2^depth` apart, rows are ordered by `x`, then `y` and `z`.
+        let step = 1 << self.depth;
+        for coordinate in &mut self.position {
+            *coordinate += step;
+            if *coordinate < Tree::<T, SIZE>::BIGGEST_ROW_SIZE {
+                return Some((position, node));
+            }
+            *coordinate = 0;
+        }
+        self.depth += 1;
+        Some((position, node))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for EnumeratePositions<'a, T, SIZE> where
+    Tree<T, SIZE>: TreeInterface
+{
+}
+
+/// Iterator over [`nodes`](Node) of the shallowest layer of [`Tree`] together with their
+/// [`indexes`](NodeIndex) in Morton order, created by [`Tree::iter_leaf_morton`].
+///
+/// Morton order visits octants of every node one after another, so spatially close nodes
+/// are yielded close to each other.
+#[derive(Debug)]
+pub struct LeafMorton<'a, T, const SIZE: usize> {
+    nodes: &'a [Node<T>; SIZE],
+    /// Morton code of the next node.
+    code: usize,
+}
+
+impl<'a, T, const SIZE: usize> LeafMorton<'a, T, SIZE> {
+    pub(crate) fn new(nodes: &'a [Node<T>; SIZE]) -> Self {
+        Self { nodes, code: 0 }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for LeafMorton<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.code >= Tree::<T, SIZE>::LEAF_LAYER_LEN {
+            return None;
+        }
+        // Bits of `x`, `y` and `z` are interleaved in this order from the lowest bit.
+        let [mut x, mut y, mut z] = [0; 3];
+        let mut code = self.code;
+        let mut bit = 0;
+        while code != 0 {
+            x |= (code & 1) << bit;
+            y |= ((code >> 1) & 1) << bit;
+            z |= ((code >> 2) & 1) << bit;
+            code >>= 3;
+            bit += 1;
+        }
+        self.code += 1;
+
+        let index = Tree::<T, SIZE>::row_size(0).stride(y, z) + x;
+        Some((NodeIndex::new(index), &self.nodes[index]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = Tree::<T, SIZE>::LEAF_LAYER_LEN - self.code;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for LeafMorton<'a, T, SIZE> where
+    Tree<T, SIZE>: TreeInterface
+{
+}
+
+/// Iterator over [`nodes`](Node) inside of an axis-aligned box of one layer of an
+/// [`OctreeAccess`] together with their [`positions`](LayerPosition), created by
+/// [`Tree::iter_region`] or [`iter_region`](crate::iter_region).
+///
+/// Nodes are yielded in the order in which they are stored, i.e. `x` changes the fastest.
+#[derive(Debug)]
+pub struct Region<'a, A>
+where
+    A: OctreeAccess,
+{
+    access: &'a A,
+    positions: PositionRangeIter<A::Layout>,
+}
+
+impl<'a, A> Region<'a, A>
+where
+    A: OctreeAccess,
+{
+    pub(crate) fn new(access: &'a A, range: PositionRange<A::Layout>) -> Self {
+        Self {
+            access,
+            positions: range.iter(),
+        }
+    }
+}
+
+impl<'a, A> Iterator for Region<'a, A>
+where
+    A: OctreeAccess,
+{
+    type Item = (LayerPosition<A::Layout>, &'a Node<A::Value>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.positions.next()?;
+        Some((position, self.access.node(position.into())))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, A> ExactSizeIterator for Region<'a, A> where A: OctreeAccess {}
+
+/// Iterator over [`nodes`](Node) on the outer faces of one layer of [`Tree`] together with their
+/// [`positions`](LayerPosition), created by [`Tree::iter_shell`].
+///
+/// Nodes are yielded in the order in which they are stored, interior of the layer is skipped
+/// without being visited.
+#[derive(Debug)]
+pub struct Shell<'a, T, const SIZE: usize> {
+    tree: &'a Tree<T, SIZE>,
+    /// Coordinate of the last node of a row.
+    last: usize,
+    /// Position of the next node, [`None`] when all nodes were visited.
+    next: Option<LayerPosition<Tree<T, SIZE>>>,
+    remaining: usize,
+}
+
+impl<'a, T, const SIZE: usize> Shell<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    pub(crate) fn new(tree: &'a Tree<T, SIZE>, depth: usize) -> Self {
+        let row_size = Tree::<T, SIZE>::row_size(depth).get();
+        let interior = row_size.saturating_sub(2).pow(3);
+        Self {
+            tree,
+            last: row_size - 1,
+            next: Some(LayerPosition::new(0, 0, 0, depth)),
+            remaining: row_size.pow(3) - interior,
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for Shell<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (LayerPosition<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.next?;
+        let mut next = position;
+        let outer_row =
+            [position.y, position.z].contains(&0) || [position.y, position.z].contains(&self.last);
+        if next.x < self.last && (outer_row || next.x == 0) {
+            // Rows inside of the layer only have their first and last node on the shell.
+            next.x = if outer_row { next.x + 1 } else { self.last };
+        } else if next.y < self.last {
+            next.x = 0;
+            next.y += 1;
+        } else if next.z < self.last {
+            next.x = 0;
+            next.y = 0;
+            next.z += 1;
+        }
+        self.next = (next != position).then_some(next);
+        self.remaining -= 1;
+        Some((position, self.tree.get(position)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for Shell<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+}
+
+/// Iterator over [`nodes`](Node) of one row of a layer of [`Tree`] together with their
+/// [`positions`](LayerPosition), created by [`Tree::iter_row`].
+#[derive(Debug)]
+pub struct Row<'a, T, const SIZE: usize> {
+    tree: &'a Tree<T, SIZE>,
+    axis: Axis,
+    /// Position of the next node, [`None`] when the end of the row was reached.
+    next: Option<LayerPosition<Tree<T, SIZE>>>,
+}
+
+impl<'a, T, const SIZE: usize> Row<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    pub(crate) fn new(
+        tree: &'a Tree<T, SIZE>,
+        start: LayerPosition<Tree<T, SIZE>>,
+        axis: Axis,
+    ) -> Self {
+        Self {
+            tree,
+            axis,
+            next: Some(start),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for Row<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (LayerPosition<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let position = self.next?;
+        let mut next = position;
+        let coordinate = match self.axis {
+            Axis::X => &mut next.x,
+            Axis::Y => &mut next.y,
+            Axis::Z => &mut next.z,
+        };
+        *coordinate += 1;
+        let row_size = Tree::<T, SIZE>::row_size(position.depth).get();
+        self.next = (*coordinate < row_size).then_some(next);
+        Some((position, self.tree.get(position)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.next.map_or(0, |next| {
+            let coordinate = match self.axis {
+                Axis::X => next.x,
+                Axis::Y => next.y,
+                Axis::Z => next.z,
+            };
+            Tree::<T, SIZE>::row_size(next.depth).get() - coordinate
+        });
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for Row<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+}
+
+/// Owning iterator over all [`nodes`](Node) of [`Tree`] in the order in which they are stored,
+/// created by [`Tree::into_iter`](IntoIterator::into_iter).
+#[derive(Debug)]
+pub struct IntoIter<T> {
+    inner: std::vec::IntoIter<Node<T>>,
+}
+
+impl<T> IntoIter<T> {
+    pub(crate) fn new<const SIZE: usize>(nodes: Box<[Node<T>; SIZE]>) -> Self {
+        let nodes: Box<[Node<T>]> = nodes;
+        Self {
+            inner: nodes.into_vec().into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = Node<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+/// Iterator over ancestors of a node, from its parent up to the root,
+/// created by [`NodeIndex::ancestors`] or [`Tree::ancestors`].
+#[derive(Debug)]
+pub struct Ancestors<U> {
+    current: Option<LayerPosition<U>>,
+}
+
+impl<U> Ancestors<U>
+where
+    U: TreeInterface,
+{
+    pub(crate) fn new(index: NodeIndex<U>) -> Self {
+        Self {
+            current: Some(LayerPosition::from(index)),
+        }
+    }
+}
+
+impl<U> Iterator for Ancestors<U>
+where
+    U: TreeInterface,
+{
+    type Item = NodeIndex<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parrent = self.current?.parrent_position();
+        self.current = parrent;
+        parrent.map(NodeIndex::from)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self
+            .current
+            .map_or(0, |position| U::MAX_DEPTH_INDEX - position.depth);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<U> ExactSizeIterator for Ancestors<U> where U: TreeInterface {}
+
+/// Iterator over [`indexes`](NodeIndex) in a range, created by [`NodeIndex::range`]
+/// or [`PositionRange::index_runs`](crate::PositionRange::index_runs).
+#[derive(Debug)]
+pub struct NodeIndexRange<U> {
+    range: Range<usize>,
+    /// Associated [`Tree`].
+    boo: PhantomData<U>,
+}
+
+impl<U> NodeIndexRange<U>
+where
+    U: TreeInterface,
+{
+    pub(crate) fn new<R>(range: R) -> Self
+    where
+        R: RangeBounds<NodeIndex<U>>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(index) => index.raw(),
+            Bound::Excluded(index) => index.raw() + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(index) => index.raw() + 1,
+            Bound::Excluded(index) => index.raw(),
+            Bound::Unbounded => U::SIZE,
+        };
+        Self {
+            range: start..end,
+            boo: PhantomData,
+        }
+    }
+}
+
+impl<U> Iterator for NodeIndexRange<U>
+where
+    U: TreeInterface,
+{
+    type Item = NodeIndex<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(NodeIndex::new)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.range.nth(n).map(NodeIndex::new)
+    }
+}
+
+impl<U> DoubleEndedIterator for NodeIndexRange<U>
+where
+    U: TreeInterface,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(NodeIndex::new)
+    }
+}
+
+impl<U> ExactSizeIterator for NodeIndexRange<U> where U: TreeInterface {}
+
+/// Iterator over [`indexes`](NodeIndex) of the eight children of a node,
+/// created by [`Tree::children_iter`].
+///
+/// Indexes are computed arithmetically from index of the first child without any allocation.
+#[derive(Debug)]
+pub struct Children<U> {
+    /// Index of a child in bottom front left corner of parrent node.
+    anchor: NodeIndex<U>,
+    /// Row size of childrens layer.
+    row_size: RowSize,
+    /// Octant of the next child.
+    octant: usize,
+}
+
+impl<U> Children<U>
+where
+    U: TreeInterface,
+{
+    /// Returns [Children] of node on `parrent`, or [`None`] if it has no children.
+    pub(crate) fn new(parrent: NodeIndex<U>) -> Option<Self> {
+        let anchor: NodeIndex<U> = NodePosition::from(parrent).child_position()?.into();
+        Some(Self {
+            anchor,
+            row_size: U::row_size(anchor.depth()),
+            octant: 0,
+        })
+    }
+}
+
+impl<U> Iterator for Children<U>
+where
+    U: TreeInterface,
+{
+    type Item = NodeIndex<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.octant >= 8 {
+            return None;
+        }
+        let [x, y, z] = OCTANT_OFFSETS[self.octant];
+        self.octant += 1;
+        Some(self.anchor + (x + self.row_size.stride(y, z)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = 8 - self.octant;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<U> ExactSizeIterator for Children<U> where U: TreeInterface {}
+
+/// Iterator over [`indexes`](NodeIndex) of the other children of parrent of a node,
+/// created by [`Tree::siblings`].
+///
+/// Siblings are yielded in the same order as [`Tree::children`] returns them, the root has none.
+#[derive(Debug)]
+pub struct Siblings<U> {
+    /// Children of parrent, [`None`] for the root.
+    children: Option<Children<U>>,
+    /// Octant of the node itself within its parrent, which is skipped.
+    skip: usize,
+}
+
+impl<U> Siblings<U>
+where
+    U: TreeInterface,
+{
+    pub(crate) fn new(index: NodeIndex<U>) -> Self {
+        let children = LayerPosition::from(index)
+            .parrent_position()
+            .and_then(|parrent| Children::new(parrent.into()));
+        Self {
+            children,
+            skip: index.octant().unwrap_or(0),
+        }
+    }
+}
+
+impl<U> Iterator for Siblings<U>
+where
+    U: TreeInterface,
+{
+    type Item = NodeIndex<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let children = self.children.as_mut()?;
+        if children.octant == self.skip {
+            children.next();
+        }
+        children.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.children.as_ref().map_or(0, |children| {
+            let remaining = 8 - children.octant;
+            if children.octant <= self.skip {
+                remaining - 1
+            } else {
+                remaining
+            }
+        });
+        (remaining, Some(remaining))
+    }
+}
+
+impl<U> ExactSizeIterator for Siblings<U> where U: TreeInterface {}
+
+/// Iterator over [`indexes`](NodeIndex) of nodes sharing a face with a node in the same layer,
+/// together with [`Direction`] towards them, created by [`Tree::neighbors6`].
+///
+/// Neighbours outside of the layer are skipped, others are yielded in order of [`Direction::ALL`].
+#[derive(Debug)]
+pub struct FaceNeighbors<U> {
+    position: LayerPosition<U>,
+    /// Index of the next direction in [`Direction::ALL`].
+    next: usize,
+}
+
+impl<U> FaceNeighbors<U>
+where
+    U: TreeInterface,
+{
+    pub(crate) fn new(index: NodeIndex<U>) -> Self {
+        Self {
+            position: index.into(),
+            next: 0,
+        }
+    }
+}
+
+impl<U> Iterator for FaceNeighbors<U>
+where
+    U: TreeInterface,
+{
+    type Item = (Direction, NodeIndex<U>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(direction) = Direction::ALL.get(self.next).copied() {
+            self.next += 1;
+            if let Some(neighbor) = self.position.neighbor(direction) {
+                return Some((direction, neighbor.into()));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(Direction::ALL.len() - self.next))
+    }
+}
+
+/// Iterator over [`indexes`](NodeIndex) of all nodes in the same layer which touch a node
+/// by a face, an edge or a corner, together with offset towards them, created by
+/// [`Tree::neighbors26`].
+///
+/// Neighbours outside of the layer are skipped, others are ordered by offset on `z`, then `y`
+/// and `x`, from `-1` to `1`.
+#[derive(Debug)]
+pub struct Neighbors<U> {
+    position: LayerPosition<U>,
+    /// Index of the next offset in the 3x3x3 cube around the node.
+    next: usize,
+}
+
+impl<U> Neighbors<U>
+where
+    U: TreeInterface,
+{
+    pub(crate) fn new(index: NodeIndex<U>) -> Self {
+        Self {
+            position: index.into(),
+            next: 0,
+        }
+    }
+}
+
+impl<U> Iterator for Neighbors<U>
+where
+    U: TreeInterface,
+{
+    type Item = ([isize; 3], NodeIndex<U>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next < 27 {
+            let next = self.next as isize;
+            self.next += 1;
+            // The middle of the cube is the node itself.
+            if next == 13 {
+                continue;
+            }
+            let offset = [next % 3 - 1, next / 3 % 3 - 1, next / 9 - 1];
+            if let Some(neighbor) = self.position.checked_offset(offset) {
+                return Some((offset, neighbor.into()));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = 27 - self.next;
+        (
+            0,
+            Some(if self.next <= 13 {
+                remaining - 1
+            } else {
+                remaining
+            }),
+        )
+    }
+}
+
+/// Breadth-first iterator over a subtree of [`Tree`], created by [`Tree::bfs_from`].
+#[derive(Debug)]
+pub struct Bfs<'a, T, const SIZE: usize> {
+    tree: &'a Tree<T, SIZE>,
+    /// Nodes which are yet to be visited.
+    queue: VecDeque<NodeIndex<Tree<T, SIZE>>>,
+}
+
+impl<'a, T, const SIZE: usize> Bfs<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    pub(crate) fn new(tree: &'a Tree<T, SIZE>, start: NodeIndex<Tree<T, SIZE>>) -> Self {
+        Self {
+            tree,
+            queue: VecDeque::from([start]),
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for Bfs<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.queue.pop_front()?;
+        if let Some(children) = self.tree.children(index) {
+            self.queue.extend(children);
+        }
+        Some((index, self.tree.get(index)))
+    }
+}
+
+/// Returns children of node on `index` in `access` as an array, so they can be prefetched.
+fn child_array<A>(access: &A, index: NodeIndex<A::Layout>) -> Option<[NodeIndex<A::Layout>; 8]>
+where
+    A: OctreeAccess,
+{
+    let mut children = access.children(index)?;
+    // `Children` always yields exactly 8 indexes, so this never panics.
+    Some(std::array::from_fn(|_| children.next().unwrap()))
+}
+
+/// Depth-first iterator over all nodes of an [`OctreeAccess`] which are not
+/// [`Empty`](Node::Empty), created by [`Tree::descend_filled`]
+/// or [`descend_filled`](crate::descend_filled).
+#[derive(Debug)]
+pub struct DescendFilled<'a, A>
+where
+    A: OctreeAccess,
+{
+    access: &'a A,
+    /// Nodes which are yet to be visited.
+    stack: Vec<NodeIndex<A::Layout>>,
+}
+
+impl<'a, A> DescendFilled<'a, A>
+where
+    A: OctreeAccess,
+{
+    pub(crate) fn new(access: &'a A) -> Self {
+        Self {
+            access,
+            stack: vec![access.root()],
+        }
+    }
+}
+
+impl<'a, A> Iterator for DescendFilled<'a, A>
+where
+    A: OctreeAccess,
+{
+    type Item = (NodeIndex<A::Layout>, &'a Node<A::Value>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.stack.pop() {
+            let node = self.access.node(index);
+            if let Node::Empty = node {
+                continue;
+            }
+            if let Some(children) = child_array(self.access, index) {
+                self.access.prefetch_children(&children);
+                // Reversed so children are visited in the same order as they are stored.
+                self.stack.extend(children.into_iter().rev());
+            }
+            return Some((index, node));
+        }
+        None
+    }
+}
+
+/// Iterator over the biggest [`Empty`](Node::Empty) nodes of an [`OctreeAccess`],
+/// created by [`Tree::iter_empty_octants`] or [`iter_empty_octants`](crate::iter_empty_octants).
+#[derive(Debug)]
+pub struct EmptyOctants<'a, A>
+where
+    A: OctreeAccess,
+{
+    access: &'a A,
+    min_depth: usize,
+    /// Nodes which are yet to be visited.
+    stack: Vec<NodeIndex<A::Layout>>,
+}
+
+impl<'a, A> EmptyOctants<'a, A>
+where
+    A: OctreeAccess,
+{
+    pub(crate) fn new(access: &'a A, min_depth: usize) -> Self {
+        Self {
+            access,
+            min_depth,
+            stack: vec![access.root()],
+        }
+    }
+}
+
+impl<'a, A> Iterator for EmptyOctants<'a, A>
+where
+    A: OctreeAccess,
+{
+    type Item = NodeIndex<A::Layout>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.stack.pop() {
+            let depth = self.access.depth(index);
+            if let Node::Empty = self.access.node(index) {
+                if depth >= self.min_depth {
+                    return Some(index);
+                }
+                continue;
+            }
+            if depth > self.min_depth {
+                if let Some(children) = child_array(self.access, index) {
+                    // Reversed so children are visited in the same order as they are stored.
+                    self.stack.extend(children.into_iter().rev());
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over [`nodes`](Node) of one layer of [`Tree`] in groups of eight siblings,
+/// together with [`index`](NodeIndex) of their common parrent, created by [`Tree::octant_groups`].
+///
+/// Parrents are yielded in the order in which they are stored and siblings are in the same
+/// order as [`Tree::children`] returns them.
+#[derive(Debug)]
+pub struct OctantGroups<'a, T, const SIZE: usize> {
+    tree: &'a Tree<T, SIZE>,
+    /// Raw indexes of parrents which are yet to be yielded.
+    parrents: Range<usize>,
+}
+
+impl<'a, T, const SIZE: usize> OctantGroups<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    pub(crate) fn new(tree: &'a Tree<T, SIZE>, depth: usize) -> Self {
+        let parrents = if depth < Tree::<T, SIZE>::MAX_DEPTH_INDEX {
+            let start = Tree::<T, SIZE>::layer_start(depth + 1);
+            start..start + Tree::<T, SIZE>::layer_size(depth + 1).get()
+        } else {
+            0..0
+        };
+        Self { tree, parrents }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for OctantGroups<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, [&'a Node<T>; 8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parrent = NodeIndex::new(self.parrents.next()?);
+        // Only parrents are iterated, so they always have children.
+        let mut children = Children::new(parrent).unwrap();
+        // `Children` always yields exactly 8 indexes, so this never panics.
+        let nodes = std::array::from_fn(|_| self.tree.get(children.next().unwrap()));
+        Some((parrent, nodes))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.parrents.size_hint()
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for OctantGroups<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+}
+
+/// Iterator over pairs of consecutive layers of [`Tree`] as slices of [`nodes`](Node),
+/// from the shallowest layer up, created by [`Tree::layer_pairs`].
+///
+/// Every pair holds a layer together with the layer of its parrents.
+#[derive(Debug)]
+pub struct LayerPairs<'a, T, const SIZE: usize> {
+    /// Nodes starting with the child layer of the next pair.
+    rest: &'a [Node<T>],
+    /// Depth of the child layer of the next pair.
+    depth: usize,
+}
+
+impl<'a, T, const SIZE: usize> LayerPairs<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    pub(crate) fn new(stored: &'a [Node<T>; SIZE]) -> Self {
+        Self {
+            rest: stored,
+            depth: 0,
+        }
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for LayerPairs<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    type Item = (&'a [Node<T>], &'a [Node<T>]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.depth >= Tree::<T, SIZE>::MAX_DEPTH_INDEX {
+            return None;
+        }
+        let (children, rest) = self
+            .rest
+            .split_at(Tree::<T, SIZE>::layer_size(self.depth).get());
+        let parrents = &rest[..Tree::<T, SIZE>::layer_size(self.depth + 1).get()];
+        self.rest = rest;
+        self.depth += 1;
+        Some((children, parrents))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = Tree::<T, SIZE>::MAX_DEPTH_INDEX.saturating_sub(self.depth);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for LayerPairs<'a, T, SIZE> where
+    Tree<T, SIZE>: TreeInterface
+{
+}
+
+/// Iterator over values of [`Filled`](Node::Filled) nodes of the shallowest layer of an
+/// [`OctreeAccess`] which share a face with an [`Empty`](Node::Empty) node or with the border
+/// of the layer, together with their [`indexes`](NodeIndex), created by [`Tree::surface`]
+/// or [`surface`](crate::surface).
+///
+/// Leaves [refined](Tree::refine_leaf) by a [`MicroGrid`] which is not full
+/// are always on the surface and do not enclose their neighbours.
+#[derive(Debug)]
+pub struct Surface<'a, A>
+where
+    A: OctreeAccess,
+{
+    access: &'a A,
+    /// Nodes which are yet to be visited.
+    stack: Vec<NodeIndex<A::Layout>>,
+}
+
+impl<'a, A> Surface<'a, A>
+where
+    A: OctreeAccess,
+{
+    pub(crate) fn new(access: &'a A) -> Self {
+        Self {
+            access,
+            stack: vec![access.root()],
+        }
+    }
+
+    /// Returns `true` if all face neighbours of node on `index` are inside of the layer
+    /// and `solid` returns `true` for them.
+    fn is_enclosed<F>(&self, index: NodeIndex<A::Layout>, solid: F) -> bool
+    where
+        F: Fn(NodeIndex<A::Layout>, &Node<A::Value>) -> bool,
+    {
+        let mut neighbors = 0;
+        for (_, neighbor) in FaceNeighbors::new(index) {
+            if !solid(neighbor, self.access.node(neighbor)) {
+                return false;
+            }
+            neighbors += 1;
+        }
+        neighbors == Direction::ALL.len()
+    }
+}
+
+impl<'a, A> Iterator for Surface<'a, A>
+where
+    A: OctreeAccess,
+{
+    type Item = (NodeIndex<A::Layout>, &'a A::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(index) = self.stack.pop() {
+            let node = self.access.node(index);
+            match child_array(self.access, index) {
+                Some(children) => {
+                    // Filled parrent enclosed by filled parrents holds only interior nodes,
+                    // unless some of them are refined.
+                    let interior = matches!(node, Node::Filled(_))
+                        && !self.access.has_refinements()
+                        && self.is_enclosed(index, |_, node| matches!(node, Node::Filled(_)));
+                    if !matches!(node, Node::Empty) && !interior {
+                        self.access.prefetch_children(&children);
+                        // Reversed so children are visited in the same order as they are stored.
+                        self.stack.extend(children.into_iter().rev());
+                    }
+                }
+                None => {
+                    if let Node::Filled(value) = node {
+                        let is_full = |index| {
+                            self.access
+                                .refinement(index)
+                                .is_none_or(|grid| grid == MicroGrid::FULL)
+                        };
+                        if !is_full(index)
+                            || !self.is_enclosed(index, |neighbor, node| {
+                                !matches!(node, Node::Empty) && is_full(neighbor)
+                            })
+                        {
+                            return Some((index, value));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod iter_tests {
+    use crate::{Node, NodeIndex, NodesRaw, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    fn tree() -> TestTree {
+        let mut nodes = NodesRaw::new();
+        for i in 0..73 {
+            nodes.push(Node::Filled(i));
+        }
+        TestTree::from(nodes)
+    }
+
+    #[test]
+    fn iter() {
+        let tree = tree();
+        let mut iter = tree.iter();
+        assert_eq!(iter.len(), 73);
+        assert_eq!(iter.next(), Some((NodeIndex::new(0), &Node::Filled(0))));
+        assert_eq!(
+            iter.next_back(),
+            Some((NodeIndex::new(72), &Node::Filled(72)))
+        );
+        for (index, node) in iter {
+            assert_eq!(node, &Node::Filled(index.raw()));
+        }
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut tree = tree();
+        for (index, node) in tree.iter_mut() {
+            if index.depth() > 0 {
+                *node = Node::Empty;
+            }
+        }
+        assert_eq!(tree.get(NodeIndex::new(63)), &Node::Filled(63));
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Empty);
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Empty);
+    }
+}
+
+#[cfg(test)]
+mod filled_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(3), Node::Filled(3));
+        tree.set(NodeIndex::new(10), Node::Reduced);
+        tree.set(NodeIndex::new(64), Node::Filled(64));
+        tree.set(NodeIndex::new(72), Node::Filled(72));
+        tree
+    }
+
+    #[test]
+    fn filled() {
+        let tree = tree();
+        assert_eq!(
+            tree.filled().collect::<Vec<_>>(),
+            vec![
+                (NodeIndex::new(3), &3),
+                (NodeIndex::new(64), &64),
+                (NodeIndex::new(72), &72)
+            ]
+        );
+        assert_eq!(tree.filled().next_back(), Some((NodeIndex::new(72), &72)));
+        assert_eq!(TestTree::new().filled().next(), None);
+    }
+
+    #[test]
+    fn filled_mut() {
+        let mut tree = tree();
+        for (index, value) in tree.filled_mut() {
+            *value = index.raw() * 2;
+        }
+        assert_eq!(tree.get(NodeIndex::new(3)), &Node::Filled(6));
+        assert_eq!(tree.get(NodeIndex::new(10)), &Node::Reduced);
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(144));
+        assert_eq!(tree.filled_mut().rev().count(), 3);
+    }
+}
+
+#[cfg(test)]
+mod enumerate_positions_tests {
+    use crate::{NodeIndex, NodePosition, Tree};
+
+    #[test]
+    fn enumerate_positions() {
+        let tree = Tree::<usize, 585>::new();
+        let positions = tree.enumerate_positions();
+        assert_eq!(positions.len(), 585);
+        for (index, (position, node)) in positions.enumerate() {
+            assert_eq!(position, NodePosition::from(NodeIndex::new(index)));
+            assert_eq!(node, tree.get(position));
+        }
+
+        let tree = Tree::<usize, 73>::new();
+        let mut positions = tree
+            .enumerate_positions()
+            .skip(63)
+            .map(|(position, _)| position);
+        assert_eq!(positions.next(), Some(NodePosition::new(3, 3, 3, 0)));
+        assert_eq!(positions.next(), Some(NodePosition::new(0, 0, 0, 1)));
+        assert_eq!(positions.next(), Some(NodePosition::new(2, 0, 0, 1)));
+        assert_eq!(positions.last(), Some(NodePosition::new(0, 0, 0, 2)));
+    }
+}
+
+#[cfg(test)]
+mod leaf_morton_tests {
+    use crate::{LayerPosition, NodeIndex, Tree};
+
+    #[test]
+    fn iter_leaf_morton() {
+        let tree = Tree::<usize, 73>::new();
+        let iter = tree.iter_leaf_morton();
+        assert_eq!(iter.len(), 64);
+
+        let indexes = iter.map(|(index, _)| index.raw()).collect::<Vec<_>>();
+        // The first eight nodes are children of the first node in depth 1.
+        assert_eq!(
+            &indexes[0..8],
+            tree.children(NodeIndex::new(64))
+                .unwrap()
+                .map(NodeIndex::raw)
+                .as_slice()
+        );
+        assert_eq!(&indexes[8..10], &[2, 3]);
+        assert_eq!(indexes[63], 63);
+
+        let mut sorted = indexes.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..64).collect::<Vec<_>>());
+
+        let tree = Tree::<usize, 585>::new();
+        assert_eq!(
+            tree.iter_leaf_morton().nth(9).map(|(index, _)| index),
+            Some(LayerPosition::new(3, 0, 0, 0).into())
+        );
+    }
+}
+
+#[cfg(test)]
+mod region_tests {
+    use crate::{LayerPosition, Node, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn iter_region() {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(2, 1, 3, 0), Node::Filled(1));
+        tree.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(2));
+
+        let region =
+            tree.iter_region(LayerPosition::new(1, 1, 2, 0)..=LayerPosition::new(2, 3, 3, 0));
+        assert_eq!(region.len(), 12);
+        let positions = region.map(|(position, _)| position).collect::<Vec<_>>();
+        assert_eq!(positions.len(), 12);
+        assert_eq!(positions[0], LayerPosition::new(1, 1, 2, 0));
+        assert_eq!(positions[1], LayerPosition::new(2, 1, 2, 0));
+        assert_eq!(positions[2], LayerPosition::new(1, 2, 2, 0));
+        assert_eq!(positions[11], LayerPosition::new(2, 3, 3, 0));
+
+        let filled = tree
+            .iter_region(LayerPosition::new(1, 1, 2, 0)..=LayerPosition::new(2, 3, 3, 0))
+            .filter(|(_, node)| **node != Node::Empty)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            filled,
+            vec![(LayerPosition::new(2, 1, 3, 0), &Node::Filled(1))]
+        );
+
+        let mut single =
+            tree.iter_region(LayerPosition::new(0, 0, 0, 0)..=LayerPosition::new(0, 0, 0, 0));
+        assert_eq!(
+            single.next(),
+            Some((LayerPosition::new(0, 0, 0, 0), &Node::Filled(2)))
+        );
+        assert_eq!(single.len(), 0);
+        assert_eq!(single.next(), None);
+
+        assert_eq!(
+            tree.iter_region(LayerPosition::new(2, 0, 0, 0)..=LayerPosition::new(1, 3, 3, 0))
+                .count(),
+            0
+        );
+        assert_eq!(
+            tree.iter_region(LayerPosition::new(0, 0, 0, 1)..=LayerPosition::new(1, 1, 1, 1))
+                .count(),
+            8
+        );
+    }
+}
+
+#[cfg(test)]
+mod row_tests {
+    use crate::{Axis, LayerPosition, Node, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn iter_row() {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(1, 3, 2, 0), Node::Filled(1));
+
+        let row = tree.iter_row(LayerPosition::new(1, 0, 2, 0), Axis::Y);
+        assert_eq!(row.len(), 4);
+        assert_eq!(
+            row.collect::<Vec<_>>(),
+            vec![
+                (LayerPosition::new(1, 0, 2, 0), &Node::Empty),
+                (LayerPosition::new(1, 1, 2, 0), &Node::Empty),
+                (LayerPosition::new(1, 2, 2, 0), &Node::Empty),
+                (LayerPosition::new(1, 3, 2, 0), &Node::Filled(1)),
+            ]
+        );
+
+        let mut row = tree.iter_row(LayerPosition::new(2, 3, 2, 0), Axis::X);
+        assert_eq!(row.len(), 2);
+        assert_eq!(
+            row.next().map(|(position, _)| position),
+            Some(LayerPosition::new(2, 3, 2, 0))
+        );
+        assert_eq!(
+            row.next().map(|(position, _)| position),
+            Some(LayerPosition::new(3, 3, 2, 0))
+        );
+        assert_eq!(row.next(), None);
+
+        assert_eq!(
+            tree.iter_row(LayerPosition::new(0, 1, 0, 1), Axis::Z)
+                .count(),
+            2
+        );
+        assert_eq!(
+            tree.iter_row(LayerPosition::new(0, 0, 0, 2), Axis::X)
+                .count(),
+            1
+        );
+    }
+}
+
+#[cfg(test)]
+mod drain_filled_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<String, 73>;
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(3), Node::Filled("a".to_string()));
+        tree.set(NodeIndex::new(10), Node::Reduced);
+        tree.set(NodeIndex::new(64), Node::Filled("b".to_string()));
+        tree.set(NodeIndex::new(72), Node::Filled("c".to_string()));
+        tree
+    }
+
+    #[test]
+    fn drain_filled() {
+        let mut tree = tree();
+        assert_eq!(
+            tree.drain_filled().collect::<Vec<_>>(),
+            vec![
+                (NodeIndex::new(3), "a".to_string()),
+                (NodeIndex::new(64), "b".to_string()),
+                (NodeIndex::new(72), "c".to_string()),
+            ]
+        );
+        assert_eq!(tree.get(NodeIndex::new(3)), &Node::Empty);
+        assert_eq!(tree.get(NodeIndex::new(10)), &Node::Reduced);
+        assert_eq!(tree.filled().count(), 0);
+    }
+
+    #[test]
+    fn drain_filled_drop() {
+        let mut tree = tree();
+        let mut drain = tree.drain_filled();
+        assert_eq!(drain.next(), Some((NodeIndex::new(3), "a".to_string())));
+        drop(drain);
+        assert_eq!(tree.filled().count(), 0);
+        assert_eq!(tree.get(NodeIndex::new(10)), &Node::Reduced);
+    }
+}
+
+#[cfg(test)]
+mod into_iter_tests {
+    use crate::{Node, NodeIndex, NodesRaw, Tree};
+
+    type TestTree = Tree<String, 73>;
+
+    fn tree() -> TestTree {
+        let mut nodes = NodesRaw::new();
+        for i in 0..73 {
+            nodes.push(Node::Filled(i.to_string()));
+        }
+        TestTree::from(nodes)
+    }
+
+    #[test]
+    fn into_iter() {
+        let nodes = tree().into_iter().collect::<Vec<_>>();
+        assert_eq!(nodes.len(), 73);
+        assert_eq!(nodes[72], Node::Filled("72".to_string()));
+    }
+
+    #[test]
+    fn into_iter_ref() {
+        let tree = tree();
+        let mut count = 0;
+        for (index, node) in &tree {
+            assert_eq!(node, &Node::Filled(index.raw().to_string()));
+            count += 1;
+        }
+        assert_eq!(count, 73);
+    }
+
+    #[test]
+    fn into_iter_mut() {
+        let mut tree = tree();
+        for (_, node) in &mut tree {
+            *node = Node::Reduced;
+        }
+        assert_eq!(tree.get(NodeIndex::new(5)), &Node::Reduced);
+    }
+}
+
+#[cfg(test)]
+mod ancestors_tests {
+    use crate::{NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn ancestors() {
+        let tree = TestTree::new();
+        assert_eq!(
+            tree.ancestors(NodeIndex::new(63)).collect::<Vec<_>>(),
+            vec![NodeIndex::new(71), NodeIndex::new(72)]
+        );
+        assert_eq!(tree.ancestors(NodeIndex::new(2)).len(), 2);
+        assert_eq!(
+            NodeIndex::<TestTree>::new(65)
+                .ancestors()
+                .collect::<Vec<_>>(),
+            vec![NodeIndex::new(72)]
+        );
+        assert_eq!(NodeIndex::<TestTree>::new(72).ancestors().next(), None);
+
+        let index = NodeIndex::<Tree<usize, 585>>::new(511);
+        assert_eq!(
+            index.ancestors().map(NodeIndex::raw).collect::<Vec<_>>(),
+            vec![575, 583, 584]
+        );
+    }
+}
+
+#[cfg(test)]
+mod siblings_tests {
+    use crate::{NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn siblings() {
+        let tree = TestTree::new();
+        assert_eq!(tree.siblings(NodeIndex::new(72)).next(), None);
+        assert_eq!(tree.siblings(NodeIndex::new(72)).len(), 0);
+
+        let siblings = tree.siblings(NodeIndex::new(6));
+        assert_eq!(siblings.len(), 7);
+        assert_eq!(
+            siblings.collect::<Vec<_>>(),
+            [2, 3, 7, 18, 19, 22, 23].map(NodeIndex::new).to_vec()
+        );
+
+        let mut siblings = tree.siblings(NodeIndex::new(64));
+        assert_eq!(siblings.next(), Some(NodeIndex::new(65)));
+        assert_eq!(siblings.len(), 6);
+        assert_eq!(siblings.last(), Some(NodeIndex::new(71)));
+
+        let mut siblings = tree.siblings(NodeIndex::new(67));
+        assert_eq!(siblings.len(), 7);
+        assert_eq!(siblings.nth(3), Some(NodeIndex::new(68)));
+
+        assert_eq!(
+            tree.siblings(NodeIndex::new(63)).last(),
+            Some(NodeIndex::new(62))
+        );
+    }
+}
+
+#[cfg(test)]
+mod face_neighbors_tests {
+    use crate::{Direction, LayerPosition, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn neighbors6() {
+        let tree = TestTree::new();
+        let neighbors = tree
+            .neighbors6(LayerPosition::new(1, 2, 1, 0))
+            .collect::<Vec<_>>();
+        assert_eq!(neighbors.len(), 6);
+        assert_eq!(
+            neighbors[0],
+            (Direction::Left, LayerPosition::new(0, 2, 1, 0).into())
+        );
+        assert_eq!(
+            neighbors[5],
+            (Direction::Back, LayerPosition::new(1, 2, 2, 0).into())
+        );
+
+        let directions = tree
+            .neighbors6(LayerPosition::new(0, 0, 1, 1))
+            .map(|(direction, _)| direction)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            directions,
+            vec![Direction::Right, Direction::Top, Direction::Front]
+        );
+
+        assert_eq!(tree.neighbors6(LayerPosition::new(0, 0, 0, 2)).count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod neighbors_tests {
+    use crate::{LayerPosition, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn neighbors26() {
+        let tree = TestTree::new();
+        let neighbors = tree
+            .neighbors26(LayerPosition::new(1, 1, 1, 0))
+            .collect::<Vec<_>>();
+        assert_eq!(neighbors.len(), 26);
+        assert_eq!(
+            neighbors[0],
+            ([-1, -1, -1], LayerPosition::new(0, 0, 0, 0).into())
+        );
+        assert_eq!(
+            neighbors[13],
+            ([1, 0, 0], LayerPosition::new(2, 1, 1, 0).into())
+        );
+        assert_eq!(
+            neighbors[25],
+            ([1, 1, 1], LayerPosition::new(2, 2, 2, 0).into())
+        );
+
+        assert_eq!(tree.neighbors26(LayerPosition::new(0, 0, 0, 0)).count(), 7);
+        assert_eq!(tree.neighbors26(LayerPosition::new(0, 1, 0, 1)).count(), 7);
+        assert_eq!(tree.neighbors26(LayerPosition::new(0, 1, 2, 0)).count(), 17);
+        assert_eq!(tree.neighbors26(NodeIndex::new(72)).count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod bfs_tests {
+    use crate::{NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn bfs_from() {
+        let tree = TestTree::new();
+
+        let order = tree
+            .bfs_from(NodeIndex::new(72))
+            .map(|(index, _)| index.depth())
+            .collect::<Vec<_>>();
+        assert_eq!(order.len(), 73);
+        assert!(order.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let order = tree
+            .bfs_from(NodeIndex::new(65))
+            .map(|(index, _)| index.raw())
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![65, 2, 3, 6, 7, 18, 19, 22, 23]);
+
+        assert_eq!(tree.bfs_from(NodeIndex::new(3)).count(), 1);
+    }
+}
+
+#[cfg(test)]
+mod descend_filled_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn descend_filled() {
+        let tree = TestTree::new();
+        assert_eq!(tree.descend_filled().count(), 0);
+
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(2), Node::Filled(2));
+        tree.set(NodeIndex::new(23), Node::Filled(23));
+        // Not reachable, parent is empty.
+        tree.set(NodeIndex::new(63), Node::Filled(63));
+        tree.set(NodeIndex::new(65), Node::Reduced);
+        tree.set(NodeIndex::new(72), Node::Reduced);
+
+        assert_eq!(
+            tree.descend_filled()
+                .map(|(index, _)| index.raw())
+                .collect::<Vec<_>>(),
+            vec![72, 65, 2, 23]
+        );
+    }
+}
+
+#[cfg(test)]
+mod empty_octants_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn iter_empty_octants() {
+        let tree = TestTree::new();
+        assert_eq!(
+            tree.iter_empty_octants(0).collect::<Vec<_>>(),
+            vec![NodeIndex::new(72)]
+        );
+
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(0), Node::Filled(0));
+        tree.set(NodeIndex::new(64), Node::Reduced);
+        tree.set(NodeIndex::new(72), Node::Reduced);
+        assert_eq!(
+            tree.iter_empty_octants(0).collect::<Vec<_>>(),
+            vec![
+                NodeIndex::new(1),
+                NodeIndex::new(4),
+                NodeIndex::new(5),
+                NodeIndex::new(16),
+                NodeIndex::new(17),
+                NodeIndex::new(20),
+                NodeIndex::new(21),
+                NodeIndex::new(65),
+                NodeIndex::new(66),
+                NodeIndex::new(67),
+                NodeIndex::new(68),
+                NodeIndex::new(69),
+                NodeIndex::new(70),
+                NodeIndex::new(71),
+            ]
+        );
+        assert_eq!(
+            tree.iter_empty_octants(1).collect::<Vec<_>>(),
+            (65..72).map(NodeIndex::new).collect::<Vec<_>>()
+        );
+        assert_eq!(tree.iter_empty_octants(2).count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod surface_tests {
+    use crate::implemented_tree_sizes::TREE_8;
+    use crate::{LayerPosition, MicroGrid, Node, NodeIndex, Tree};
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+            Node::Empty
+        } else if nodes.iter().all(|node| matches!(node, Node::Filled(_))) {
+            Node::Filled(1)
+        } else {
+            Node::Reduced
+        }
+    }
+
+    #[test]
+    fn surface() {
+        let mut tree = Tree::<usize, 73>::new();
+        for index in [0, 1, 4, 5, 16, 17, 20, 21, 42] {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        tree.build(combine_rule);
+        assert_eq!(
+            tree.surface()
+                .map(|(index, _)| index.raw())
+                .collect::<Vec<_>>(),
+            vec![0, 1, 4, 5, 16, 17, 20, 21, 42]
+        );
+    }
+
+    #[test]
+    fn surface_skips_interior() {
+        let mut tree = Tree::<usize, TREE_8>::new();
+        for index in 0..512 {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        tree.build(combine_rule);
+
+        let surface: Vec<_> = tree.surface().map(|(index, _)| index).collect();
+        assert_eq!(surface.len(), 512 - 6 * 6 * 6);
+        for index in surface {
+            assert!(tree.neighbors6(index).count() < 6);
+        }
+    }
+
+    #[test]
+    fn surface_refinements() {
+        let mut tree = Tree::<usize, TREE_8>::new();
+        for index in 0..512 {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        tree.build(combine_rule);
+        tree.refine_leaf(LayerPosition::new(3, 3, 3, 0), MicroGrid::FULL);
+        assert_eq!(tree.surface().count(), 512 - 6 * 6 * 6);
+
+        // Partially refined interior leaf exposes itself and its neighbours.
+        tree.refine_leaf(LayerPosition::new(3, 3, 3, 0), MicroGrid::new(1));
+        let surface: Vec<_> = tree.surface().map(|(index, _)| index).collect();
+        assert_eq!(surface.len(), 512 - 6 * 6 * 6 + 7);
+        assert!(surface.contains(&LayerPosition::new(3, 3, 3, 0).into()));
+        assert!(surface.contains(&LayerPosition::new(3, 4, 3, 0).into()));
+    }
+}
+
+#[cfg(test)]
+mod octant_groups_tests {
+    use crate::{Depth, Node, NodeIndex, NodesRaw, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn octant_groups() {
+        let mut nodes = NodesRaw::new();
+        for i in 0..73 {
+            nodes.push(Node::Filled(i));
+        }
+        let tree = TestTree::from(nodes);
+
+        let groups = tree.octant_groups(Depth::new(0));
+        assert_eq!(groups.len(), 8);
+        for (parrent, children) in groups {
+            let expected = tree.children(parrent).unwrap().map(|child| tree.get(child));
+            assert_eq!(children, expected);
+        }
+
+        let mut groups = tree.octant_groups(Depth::new(1));
+        assert_eq!(groups.len(), 1);
+        let (parrent, children) = groups.next().unwrap();
+        assert_eq!(parrent, NodeIndex::new(72));
+        assert_eq!(
+            children,
+            (64..72)
+                .map(|i| tree.get(NodeIndex::new(i)))
+                .collect::<Vec<_>>()[..]
+        );
+        assert!(groups.next().is_none());
+
+        assert_eq!(tree.octant_groups(Depth::new(2)).count(), 0);
+    }
+}
+
+#[cfg(test)]
+mod layer_pairs_tests {
+    use crate::{Depth, Node, NodeIndex, NodesRaw, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn layer_pairs() {
+        let mut nodes = NodesRaw::new();
+        for i in 0..73 {
+            nodes.push(Node::Filled(i));
+        }
+        let tree = TestTree::from(nodes);
+
+        let mut pairs = tree.layer_pairs();
+        assert_eq!(pairs.len(), 2);
+        let (children, parrents) = pairs.next().unwrap();
+        assert_eq!((children.len(), parrents.len()), (64, 8));
+        assert_eq!(children[63], Node::Filled(63));
+        assert_eq!(parrents[0], Node::Filled(64));
+        let (children, parrents) = pairs.next().unwrap();
+        assert_eq!((children.len(), parrents.len()), (8, 1));
+        assert_eq!(parrents[0], Node::Filled(72));
+        assert!(pairs.next().is_none());
+        assert_eq!(Tree::<usize, 1>::new().layer_pairs().count(), 0);
+    }
+
+    #[test]
+    fn layer_pair_mut() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(42), Node::Filled(1));
+        let (children, parrents) = tree.layer_pair_mut(Depth::new(0));
+        assert_eq!(children[42], Node::Filled(1));
+        assert_eq!(parrents.len(), 8);
+        parrents.fill(Node::Reduced);
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Reduced);
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Empty);
+    }
+}
+
+#[cfg(test)]
+mod node_index_range_tests {
+    use crate::{NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn node_index_range() {
+        let mut raw = Vec::new();
+        for index in NodeIndex::<TestTree>::range(NodeIndex::new(60)..NodeIndex::new(66)) {
+            raw.push(index.raw());
+        }
+        assert_eq!(raw, vec![60, 61, 62, 63, 64, 65]);
+
+        let mut range = NodeIndex::<TestTree>::range(NodeIndex::new(0)..NodeIndex::new(64));
+        assert_eq!(range.len(), 64);
+        assert_eq!(range.next_back(), Some(NodeIndex::new(63)));
+        assert_eq!(range.nth(10), Some(NodeIndex::new(10)));
+        assert_eq!(range.len(), 52);
+        assert_eq!(
+            NodeIndex::<TestTree>::range(NodeIndex::new(5)..NodeIndex::new(5)).next(),
+            None
+        );
+
+        let mut range = NodeIndex::<TestTree>::range(NodeIndex::new(64)..=NodeIndex::new(72));
+        assert_eq!(range.len(), 9);
+        assert_eq!(range.next_back(), Some(NodeIndex::new(72)));
+        assert_eq!(NodeIndex::<TestTree>::range(..).len(), 73);
+        assert_eq!(
+            NodeIndex::<TestTree>::range(NodeIndex::new(70)..).collect::<Vec<_>>(),
+            vec![NodeIndex::new(70), NodeIndex::new(71), NodeIndex::new(72)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod shell_tests {
+    use crate::implemented_tree_sizes::TREE_8;
+    use crate::{Depth, LayerPosition, Node, Tree};
+
+    #[test]
+    fn iter_shell() {
+        let mut tree = Tree::<usize, TREE_8>::new();
+        tree.set(LayerPosition::new(0, 3, 7, 0), Node::Filled(1));
+        tree.set(LayerPosition::new(3, 3, 3, 0), Node::Filled(2));
+
+        let shell = tree.iter_shell(Depth::new(0));
+        assert_eq!(shell.len(), 8 * 8 * 8 - 6 * 6 * 6);
+        let positions = shell.map(|(position, _)| position).collect::<Vec<_>>();
+        assert_eq!(positions.len(), 8 * 8 * 8 - 6 * 6 * 6);
+        let expected = LayerPosition::iter_layer(0)
+            .filter(|position| {
+                [position.x, position.y, position.z]
+                    .iter()
+                    .any(|coordinate| *coordinate == 0 || *coordinate == 7)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(positions, expected);
+
+        let filled = tree
+            .iter_shell(Depth::new(0))
+            .filter(|(_, node)| **node != Node::Empty)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            filled,
+            vec![(LayerPosition::new(0, 3, 7, 0), &Node::Filled(1))]
+        );
+
+        assert_eq!(tree.iter_shell(Depth::new(1)).len(), 4 * 4 * 4 - 2 * 2 * 2);
+        assert_eq!(tree.iter_shell(Depth::new(2)).len(), 8);
+        assert_eq!(tree.iter_shell(Depth::new(3)).count(), 1);
+    }
+}
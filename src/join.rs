@@ -0,0 +1,104 @@
+use std::fmt::Debug;
+
+use crate::{Node, NodeIndex, NodePosition, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Walks leaf layers of this tree and `other` together and collects results of `f` called
+    /// with leaves on the same position, skipping [`None`]s.
+    ///
+    /// With `skip_empty` subtrees which root is [`Empty`](Node::Empty) in either tree are skipped
+    /// without visiting their leaves, so both trees are expected to be [built](Tree::build)
+    /// and `f` is not called for some leaves which are empty in either tree.
+    /// Otherwise results are in the order in which leaves are stored.
+    pub fn spatial_join<U, R, F>(&self, other: &Tree<U, SIZE>, skip_empty: bool, mut f: F) -> Vec<R>
+    where
+        Tree<U, SIZE>: TreeInterface,
+        U: Debug,
+        F: FnMut(&Node<T>, &Node<U>, NodePosition<Self>) -> Option<R>,
+    {
+        let mut results = Vec::new();
+        let mut join = |index: NodeIndex<Self>| {
+            let node = &self.stored[index.raw()];
+            let other_node = &other.stored[index.raw()];
+            if let Some(result) = f(node, other_node, index.into()) {
+                results.push(result);
+            }
+        };
+        if !skip_empty {
+            (0..Self::LEAF_LAYER_LEN).for_each(|index| join(NodeIndex::new(index)));
+            return results;
+        }
+
+        let mut stack = vec![NodeIndex::<Self>::new(SIZE - 1)];
+        while let Some(index) = stack.pop() {
+            if index.depth() == 0 {
+                join(index);
+            } else if !matches!(self.stored[index.raw()], Node::Empty)
+                && !matches!(other.stored[index.raw()], Node::Empty)
+            {
+                // `Children` are always present for nodes which are not leaves.
+                let children = self.children(index).unwrap();
+                stack.extend(children.into_iter().rev());
+            }
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod join_tests {
+    use crate::{Node, NodeIndex, NodePosition, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    /// Returns [`Reduced`](Node::Reduced) if any child is not [`Empty`](Node::Empty).
+    fn combine_rule<T>(children: &[&Node<T>]) -> Node<T> {
+        if children.iter().all(|child| matches!(child, Node::Empty)) {
+            Node::Empty
+        } else {
+            Node::Reduced
+        }
+    }
+
+    #[test]
+    fn spatial_join() {
+        let mut world = TestTree::new();
+        for index in [0, 5, 21, 63] {
+            world.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        world.build(combine_rule);
+        let mut object = Tree::<bool, 73>::new();
+        for index in [5, 6, 42, 63] {
+            object.set(NodeIndex::new(index), Node::Filled(true));
+        }
+        object.build(combine_rule);
+
+        let collide =
+            |a: &Node<usize>, b: &Node<bool>, position: NodePosition<TestTree>| match (a, b) {
+                (Node::Filled(value), Node::Filled(_)) => Some((*value, position)),
+                _ => None,
+            };
+        let expected = vec![
+            (5, NodePosition::new(1, 1, 0, 0)),
+            (63, NodePosition::new(3, 3, 3, 0)),
+        ];
+        assert_eq!(world.spatial_join(&object, false, collide), expected);
+        assert_eq!(world.spatial_join(&object, true, collide), expected);
+
+        let mut visited = 0;
+        world.spatial_join(&object, true, |_, _, _| {
+            visited += 1;
+            None::<()>
+        });
+        // Only leaves of the two bricks which are not empty in both trees are visited.
+        assert_eq!(visited, 16);
+        assert_eq!(
+            world.spatial_join(&object, false, |_, _, _| Some(())).len(),
+            64
+        );
+    }
+}
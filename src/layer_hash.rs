@@ -0,0 +1,208 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use crate::{Node, Tree, TreeInterface};
+
+/// Hashes of all layers of [`Tree`], kept up to date by [`Tree::set`].
+///
+/// Hash of a layer is a wrapping sum of hashes of its nodes together with their indexes,
+/// so it can be updated by a single node without rehashing the whole layer.
+#[derive(Debug, Clone)]
+pub(crate) struct LayerHashes<T> {
+    /// Hashes a node together with its raw index.
+    hash: fn(usize, &Node<T>) -> u64,
+    /// Raw index of the first node of every layer.
+    layer_starts: Vec<usize>,
+    /// Hash of every layer, [`None`] if the layer was changed without being tracked.
+    layers: Vec<Option<u64>>,
+}
+
+impl<T> LayerHashes<T> {
+    /// Returns depth of node on raw `index`.
+    fn depth(&self, index: usize) -> usize {
+        self.layer_starts.partition_point(|start| *start <= index) - 1
+    }
+
+    /// Updates hash of the layer after node on `index` changed from `previous` to `current`.
+    pub(crate) fn record(&mut self, index: usize, previous: &Node<T>, current: &Node<T>) {
+        let depth = self.depth(index);
+        if let Some(hash) = &mut self.layers[depth] {
+            *hash = hash
+                .wrapping_sub((self.hash)(index, previous))
+                .wrapping_add((self.hash)(index, current));
+        }
+    }
+
+    /// Marks hash of layer containing raw `index` as unknown.
+    pub(crate) fn invalidate(&mut self, index: usize) {
+        let depth = self.depth(index);
+        self.layers[depth] = None;
+    }
+
+    /// Marks hashes of all layers as unknown.
+    pub(crate) fn invalidate_all(&mut self) {
+        self.layers.fill(None);
+    }
+
+    /// Returns `true` if any layer has a known hash in both `self` and `other` which differs.
+    fn differs(&self, other: &Self) -> bool {
+        self.layers.iter().zip(&other.layers).any(
+            |(hash, other)| matches!((hash, other), (Some(hash), Some(other)) if hash != other),
+        )
+    }
+}
+
+/// Hashes `node` together with its raw `index`.
+fn hash_node<T>(index: usize, node: &Node<T>) -> u64
+where
+    T: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    index.hash(&mut hasher);
+    match node {
+        Node::Empty => 0_u8.hash(&mut hasher),
+        Node::Reduced => 1_u8.hash(&mut hasher),
+        Node::Filled(value) => {
+            2_u8.hash(&mut hasher);
+            value.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE> {
+    /// Returns `true` if both trees have a known hash of some layer and those hashes differ,
+    /// in which case the trees can not be equal.
+    pub(crate) fn layer_hashes_differ(&self, other: &Self) -> bool {
+        match (&self.hashes, &other.hashes) {
+            (Some(hashes), Some(other)) => hashes.differs(other),
+            _ => false,
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Starts keeping a hash of every layer, which is updated by every [`set`](Tree::set).
+    ///
+    /// When both compared trees have layer hashes, [`PartialEq`] returns `false` as soon as hashes
+    /// of any layer differ, without comparing the nodes. Changes made through
+    /// [`get_mut`](Tree::get_mut) or mutable iterators make hash of the changed layers unknown
+    /// until [`refresh_layer_hashes`](Tree::refresh_layer_hashes) is called.
+    /// Does nothing if layer hashes are already kept.
+    pub fn enable_layer_hashes(&mut self)
+    where
+        T: Hash,
+    {
+        if self.hashes.is_none() {
+            self.hashes = Some(Box::new(LayerHashes {
+                hash: hash_node::<T>,
+                layer_starts: (0..Self::DEPTH).map(Self::layer_start).collect(),
+                layers: vec![None; Self::DEPTH],
+            }));
+            self.refresh_layer_hashes();
+        }
+    }
+
+    /// Stops keeping layer hashes.
+    pub fn disable_layer_hashes(&mut self) {
+        self.hashes = None;
+    }
+
+    /// Rehashes all layers whose hash is unknown.
+    ///
+    /// Does nothing if layer hashes are not [enabled](Tree::enable_layer_hashes).
+    pub fn refresh_layer_hashes(&mut self) {
+        let Some(hashes) = &mut self.hashes else {
+            return;
+        };
+        for depth in 0..hashes.layers.len() {
+            if hashes.layers[depth].is_some() {
+                continue;
+            }
+            let start = Self::layer_start(depth);
+            let end = start + Self::layer_size(depth).get();
+            let hash = (start..end).fold(0_u64, |hash, index| {
+                hash.wrapping_add((hashes.hash)(index, &self.stored[index]))
+            });
+            hashes.layers[depth] = Some(hash);
+        }
+    }
+
+    /// Returns hash of layer in `depth`, or [`None`] if layer hashes are not
+    /// [enabled](Tree::enable_layer_hashes) or the hash is unknown.
+    ///
+    /// Hashes are not stable across builds and platforms, see [determinism](crate#determinism).
+    ///
+    /// Expects in-bounds `depth`.
+    pub fn layer_hash(&self, depth: usize) -> Option<u64> {
+        self.hashes.as_ref()?.layers[depth]
+    }
+
+    /// Returns `true` if both trees have a known hash of layer in `depth` and those hashes
+    /// are equal, in which case the layers are treated as equal.
+    pub(crate) fn layer_hashes_match(&self, other: &Self, depth: usize) -> bool {
+        matches!(
+            (self.layer_hash(depth), other.layer_hash(depth)),
+            (Some(hash), Some(other)) if hash == other
+        )
+    }
+}
+
+#[cfg(test)]
+mod layer_hash_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn layer_hash() {
+        let mut tree = TestTree::new();
+        assert_eq!(tree.layer_hash(0), None);
+        tree.enable_layer_hashes();
+        let empty = tree.layer_hash(0).unwrap();
+
+        tree.set(NodeIndex::new(5), Node::Filled(5));
+        assert_ne!(tree.layer_hash(0), Some(empty));
+        assert_eq!(tree.layer_hash(1), TestTree::new_hashed().layer_hash(1));
+        tree.set(NodeIndex::new(5), Node::Empty);
+        assert_eq!(tree.layer_hash(0), Some(empty));
+
+        *tree.get_mut(NodeIndex::new(64)) = Node::Reduced;
+        assert_eq!(tree.layer_hash(0), Some(empty));
+        assert_eq!(tree.layer_hash(1), None);
+        tree.refresh_layer_hashes();
+        assert!(tree.layer_hash(1).is_some());
+    }
+
+    #[test]
+    fn eq() {
+        let mut tree = TestTree::new_hashed();
+        let mut other = TestTree::new_hashed();
+        assert_eq!(tree, other);
+
+        tree.set(NodeIndex::new(72), Node::Filled(1));
+        assert_ne!(tree, other);
+        other.set(NodeIndex::new(72), Node::Filled(1));
+        assert_eq!(tree, other);
+
+        // Unknown hashes fall back to comparing nodes.
+        for (_, node) in tree.iter_mut() {
+            *node = Node::Filled(2);
+        }
+        assert_ne!(tree, other);
+        assert_eq!(tree.layer_hash(0), None);
+    }
+
+    impl TestTree {
+        fn new_hashed() -> Self {
+            let mut tree = Self::new();
+            tree.enable_layer_hashes();
+            tree
+        }
+    }
+}
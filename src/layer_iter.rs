@@ -0,0 +1,237 @@
+use std::fmt::Debug;
+
+use crate::{Axis, LayerPosition, Node, NodeIndex, Tree, TreeInterface};
+
+/// Iterator over all [`nodes`](Node) of one layer of [`Tree`], or of one of its planes.
+///
+/// Layer is walked plane by plane along chosen [`Axis`], i.e. the coordinate on that axis
+/// changes the slowest. Inside of a plane the remaining axes are walked in `x`, `y`, `z` order,
+/// the first of them changing the fastest.
+#[derive(Debug)]
+pub struct LayerIter<'a, T, const SIZE: usize> {
+    tree: &'a Tree<T, SIZE>,
+    axis: Axis,
+    depth: usize,
+    row_size: usize,
+    /// Index of the first node of the layer.
+    layer_start: usize,
+    /// Coordinate on iterated `axis`.
+    plane: usize,
+    /// Coordinate on iterated `axis` at which the iteration stops.
+    end_plane: usize,
+    /// Faster changing coordinate inside of a plane.
+    u: usize,
+    /// Slower changing coordinate inside of a plane.
+    v: usize,
+}
+
+impl<'a, T, const SIZE: usize> LayerIter<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    /// Creates a new [LayerIter] over layer in `depth` of `tree`, walking it along `axis`.
+    ///
+    /// Expects in-bounds `depth`.
+    pub fn from_tree(tree: &'a Tree<T, SIZE>, depth: usize, axis: Axis) -> Self {
+        Self {
+            tree,
+            axis,
+            depth,
+            row_size: Tree::<T, SIZE>::row_size(depth).get(),
+            layer_start: Tree::<T, SIZE>::layer_start(depth),
+            plane: 0,
+            end_plane: Tree::<T, SIZE>::row_size(depth).get(),
+            u: 0,
+            v: 0,
+        }
+    }
+
+    /// Creates a new [LayerIter] over only one plane of layer in `depth` of `tree`,
+    /// where coordinate on `axis` is equal to `coordinate`.
+    ///
+    /// Expects in-bounds `depth` and `coordinate`.
+    pub fn plane(tree: &'a Tree<T, SIZE>, depth: usize, axis: Axis, coordinate: usize) -> Self {
+        let mut iter = Self::from_tree(tree, depth, axis);
+        debug_assert!(coordinate < iter.row_size);
+        iter.plane = coordinate;
+        iter.end_plane = coordinate + 1;
+        iter
+    }
+
+    /// Returns `x`, `y`, `z` of current position.
+    fn current(&self) -> (usize, usize, usize) {
+        match self.axis {
+            Axis::X => (self.plane, self.u, self.v),
+            Axis::Y => (self.u, self.plane, self.v),
+            Axis::Z => (self.u, self.v, self.plane),
+        }
+    }
+
+    /// Returns amount of positions which were not yet visited.
+    fn remaining(&self) -> usize {
+        if self.plane >= self.end_plane {
+            return 0;
+        }
+        let plane_size = self.row_size * self.row_size;
+        (self.end_plane - self.plane) * plane_size - self.v * self.row_size - self.u
+    }
+}
+
+impl<'a, T, const SIZE: usize> Iterator for LayerIter<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (LayerPosition<Tree<T, SIZE>>, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.plane >= self.end_plane {
+            return None;
+        }
+        let (x, y, z) = self.current();
+        let index =
+            self.layer_start + x + (y * self.row_size) + (z * self.row_size * self.row_size);
+        let item = (
+            LayerPosition::new(x, y, z, self.depth),
+            self.tree.get(NodeIndex::new(index)),
+        );
+
+        self.u += 1;
+        if self.u == self.row_size {
+            self.u = 0;
+            self.v += 1;
+            if self.v == self.row_size {
+                self.v = 0;
+                self.plane += 1;
+            }
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T, const SIZE: usize> ExactSizeIterator for LayerIter<'a, T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+}
+
+#[cfg(test)]
+mod layer_iter_tests {
+    use crate::{Axis, Depth, LayerPosition, Node, NodeIndex, NodesRaw, Tree};
+
+    use super::LayerIter;
+
+    type TestTree = Tree<usize, 73>;
+    type TestLayerPosition = LayerPosition<TestTree>;
+
+    fn tree() -> TestTree {
+        let mut nodes = NodesRaw::new();
+        for i in 0..73 {
+            nodes.push(Node::Filled(i));
+        }
+        TestTree::from(nodes)
+    }
+
+    #[test]
+    fn from_tree() {
+        let tree = tree();
+
+        let layer = LayerIter::from_tree(&tree, 0, Axis::Z).collect::<Vec<_>>();
+        assert_eq!(layer.len(), 64);
+        for (i, (position, node)) in layer.into_iter().enumerate() {
+            assert_eq!(node, &Node::Filled(i));
+            assert_eq!(position, TestLayerPosition::from(NodeIndex::new(i)));
+        }
+
+        let layer = LayerIter::from_tree(&tree, 1, Axis::Z)
+            .map(|(_, node)| node.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(layer, (64..72).map(Node::Filled).collect::<Vec<_>>());
+
+        let mut layer = LayerIter::from_tree(&tree, 2, Axis::X);
+        assert_eq!(
+            layer.next(),
+            Some((TestLayerPosition::new(0, 0, 0, 2), &Node::Filled(72)))
+        );
+        assert_eq!(layer.next(), None);
+    }
+
+    #[test]
+    fn axis_order() {
+        let tree = tree();
+
+        let positions = LayerIter::from_tree(&tree, 1, Axis::X)
+            .map(|(position, _)| position.get_raw())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (0, 0, 0, 1),
+                (0, 1, 0, 1),
+                (0, 0, 1, 1),
+                (0, 1, 1, 1),
+                (1, 0, 0, 1),
+                (1, 1, 0, 1),
+                (1, 0, 1, 1),
+                (1, 1, 1, 1),
+            ]
+        );
+
+        let positions = LayerIter::from_tree(&tree, 1, Axis::Y)
+            .map(|(position, _)| position.get_raw())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (0, 0, 0, 1),
+                (1, 0, 0, 1),
+                (0, 0, 1, 1),
+                (1, 0, 1, 1),
+                (0, 1, 0, 1),
+                (1, 1, 0, 1),
+                (0, 1, 1, 1),
+                (1, 1, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn size_hint() {
+        let tree = tree();
+        let mut layer = LayerIter::from_tree(&tree, 0, Axis::Y);
+        assert_eq!(layer.len(), 64);
+        layer.next();
+        assert_eq!(layer.len(), 63);
+        assert_eq!(layer.count(), 63);
+    }
+
+    #[test]
+    fn plane() {
+        let tree = tree();
+
+        let mut plane = LayerIter::plane(&tree, 0, Axis::Y, 2);
+        assert_eq!(plane.len(), 16);
+        assert_eq!(
+            plane.next(),
+            Some((TestLayerPosition::new(0, 2, 0, 0), &Node::Filled(8)))
+        );
+        assert_eq!(plane.len(), 15);
+        assert!(plane.all(|(position, _)| position.y == 2));
+
+        let positions = tree
+            .slice_plane(Depth::new(1), Axis::Z, 1)
+            .map(|(position, _)| position.get_raw())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![(0, 0, 1, 1), (1, 0, 1, 1), (0, 1, 1, 1), (1, 1, 1, 1)]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use std::marker::PhantomData;
 
-use crate::{NodeIndex, NodePosition, TreeInterface};
+use crate::{Axis, CoordinateError, Direction, NodeIndex, NodePosition, Offset, TreeInterface};
 
 /// Index of [`Node`](crate::Node) in specific layer.
 ///
@@ -24,11 +24,7 @@ pub struct LayerIndex<T> {
 /// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
 impl<T> Clone for LayerIndex<T> {
     fn clone(&self) -> Self {
-        Self {
-            index: self.index,
-            depth: self.depth,
-            boo: PhantomData,
-        }
+        *self
     }
 }
 
@@ -81,8 +77,7 @@ where
     T: TreeInterface,
 {
     fn from(value: LayerPosition<T>) -> Self {
-        let row_size = T::row_size(value.depth);
-        let index = value.x + (value.y * row_size) + (value.z * row_size * row_size);
+        let index = value.x + T::row_size(value.depth).stride(value.y, value.z);
         Self::new(index, value.depth)
     }
 }
@@ -103,11 +98,24 @@ where
         }
     }
 
+    /// Creates a new [LayerIndex] if provided `index` and `depth` are valid,
+    /// otherwise [`CoordinateError`] describing the violated limit is returned.
+    pub fn new_checked(index: usize, depth: usize) -> Result<Self, CoordinateError> {
+        let layer_size = T::try_layer_size(depth)?;
+        if index >= layer_size.get() {
+            return Err(CoordinateError::InvalidIndex {
+                index,
+                size: layer_size.get(),
+            });
+        }
+        Ok(Self::new(index, depth))
+    }
+
     /// Returns `true` if an `depth` is less than [MAX_DEPTH_INDEX](TreeParameters::MAX_DEPTH_INDEX)
     /// of an associated [`Tree`](crate::Tree)
     /// and `index` is less than .
     pub fn is_valid_index_depth(index: usize, depth: usize) -> bool {
-        depth <= T::MAX_DEPTH_INDEX && index < T::layers_sizes()[depth]
+        depth <= T::MAX_DEPTH_INDEX && index < T::layer_size(depth).get()
     }
 
     /// Returns `true` if call to [is_valid_index_depth](LayerIndex::is_valid_index_depth)
@@ -125,6 +133,18 @@ where
     pub fn get_raw(self) -> (usize, usize) {
         (self.index, self.depth)
     }
+
+    /// Converts into another coordinate type if this index is valid, otherwise returns
+    /// [`CoordinateError`] describing the problem.
+    ///
+    /// [`From`] conversions expect valid data and check it only in debug mode,
+    /// use this for data which may be invalid, e.g. created by `new` in release mode.
+    pub fn checked_into<U>(self) -> Result<U, CoordinateError>
+    where
+        U: From<Self>,
+    {
+        Ok(Self::new_checked(self.index, self.depth)?.into())
+    }
 }
 
 /// Position of [`Node`](crate::Node) in specific layer.
@@ -149,13 +169,7 @@ pub struct LayerPosition<T> {
 /// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
 impl<T> Clone for LayerPosition<T> {
     fn clone(&self) -> Self {
-        Self {
-            x: self.x,
-            y: self.y,
-            z: self.z,
-            depth: self.depth,
-            boo: PhantomData,
-        }
+        *self
     }
 }
 
@@ -193,12 +207,7 @@ where
 {
     fn from(value: NodeIndex<T>) -> Self {
         let depth = value.depth();
-        let rows_sizes = T::rows_sizes();
-        let remainder: usize = rows_sizes[0..depth]
-            .iter()
-            .map(|row_size| row_size * row_size * row_size)
-            .sum();
-        let layer_index = LayerIndex::new(value.raw() - remainder, depth);
+        let layer_index = LayerIndex::new(value.raw() - T::layer_start(depth), depth);
         layer_index.into()
     }
 }
@@ -208,8 +217,7 @@ where
     T: TreeInterface,
 {
     fn from(value: NodePosition<T>) -> Self {
-        let row_size = T::row_size(value.depth);
-        let divisor = T::BIGGEST_ROW_SIZE / row_size;
+        let divisor = T::BIGGEST_ROW_SIZE / T::row_size(value.depth).get();
         let x = value.x / divisor;
         let y = value.y / divisor;
         let z = value.z / divisor;
@@ -225,10 +233,10 @@ where
     fn from(value: LayerIndex<T>) -> Self {
         let row_size = T::row_size(value.depth);
 
-        let z = value.index / (row_size * row_size);
-        let index = value.index - (z * row_size * row_size);
-        let y = index / row_size;
-        let x = index % row_size;
+        let z = value.index / row_size.plane();
+        let index = value.index - (z * row_size.plane());
+        let y = index / row_size.get();
+        let x = index % row_size.get();
 
         LayerPosition::new(x, y, z, value.depth)
     }
@@ -252,12 +260,46 @@ where
         }
     }
 
+    /// Creates a new [LayerPosition] without checking it is valid,
+    /// used only for bounds of empty [`PositionRange`](crate::PositionRange).
+    pub(crate) fn new_unchecked(x: usize, y: usize, z: usize, depth: usize) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            depth,
+            boo: PhantomData,
+        }
+    }
+
+    /// Creates a new [LayerPosition] if provided `position` and `depth` are valid,
+    /// otherwise [`CoordinateError`] describing the violated limit is returned.
+    pub fn new_checked(
+        x: usize,
+        y: usize,
+        z: usize,
+        depth: usize,
+    ) -> Result<Self, CoordinateError> {
+        let row_size = T::try_row_size(depth)?;
+        if !Self::is_valid_position(x, y, z, depth) {
+            return Err(CoordinateError::InvalidPosition {
+                position: [x, y, z],
+                depth,
+                row_size: row_size.get(),
+            });
+        }
+        Ok(Self::new(x, y, z, depth))
+    }
+
     /// Returns `true` if `x`, `y` and `z` are less than row size of specific layer
     /// and `depth` is less or equal to [MAX_DEPTH_INDEX](TreeParameters::MAX_DEPTH_INDEX).
     pub fn is_valid_position(x: usize, y: usize, z: usize, depth: usize) -> bool {
-        let row_size = T::row_size(depth);
+        if depth > T::MAX_DEPTH_INDEX {
+            return false;
+        }
+        let row_size = T::row_size(depth).get();
 
-        x < row_size && y < row_size && z < row_size && depth <= T::MAX_DEPTH_INDEX
+        x < row_size && y < row_size && z < row_size
     }
 
     /// Returns `true` if call to [LayerPosition::is_valid_position] on inner values
@@ -266,6 +308,18 @@ where
         Self::is_valid_position(self.x, self.y, self.z, self.depth)
     }
 
+    /// Converts into another coordinate type if this position is valid, otherwise returns
+    /// [`CoordinateError`] describing the problem.
+    ///
+    /// [`From`] conversions expect valid data and check it only in debug mode,
+    /// use this for data which may be invalid, e.g. one with fields changed directly.
+    pub fn checked_into<U>(self) -> Result<U, CoordinateError>
+    where
+        U: From<Self>,
+    {
+        Ok(Self::new_checked(self.x, self.y, self.z, self.depth)?.into())
+    }
+
     /// Returns a tuple of `x`, `y`, `z` and `depth` in this order.
     pub fn get_raw(self) -> (usize, usize, usize, usize) {
         (self.x, self.y, self.z, self.depth)
@@ -278,24 +332,138 @@ where
             return None;
         }
 
+        // Each layer has half of the row size of the layer above it.
         self.depth += 1;
-        // TODO: Remove this special case and replace with general solution.
-        if self.depth == T::MAX_DEPTH_INDEX {
-            return Some(Self::new(0, 0, 0, self.depth));
+        self.x /= 2;
+        self.y /= 2;
+        self.z /= 2;
+
+        Some(self)
+    }
+
+    /// Returns octant of this position inside of its parrent, i.e. `x | y << 1 | z << 2` of it
+    /// inside of the parrent, or [`None`] for the root.
+    ///
+    /// Octants are ordered in the same way as [`Tree::children`](crate::Tree::children) returns them.
+    pub fn octant(self) -> Option<usize> {
+        if self.depth >= T::MAX_DEPTH_INDEX {
+            return None;
         }
-        let row_size = T::row_size(self.depth);
+        Some((self.x & 1) | (self.y & 1) << 1 | (self.z & 1) << 2)
+    }
 
-        self.x /= row_size;
-        self.y /= row_size;
-        self.z /= row_size;
+    /// Returns position of child in `octant` of the same parrent, or [`None`] for the root.
+    ///
+    /// Expects `octant` to be less than 8.
+    pub fn sibling(self, octant: usize) -> Option<Self> {
+        debug_assert!(octant < 8);
+        self.octant()?;
+        Some(Self::new(
+            (self.x & !1) | (octant & 1),
+            (self.y & !1) | (octant >> 1 & 1),
+            (self.z & !1) | (octant >> 2),
+            self.depth,
+        ))
+    }
 
-        Some(self)
+    /// Returns position of the sibling next to this one in `direction`,
+    /// or [`None`] if the neighbour in `direction` has a different parrent.
+    pub fn sibling_toward(self, direction: Direction) -> Option<Self> {
+        let octant = self.octant()?;
+        let bit = match direction.axis() {
+            Axis::X => 1,
+            Axis::Y => 2,
+            Axis::Z => 4,
+        };
+        // Moving towards the higher coordinate stays inside of the parrent only from the lower half.
+        let towards_higher = direction.offset().iter().sum::<isize>() > 0;
+        if (octant & bit == 0) != towards_higher {
+            return None;
+        }
+        self.sibling(octant ^ bit)
+    }
+
+    /// Returns this position moved by `offset` on `x`, `y` and `z` axes,
+    /// or [`None`] if moved position is outside of the layer.
+    pub fn checked_offset(self, offset: [isize; 3]) -> Option<Self> {
+        let row_size = T::row_size(self.depth).get();
+        let [x, y, z] = [
+            (self.x, offset[0]),
+            (self.y, offset[1]),
+            (self.z, offset[2]),
+        ]
+        .map(|(coordinate, offset)| {
+            coordinate
+                .checked_add_signed(offset)
+                .filter(|coordinate| *coordinate < row_size)
+        });
+        Some(Self::new(x?, y?, z?, self.depth))
+    }
+
+    /// Returns position of the neighbour in `direction`, i.e. this position moved by one node,
+    /// or [`None`] if there is no neighbour inside of the layer.
+    pub fn neighbor(self, direction: Direction) -> Option<Self> {
+        self.checked_offset(direction.offset())
+    }
+
+    /// Returns this position moved by `offset` on `x`, `y` and `z`, wrapping around the layer
+    /// on each axis instead of leaving it.
+    pub fn wrapping_offset<O>(self, offset: O) -> Self
+    where
+        O: Into<Offset>,
+    {
+        let offset = offset.into();
+        let row_size = T::row_size(self.depth).get() as isize;
+        let [x, y, z] = [
+            (self.x, offset.dx),
+            (self.y, offset.dy),
+            (self.z, offset.dz),
+        ]
+        .map(|(coordinate, offset)| {
+            (coordinate as isize + offset % row_size).rem_euclid(row_size) as usize
+        });
+        Self::new(x, y, z, self.depth)
+    }
+
+    /// Returns position of the neighbour in `direction`, wrapping around the layer
+    /// so a neighbour always exists.
+    pub fn wrapping_neighbor(self, direction: Direction) -> Self {
+        self.wrapping_offset(direction.offset())
+    }
+
+    /// Returns [Manhattan distance](NodePosition::manhattan_distance) to `other`, measured in
+    /// leaves, so positions in different depths can be compared.
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        NodePosition::from(self).manhattan_distance(other.into())
+    }
+
+    /// Returns [Chebyshev distance](NodePosition::chebyshev_distance) to `other`, measured in
+    /// leaves, so positions in different depths can be compared.
+    pub fn chebyshev_distance(self, other: Self) -> usize {
+        NodePosition::from(self).chebyshev_distance(other.into())
+    }
+
+    /// Returns [squared Euclidean distance](NodePosition::squared_euclidean_distance) to `other`,
+    /// measured in leaves, so positions in different depths can be compared.
+    pub fn squared_euclidean_distance(self, other: Self) -> usize {
+        NodePosition::from(self).squared_euclidean_distance(other.into())
+    }
+
+    /// Returns an iterator over all positions in layer in `depth`, in the order in which nodes
+    /// are stored, i.e. `x` changes the fastest.
+    ///
+    /// Expects in-bounds `depth`.
+    pub fn iter_layer(depth: usize) -> impl Iterator<Item = Self> {
+        let row_size = T::row_size(depth).get();
+        (0..row_size).flat_map(move |z| {
+            (0..row_size).flat_map(move |y| (0..row_size).map(move |x| Self::new(x, y, z, depth)))
+        })
     }
 }
 
 #[cfg(test)]
 mod layer_index_tests {
-    use crate::{LayerIndex, LayerPosition, NodeIndex, NodePosition, Tree};
+    use crate::{CoordinateError, LayerIndex, LayerPosition, NodeIndex, NodePosition, Tree};
 
     type TestTree = Tree<usize, 73>;
     type TestNodeIndex = NodeIndex<TestTree>;
@@ -318,6 +486,22 @@ mod layer_index_tests {
         std::panic::catch_unwind(|| TestLayerIndex::new(1, 2)).unwrap_err();
     }
 
+    #[test]
+    fn new_checked() {
+        assert_eq!(
+            TestLayerIndex::new_checked(7, 1),
+            Ok(TestLayerIndex::new(7, 1))
+        );
+        assert_eq!(
+            TestLayerIndex::new_checked(8, 1),
+            Err(CoordinateError::InvalidIndex { index: 8, size: 8 })
+        );
+        assert_eq!(
+            TestLayerIndex::new_checked(0, 3),
+            Err(CoordinateError::InvalidDepth { depth: 3, max: 2 })
+        );
+    }
+
     #[test]
     fn from_node_index() {
         let index = TestNodeIndex::new(0);
@@ -393,13 +577,195 @@ mod layer_index_tests {
 
 #[cfg(test)]
 mod layer_position_tests {
-    use crate::{LayerPosition, NodeIndex, NodePosition, Tree};
+    use crate::{
+        CoordinateError, Direction, LayerIndex, LayerPosition, NodeIndex, NodePosition, Offset,
+        Tree,
+    };
 
     type TestTree = Tree<usize, 73>;
     type TestNodeIndex = NodeIndex<TestTree>;
     type TestNodePosition = NodePosition<TestTree>;
     type TestLayerPosition = LayerPosition<TestTree>;
 
+    #[test]
+    fn iter_layer() {
+        let positions = TestLayerPosition::iter_layer(0).collect::<Vec<_>>();
+        assert_eq!(positions.len(), 64);
+        for (index, position) in positions.into_iter().enumerate() {
+            assert_eq!(position, TestLayerPosition::from(TestNodeIndex::new(index)));
+        }
+        assert_eq!(
+            TestLayerPosition::iter_layer(2).collect::<Vec<_>>(),
+            vec![TestLayerPosition::new(0, 0, 0, 2)]
+        );
+    }
+
+    #[test]
+    fn new_checked() {
+        assert_eq!(
+            TestLayerPosition::new_checked(1, 1, 1, 1),
+            Ok(TestLayerPosition::new(1, 1, 1, 1))
+        );
+        assert_eq!(
+            TestLayerPosition::new_checked(2, 0, 1, 1),
+            Err(CoordinateError::InvalidPosition {
+                position: [2, 0, 1],
+                depth: 1,
+                row_size: 2
+            })
+        );
+        assert_eq!(
+            TestLayerPosition::new_checked(0, 0, 0, 5),
+            Err(CoordinateError::InvalidDepth { depth: 5, max: 2 })
+        );
+    }
+
+    #[test]
+    fn octant() {
+        assert_eq!(TestLayerPosition::new(0, 0, 0, 0).octant(), Some(0));
+        assert_eq!(TestLayerPosition::new(3, 0, 1, 0).octant(), Some(5));
+        assert_eq!(TestLayerPosition::new(1, 1, 0, 1).octant(), Some(3));
+        assert_eq!(TestLayerPosition::new(0, 0, 0, 2).octant(), None);
+
+        assert_eq!(TestNodeIndex::new(7).octant(), Some(3));
+        assert_eq!(TestNodeIndex::new(71).octant(), Some(7));
+        assert_eq!(TestNodeIndex::new(72).octant(), None);
+    }
+
+    #[test]
+    fn sibling() {
+        let position = TestLayerPosition::new(3, 0, 1, 0);
+        assert_eq!(
+            position.sibling(0),
+            Some(TestLayerPosition::new(2, 0, 0, 0))
+        );
+        assert_eq!(position.sibling(5), Some(position));
+        assert_eq!(
+            position.sibling(6),
+            Some(TestLayerPosition::new(2, 1, 1, 0))
+        );
+        assert_eq!(TestLayerPosition::new(0, 0, 0, 2).sibling(1), None);
+
+        assert_eq!(
+            position.sibling_toward(Direction::Left),
+            Some(TestLayerPosition::new(2, 0, 1, 0))
+        );
+        assert_eq!(position.sibling_toward(Direction::Right), None);
+        assert_eq!(
+            position.sibling_toward(Direction::Top),
+            Some(TestLayerPosition::new(3, 1, 1, 0))
+        );
+        assert_eq!(position.sibling_toward(Direction::Back), None);
+        assert_eq!(
+            position.sibling_toward(Direction::Front),
+            Some(TestLayerPosition::new(3, 0, 0, 0))
+        );
+
+        assert_eq!(
+            TestNodeIndex::new(6).sibling(1),
+            Some(TestNodeIndex::new(3))
+        );
+        assert_eq!(
+            TestNodeIndex::new(64).sibling(7),
+            Some(TestNodeIndex::new(71))
+        );
+        assert_eq!(TestNodeIndex::new(72).sibling(0), None);
+    }
+
+    #[test]
+    fn checked_offset() {
+        let position = TestLayerPosition::new(1, 0, 3, 0);
+        assert_eq!(
+            position.checked_offset([2, 1, -3]),
+            Some(TestLayerPosition::new(3, 1, 0, 0))
+        );
+        assert_eq!(position.checked_offset([3, 0, 0]), None);
+        assert_eq!(position.checked_offset([0, -1, 0]), None);
+        assert_eq!(
+            TestLayerPosition::new(0, 0, 0, 2).checked_offset([0, 0, 1]),
+            None
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = TestLayerPosition::new(1, 2, 3, 0);
+        let b = TestLayerPosition::new(1, 0, 1, 1);
+        assert_eq!(a.manhattan_distance(b), 4);
+        assert_eq!(a.chebyshev_distance(b), 2);
+        assert_eq!(a.squared_euclidean_distance(b), 6);
+        assert_eq!(TestLayerPosition::new(0, 0, 0, 2).chebyshev_distance(a), 3);
+    }
+
+    #[test]
+    fn checked_into() {
+        let mut position = TestLayerPosition::new(1, 0, 3, 0);
+        assert_eq!(position.checked_into(), Ok(TestNodeIndex::new(49)));
+        position.x = 4;
+        assert_eq!(
+            position.checked_into::<TestNodeIndex>(),
+            Err(CoordinateError::InvalidPosition {
+                position: [4, 0, 3],
+                depth: 0,
+                row_size: 4
+            })
+        );
+        assert_eq!(
+            LayerIndex::<TestTree>::new(7, 1).checked_into(),
+            Ok(TestLayerPosition::new(1, 1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn wrapping_offset() {
+        let position = TestLayerPosition::new(1, 0, 3, 0);
+        assert_eq!(
+            position.wrapping_offset([2, 1, -3]),
+            TestLayerPosition::new(3, 1, 0, 0)
+        );
+        assert_eq!(
+            position.wrapping_offset([3, -1, 1]),
+            TestLayerPosition::new(0, 3, 0, 0)
+        );
+        assert_eq!(
+            position.wrapping_offset([-9, 8, isize::MIN]),
+            TestLayerPosition::new(0, 0, 3, 0)
+        );
+        assert_eq!(
+            position.wrapping_offset(Offset::new(-2, 0, 1)),
+            TestLayerPosition::new(3, 0, 0, 0)
+        );
+        assert_eq!(
+            position.wrapping_neighbor(Direction::Bottom),
+            TestLayerPosition::new(1, 3, 3, 0)
+        );
+        assert_eq!(
+            position.wrapping_neighbor(Direction::Back),
+            TestLayerPosition::new(1, 0, 0, 0)
+        );
+        let root = TestLayerPosition::new(0, 0, 0, 2);
+        assert_eq!(root.wrapping_neighbor(Direction::Left), root);
+    }
+
+    #[test]
+    fn neighbor() {
+        let position = TestLayerPosition::new(1, 0, 3, 0);
+        assert_eq!(
+            position.neighbor(Direction::Left),
+            Some(TestLayerPosition::new(0, 0, 3, 0))
+        );
+        assert_eq!(
+            position.neighbor(Direction::Top),
+            Some(TestLayerPosition::new(1, 1, 3, 0))
+        );
+        assert_eq!(position.neighbor(Direction::Bottom), None);
+        assert_eq!(position.neighbor(Direction::Back), None);
+        assert_eq!(
+            TestLayerPosition::new(0, 0, 0, 2).neighbor(Direction::Right),
+            None
+        );
+    }
+
     #[test]
     fn new() {
         TestLayerPosition::new(0, 0, 0, 0);
@@ -463,6 +829,9 @@ mod layer_position_tests {
 
         let pos = TestLayerPosition::new(0, 0, 0, 2);
         assert_eq!(pos.parrent_position(), None);
+
+        let pos = LayerPosition::<Tree<usize, 585>>::new(7, 5, 2, 0);
+        assert_eq!(pos.parrent_position(), Some(LayerPosition::new(3, 2, 1, 1)));
     }
 
     #[test]
@@ -0,0 +1,185 @@
+use std::fmt::Debug;
+
+use crate::{Children, Node, NodeIndex, Tree, TreeInterface};
+
+/// Octree with the same layout as [`Tree`], which stores `T` in the shallowest layer
+/// and a summary `S` in all deeper layers.
+///
+/// Positions are expressed in coordinates of [`Tree<T, SIZE>`](Tree), leaves are in depth 0.
+#[derive(Debug, Clone)]
+pub struct LayeredTree<T, S, const SIZE: usize> {
+    /// Nodes of the shallowest layer.
+    leaves: Box<[Node<T>]>,
+    /// Nodes of all deeper layers, in the order in which they are stored in [`Tree`].
+    summaries: Box<[Node<S>]>,
+}
+
+impl<T, S, const SIZE: usize> Default for LayeredTree<T, S, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+{
+    fn default() -> Self {
+        let leaves = Tree::<T, SIZE>::LEAF_LAYER_LEN;
+        Self {
+            leaves: (0..leaves).map(|_| Node::Empty).collect(),
+            summaries: (leaves..SIZE).map(|_| Node::Empty).collect(),
+        }
+    }
+}
+
+impl<T, S, const SIZE: usize> LayeredTree<T, S, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+    S: Debug,
+{
+    /// Creates a new [`LayeredTree`] with all [`nodes`](Node) set to [`Empty`](Node::Empty).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a reference to a leaf on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn get_leaf<P>(&self, position: P) -> &Node<T>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        &self.leaves[Self::leaf_index(position.into())]
+    }
+
+    /// Sets the leaf on `position` to provided [`node`](Node)
+    /// and returns a [`Node`] previously stored on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn set_leaf<P>(&mut self, position: P, node: Node<T>) -> Node<T>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let index = Self::leaf_index(position.into());
+        std::mem::replace(&mut self.leaves[index], node)
+    }
+
+    /// Returns a reference to a summary on `position`.
+    ///
+    /// Expects `position` to be deeper than depth 0.
+    pub fn get_summary<P>(&self, position: P) -> &Node<S>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        &self.summaries[Self::summary_index(position.into())]
+    }
+
+    /// Sets the summary on `position` to provided [`node`](Node)
+    /// and returns a [`Node`] previously stored on `position`.
+    ///
+    /// Expects `position` to be deeper than depth 0.
+    pub fn set_summary<P>(&mut self, position: P, node: Node<S>) -> Node<S>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let index = Self::summary_index(position.into());
+        std::mem::replace(&mut self.summaries[index], node)
+    }
+
+    /// Builds summaries from bottom up, parrents of leaves are combined by `leaf_rule`
+    /// and all deeper summaries by `summary_rule`.
+    ///
+    /// Children are passed in the same order as [`Tree::children`] returns them.
+    pub fn build<F, G>(&mut self, mut leaf_rule: F, mut summary_rule: G)
+    where
+        F: FnMut(&[&Node<T>; 8]) -> Node<S>,
+        G: FnMut(&[&Node<S>; 8]) -> Node<S>,
+    {
+        for index in Tree::<T, SIZE>::LEAF_LAYER_LEN..SIZE {
+            let index = NodeIndex::<Tree<T, SIZE>>::new(index);
+            let children = Self::children(index);
+            let node = if index.depth() == 1 {
+                leaf_rule(&children.map(|child| &self.leaves[Self::leaf_index(child)]))
+            } else {
+                summary_rule(&children.map(|child| &self.summaries[Self::summary_index(child)]))
+            };
+            self.summaries[Self::summary_index(index)] = node;
+        }
+    }
+
+    /// Returns indexes of children of node on `index`, which is expected to have them.
+    fn children(index: NodeIndex<Tree<T, SIZE>>) -> [NodeIndex<Tree<T, SIZE>>; 8] {
+        let mut children = Children::new(index).unwrap();
+        // `Children` always yields exactly 8 indexes, so this never panics.
+        std::array::from_fn(|_| children.next().unwrap())
+    }
+
+    /// Returns index of leaf on `index` in `leaves`.
+    fn leaf_index(index: NodeIndex<Tree<T, SIZE>>) -> usize {
+        debug_assert!(index.raw() < Tree::<T, SIZE>::LEAF_LAYER_LEN);
+        index.raw()
+    }
+
+    /// Returns index of summary on `index` in `summaries`.
+    fn summary_index(index: NodeIndex<Tree<T, SIZE>>) -> usize {
+        debug_assert!(index.raw() >= Tree::<T, SIZE>::LEAF_LAYER_LEN);
+        index.raw() - Tree::<T, SIZE>::LEAF_LAYER_LEN
+    }
+}
+
+#[cfg(test)]
+mod layered_tree_tests {
+    use crate::{LayerPosition, Node, NodeIndex};
+
+    use super::LayeredTree;
+
+    // Summaries hold amount of filled leaves below them.
+    type TestTree = LayeredTree<char, usize, 73>;
+
+    fn count_leaves(children: &[&Node<char>; 8]) -> Node<usize> {
+        let count = children
+            .iter()
+            .filter(|child| matches!(child, Node::Filled(_)))
+            .count();
+        if count == 0 {
+            Node::Empty
+        } else {
+            Node::Filled(count)
+        }
+    }
+
+    fn sum_summaries(children: &[&Node<usize>; 8]) -> Node<usize> {
+        let count = children
+            .iter()
+            .map(|child| match child {
+                Node::Filled(count) => *count,
+                _ => 0,
+            })
+            .sum();
+        if count == 0 {
+            Node::Empty
+        } else {
+            Node::Filled(count)
+        }
+    }
+
+    #[test]
+    fn build() {
+        let mut tree = TestTree::new();
+        tree.set_leaf(LayerPosition::new(0, 0, 0, 0), Node::Filled('a'));
+        tree.set_leaf(LayerPosition::new(1, 1, 1, 0), Node::Filled('b'));
+        assert_eq!(
+            tree.set_leaf(LayerPosition::new(3, 3, 3, 0), Node::Filled('c')),
+            Node::Empty
+        );
+        tree.build(count_leaves, sum_summaries);
+
+        assert_eq!(tree.get_leaf(NodeIndex::new(0)), &Node::Filled('a'));
+        assert_eq!(tree.get_summary(NodeIndex::new(64)), &Node::Filled(2));
+        assert_eq!(tree.get_summary(NodeIndex::new(65)), &Node::Empty);
+        assert_eq!(tree.get_summary(NodeIndex::new(71)), &Node::Filled(1));
+        assert_eq!(tree.get_summary(NodeIndex::new(72)), &Node::Filled(3));
+
+        tree.set_summary(NodeIndex::new(72), Node::Reduced);
+        assert_eq!(
+            tree.get_summary(LayerPosition::new(0, 0, 0, 2)),
+            &Node::Reduced
+        );
+    }
+}
@@ -1,13 +1,138 @@
 #![forbid(missing_docs)]
 
 //! `packed_tree` provides [Tree] struct and different coordinate systems used to index into it.
+//!
+//! Core types which are covered by semver compatibility are re-exported from [`stable`] module.
+//! Subsystems behind `unstable-*` features may change between minor versions.
+//!
+//! # Determinism
+//!
+//! All operations give bit-identical results for the same input on every platform, so trees
+//! can be used in lockstep simulations. Iterators yield items in a documented order, which is
+//! the order in which nodes are stored unless stated otherwise, and build passes call combine
+//! rules bottom up in that order. Parallel variants behind `rayon` feature keep the order of
+//! items and produce the same results as sequential ones regardless of amount of threads,
+//! only closures passed to parallel iterators are run in an unspecified order.
+//!
+//! `deterministic` feature forces stable reduction orders in parallel paths. All current parallel
+//! variants already keep the order of items, so it has no effect yet, but parallel reductions
+//! added later have to honour it instead of combining partial results in the order in which
+//! threads finish.
+//!
+//! Hashes are the exception. [`Tree::layer_hash`] and false positives of
+//! [`Tree::may_contain_value`] depend on [`DefaultHasher`](std::collections::hash_map::DefaultHasher),
+//! whose output may change between Rust releases, and on [`Hash`](std::hash::Hash) of node indexes
+//! and values, which differs between 32 and 64 bit targets for [`usize`]. They are only stable
+//! within a single build, so they should not be compared across machines. Results which are
+//! exact, like [`PartialEq`] of trees or `false` from [`Tree::may_contain_value`], are not affected.
 
 mod absolute_position;
+mod access;
+#[cfg(any(test, feature = "bench-fixtures"))]
+pub mod bench_fixtures;
+mod blend;
+mod blit;
+#[cfg(feature = "unstable-io")]
+mod block_io;
+mod bricked;
+mod coarse;
+mod combine;
+mod consistency;
+mod depth;
+mod diff_report;
+mod direction;
+mod downsample;
+mod edit_scope;
+mod entry;
+mod error;
+mod face_coverage;
+mod incremental;
+#[cfg(feature = "unstable-io")]
+mod io;
+mod iter;
+mod join;
+mod layer_hash;
+mod layer_iter;
 mod layer_position;
+mod layered;
+mod micro_grid;
+#[cfg(feature = "mint")]
+mod mint_impls;
 mod node;
+mod octant_path;
+mod offset;
+#[cfg(feature = "rayon")]
+mod par_iter;
+mod persistent;
+mod position_range;
+mod prefetch;
+mod query_cache;
+mod recenter;
+mod remap;
+mod render;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod size;
+mod snapshot_store;
+mod solid;
+mod solid_boxes;
+mod streaming;
+mod swizzle;
+mod tombstone;
 mod tree;
+mod value_filter;
+mod visit;
+mod voxel_space;
+mod window;
 
-pub use absolute_position::{NodeIndex, NodePosition};
+pub use absolute_position::{NodeIndex, NodePosition, RawNodeIndex, TryIntoIndex};
+pub use access::{descend_filled, iter_empty_octants, iter_region, surface, OctreeAccess};
+pub use blend::Lerp;
+pub use bricked::BrickedLeaves;
+pub use coarse::CoarseTree;
+pub use combine::{CombineContext, Reducer};
+pub use depth::Depth;
+pub use diff_report::DiffReport;
+pub use direction::{Axis, Direction};
+pub use downsample::downsample_region;
+pub use edit_scope::EditScope;
+pub use entry::Entry;
+pub use error::{CoordinateError, ImportIssue, TreeError};
+pub use face_coverage::FaceCoverage;
+pub use incremental::{Budget, IncrementalBuild, Progress, TreeTask};
+pub use iter::{
+    Ancestors, Bfs, Children, DescendFilled, DrainFilled, EmptyOctants, EnumeratePositions,
+    FaceNeighbors, Filled, FilledMut, IntoIter, Iter, IterMut, LayerPairs, LeafMorton, Neighbors,
+    NodeIndexRange, OctantGroups, Region, Row, Shell, Siblings, Surface,
+};
+pub use layer_iter::LayerIter;
 pub use layer_position::{LayerIndex, LayerPosition};
+pub use layered::LayeredTree;
+pub use micro_grid::MicroGrid;
 pub use node::{Node, NodesRaw};
-pub use tree::{implemented_tree_sizes, Tree, TreeInterface};
+pub use octant_path::OctantPath;
+pub use offset::Offset;
+pub use persistent::PersistentTree;
+pub use position_range::{PositionRange, PositionRangeIter};
+pub use query_cache::QueryCache;
+pub use render::Rgba;
+pub use size::{LayerSize, RowSize};
+pub use snapshot_store::SnapshotStore;
+pub use solid::SolidTree;
+pub use swizzle::AxisMapping;
+pub use tree::{implemented_tree_sizes, Tree, TreeInterface, OCTANT_OFFSETS};
+pub use value_filter::may_contain_value;
+pub use visit::{visit, VisitControl};
+pub use voxel_space::{LeafOrigin, VoxelSpace};
+pub use window::{BoundsPolicy, Window3};
+
+/// Subset of the API which is covered by semver compatibility.
+///
+/// Items re-exported here do not change in a breaking way without a new major version,
+/// everything else in the crate may still change between minor versions.
+pub mod stable {
+    pub use crate::{
+        implemented_tree_sizes, Axis, CoordinateError, LayerIndex, LayerPosition, LayerSize, Node,
+        NodeIndex, NodePosition, NodesRaw, RowSize, Tree, TreeError, TreeInterface,
+    };
+}
@@ -0,0 +1,175 @@
+use std::fmt::Debug;
+
+use crate::{AxisMapping, Node, NodeIndex, Tree, TreeInterface};
+
+/// Grid of 4x4x4 micro voxels refining a single leaf of [`Tree`], every micro voxel is one bit.
+///
+/// Bits are ordered the same way as nodes of a layer, i.e. `x` changes the fastest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MicroGrid(u64);
+
+impl MicroGrid {
+    /// Amount of micro voxels in one row of the grid.
+    pub const ROW_SIZE: usize = 4;
+    /// Grid without any filled micro voxel.
+    pub const EMPTY: Self = Self(0);
+    /// Grid with all micro voxels filled.
+    pub const FULL: Self = Self(u64::MAX);
+
+    /// Creates a new [`MicroGrid`] from raw `bits`.
+    pub const fn new(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns raw bits of the grid.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if micro voxel on `(x, y, z)` is filled.
+    ///
+    /// Expects all coordinates to be less than [`ROW_SIZE`](MicroGrid::ROW_SIZE).
+    pub fn get(self, x: usize, y: usize, z: usize) -> bool {
+        self.0 & Self::mask(x, y, z) != 0
+    }
+
+    /// Sets micro voxel on `(x, y, z)` to be `filled`.
+    ///
+    /// Expects all coordinates to be less than [`ROW_SIZE`](MicroGrid::ROW_SIZE).
+    pub fn set(&mut self, x: usize, y: usize, z: usize, filled: bool) {
+        if filled {
+            self.0 |= Self::mask(x, y, z);
+        } else {
+            self.0 &= !Self::mask(x, y, z);
+        }
+    }
+
+    /// Returns amount of filled micro voxels.
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns grid with micro voxels remapped by `mapping`, flipped coordinates are mirrored
+    /// around the center of the grid.
+    pub fn swizzle(self, mapping: AxisMapping) -> Self {
+        let mut grid = Self::EMPTY;
+        for z in 0..Self::ROW_SIZE {
+            for y in 0..Self::ROW_SIZE {
+                for x in 0..Self::ROW_SIZE {
+                    if self.get(x, y, z) {
+                        let [x, y, z] = mapping.map([x, y, z], 1, Self::ROW_SIZE);
+                        grid.set(x, y, z, true);
+                    }
+                }
+            }
+        }
+        grid
+    }
+
+    fn mask(x: usize, y: usize, z: usize) -> u64 {
+        debug_assert!(x < Self::ROW_SIZE && y < Self::ROW_SIZE && z < Self::ROW_SIZE);
+        1 << (x + y * Self::ROW_SIZE + z * Self::ROW_SIZE * Self::ROW_SIZE)
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Attaches `grid` to the leaf on `position` and returns [`MicroGrid`] previously
+    /// attached to it.
+    ///
+    /// Refinement only describes shape of a [`Filled`](Node::Filled) leaf, it is kept when the
+    /// leaf is changed and is removed only by [`unrefine_leaf`](Tree::unrefine_leaf).
+    /// Expects `position` to be in the shallowest layer.
+    pub fn refine_leaf<P>(&mut self, position: P, grid: MicroGrid) -> Option<MicroGrid>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        let index = position.into();
+        debug_assert_eq!(index.depth(), 0);
+        self.refinements.insert(index.raw(), grid)
+    }
+
+    /// Removes [`MicroGrid`] attached to the leaf on `position` and returns it.
+    pub fn unrefine_leaf<P>(&mut self, position: P) -> Option<MicroGrid>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        self.refinements.remove(&position.into().raw())
+    }
+
+    /// Returns [`MicroGrid`] attached to the leaf on `position`, if there is any.
+    pub fn refinement<P>(&self, position: P) -> Option<MicroGrid>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        self.refinements.get(&position.into().raw()).copied()
+    }
+
+    /// Returns `true` if micro voxel on `(x, y, z)` inside of the leaf on `position` is filled.
+    ///
+    /// Leaves which are not [`Filled`](Node::Filled) are empty and [`Filled`](Node::Filled)
+    /// leaves without [refinement](Tree::refine_leaf) are full.
+    pub fn is_micro_filled<P>(&self, position: P, x: usize, y: usize, z: usize) -> bool
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        let index = position.into();
+        match self.get(index) {
+            Node::Filled(_) => self.refinement(index).is_none_or(|grid| grid.get(x, y, z)),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod micro_grid_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    use super::MicroGrid;
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn micro_grid() {
+        let mut grid = MicroGrid::EMPTY;
+        grid.set(1, 2, 3, true);
+        assert!(grid.get(1, 2, 3));
+        assert!(!grid.get(3, 2, 1));
+        assert_eq!(grid.bits(), 1 << (1 + 2 * 4 + 3 * 16));
+        assert_eq!(grid.count(), 1);
+        grid.set(1, 2, 3, false);
+        assert_eq!(grid, MicroGrid::EMPTY);
+        assert_eq!(MicroGrid::FULL.count(), 64);
+    }
+
+    #[test]
+    fn refine_leaf() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(5), Node::Filled(1));
+        let mut slab = MicroGrid::EMPTY;
+        for x in 0..4 {
+            for z in 0..4 {
+                slab.set(x, 0, z, true);
+            }
+        }
+
+        assert!(tree.is_micro_filled(NodeIndex::new(5), 0, 3, 0));
+        assert_eq!(tree.refine_leaf(NodeIndex::new(5), slab), None);
+        assert_eq!(tree.refinement(NodeIndex::new(5)), Some(slab));
+        assert!(tree.is_micro_filled(NodeIndex::new(5), 0, 0, 0));
+        assert!(!tree.is_micro_filled(NodeIndex::new(5), 0, 3, 0));
+
+        let mut other = tree.clone();
+        assert_eq!(other, tree);
+        other.unrefine_leaf(NodeIndex::new(5));
+        assert_ne!(other, tree);
+
+        tree.set(NodeIndex::new(5), Node::Empty);
+        assert!(!tree.is_micro_filled(NodeIndex::new(5), 0, 0, 0));
+        assert_eq!(tree.unrefine_leaf(NodeIndex::new(5)), Some(slab));
+        assert_eq!(tree.refinement(NodeIndex::new(5)), None);
+    }
+}
@@ -0,0 +1,114 @@
+//! Conversions between position types and [`mint`] types.
+//!
+//! Positions convert to [`Point3<u32>`] without their depth, so conversions back
+//! create positions of leaves and are validated against the associated [`Tree`](crate::Tree).
+
+use std::num::TryFromIntError;
+
+use mint::{Point3, Vector3};
+
+use crate::{CoordinateError, LayerPosition, NodePosition, Offset, TreeInterface};
+
+impl<T> From<NodePosition<T>> for Point3<u32> {
+    fn from(value: NodePosition<T>) -> Self {
+        Point3 {
+            x: value.x as u32,
+            y: value.y as u32,
+            z: value.z as u32,
+        }
+    }
+}
+
+/// Creates position of a leaf.
+impl<T> TryFrom<Point3<u32>> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    type Error = CoordinateError;
+
+    fn try_from(value: Point3<u32>) -> Result<Self, Self::Error> {
+        Self::new_checked(value.x as usize, value.y as usize, value.z as usize, 0)
+    }
+}
+
+impl<T> From<LayerPosition<T>> for Point3<u32> {
+    fn from(value: LayerPosition<T>) -> Self {
+        Point3 {
+            x: value.x as u32,
+            y: value.y as u32,
+            z: value.z as u32,
+        }
+    }
+}
+
+/// Creates position of a leaf.
+impl<T> TryFrom<Point3<u32>> for LayerPosition<T>
+where
+    T: TreeInterface,
+{
+    type Error = CoordinateError;
+
+    fn try_from(value: Point3<u32>) -> Result<Self, Self::Error> {
+        Self::new_checked(value.x as usize, value.y as usize, value.z as usize, 0)
+    }
+}
+
+impl From<Vector3<i32>> for Offset {
+    fn from(value: Vector3<i32>) -> Self {
+        Self::new(value.x as isize, value.y as isize, value.z as isize)
+    }
+}
+
+/// Fails if any difference does not fit into [`i32`].
+impl TryFrom<Offset> for Vector3<i32> {
+    type Error = TryFromIntError;
+
+    fn try_from(value: Offset) -> Result<Self, Self::Error> {
+        Ok(Vector3 {
+            x: value.dx.try_into()?,
+            y: value.dy.try_into()?,
+            z: value.dz.try_into()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod mint_tests {
+    use mint::{Point3, Vector3};
+
+    use crate::{CoordinateError, LayerPosition, NodePosition, Offset, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn points() {
+        let point = Point3::from(NodePosition::<TestTree>::new(2, 0, 2, 1));
+        assert_eq!(point, Point3 { x: 2, y: 0, z: 2 });
+        assert_eq!(
+            NodePosition::<TestTree>::try_from(point),
+            Ok(NodePosition::new(2, 0, 2, 0))
+        );
+
+        let point = Point3::from(LayerPosition::<TestTree>::new(1, 2, 3, 0));
+        assert_eq!(
+            LayerPosition::<TestTree>::try_from(point),
+            Ok(LayerPosition::new(1, 2, 3, 0))
+        );
+        assert_eq!(
+            LayerPosition::<TestTree>::try_from(Point3 { x: 4, y: 0, z: 0 }),
+            Err(CoordinateError::InvalidPosition {
+                position: [4, 0, 0],
+                depth: 0,
+                row_size: 4
+            })
+        );
+    }
+
+    #[test]
+    fn vectors() {
+        let offset = Offset::from(Vector3 { x: -1, y: 2, z: 0 });
+        assert_eq!(offset, Offset::new(-1, 2, 0));
+        assert_eq!(Vector3::try_from(offset), Ok(Vector3 { x: -1, y: 2, z: 0 }));
+        assert!(Vector3::<i32>::try_from(Offset::new(isize::MAX, 0, 0)).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::marker::PhantomData;
 
-use crate::{NodeIndex, TreeInterface};
+use crate::{NodeIndex, TreeError, TreeInterface};
 
 /// Data inside a [`Tree`](crate::Tree).
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +13,21 @@ pub enum Node<T> {
     Empty,
 }
 
+impl<T> Node<T> {
+    /// Returns a clone of value inside of [`Filled`](Node::Filled) node,
+    /// for [`Reduced`](Node::Reduced) and [`Empty`](Node::Empty) nodes [`T::default`](Default::default)
+    /// is returned.
+    pub fn value_or_default(&self) -> T
+    where
+        T: Default + Clone,
+    {
+        match self {
+            Node::Filled(value) => value.clone(),
+            Node::Reduced | Node::Empty => T::default(),
+        }
+    }
+}
+
 /// Helper struct to ease building [`Tree`] from data.
 #[derive(Debug)]
 pub struct NodesRaw<T, U> {
@@ -61,6 +76,36 @@ where
         Self::default()
     }
 
+    /// Creates a new [NodesRaw] from `nodes` if their length is exactly
+    /// [tree size](TreeInterface::SIZE), otherwise [`TreeError::InvalidNodesLength`] is returned.
+    pub fn try_from_exact(nodes: Vec<Node<T>>) -> Result<Self, TreeError> {
+        if nodes.len() != U::SIZE {
+            return Err(TreeError::InvalidNodesLength {
+                expected: U::SIZE,
+                actual: nodes.len(),
+            });
+        }
+        Ok(Self {
+            nodes,
+            boo: PhantomData,
+        })
+    }
+
+    /// Creates a new [NodesRaw] from `nodes` if their length does not exceed
+    /// [tree size](TreeInterface::SIZE), otherwise [`TreeError::InvalidNodesLength`] is returned.
+    pub fn try_from_max(nodes: Vec<Node<T>>) -> Result<Self, TreeError> {
+        if nodes.len() > U::SIZE {
+            return Err(TreeError::InvalidNodesLength {
+                expected: U::SIZE,
+                actual: nodes.len(),
+            });
+        }
+        Ok(Self {
+            nodes,
+            boo: PhantomData,
+        })
+    }
+
     /// Appends a `node` to the back of a collection.
     pub fn push(&mut self, node: Node<T>) {
         debug_assert!(self.nodes.len() < U::SIZE);
@@ -95,3 +140,55 @@ where
         value
     }
 }
+
+#[cfg(test)]
+mod node_tests {
+    use crate::Node;
+
+    #[test]
+    fn value_or_default() {
+        assert_eq!(Node::Filled(0.5).value_or_default(), 0.5);
+        assert_eq!(Node::<f32>::Reduced.value_or_default(), 0.0);
+        assert_eq!(Node::<f32>::Empty.value_or_default(), 0.0);
+    }
+}
+
+#[cfg(test)]
+mod nodes_raw_tests {
+    use crate::{Node, NodesRaw, Tree, TreeError};
+
+    type TestTree = Tree<usize, 73>;
+    type TestNodesRaw = NodesRaw<usize, TestTree>;
+
+    #[test]
+    fn try_from_exact() {
+        assert!(TestNodesRaw::try_from_exact(vec![Node::Empty; 73]).is_ok());
+        assert_eq!(
+            TestNodesRaw::try_from_exact(vec![Node::Empty; 72]).unwrap_err(),
+            TreeError::InvalidNodesLength {
+                expected: 73,
+                actual: 72
+            }
+        );
+        assert_eq!(
+            TestNodesRaw::try_from_exact(vec![Node::Empty; 74]).unwrap_err(),
+            TreeError::InvalidNodesLength {
+                expected: 73,
+                actual: 74
+            }
+        );
+    }
+
+    #[test]
+    fn try_from_max() {
+        assert!(TestNodesRaw::try_from_max(Vec::new()).is_ok());
+        assert!(TestNodesRaw::try_from_max(vec![Node::Empty; 73]).is_ok());
+        assert_eq!(
+            TestNodesRaw::try_from_max(vec![Node::Empty; 74]).unwrap_err(),
+            TreeError::InvalidNodesLength {
+                expected: 73,
+                actual: 74
+            }
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use crate::{NodeIndex, NodePosition, TreeInterface};
+
+/// Addresses [`Node`](crate::Node) in [`Tree`](crate::Tree) by octants chosen on the way
+/// from the root, the empty path is the root itself.
+///
+/// Octants are ordered in the same way as [`Tree::children`](crate::Tree::children) returns them,
+/// so octant is `x | y << 1 | z << 2` of the child inside of its parrent.
+#[derive(Debug)]
+pub struct OctantPath<T> {
+    /// Octants packed by 3 bits, the first octant from the root is in the lowest bits.
+    octants: u64,
+    /// Amount of octants in the path.
+    len: usize,
+    /// Associated [`Tree`](crate::Tree).
+    boo: PhantomData<T>,
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Clone for OctantPath<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// [`Copy`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Copy for OctantPath<T> {}
+
+/// [`PartialEq`] is implemented manually, so there is no requirement on `T` to also implement [`PartialEq`].
+impl<T> PartialEq for OctantPath<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.octants == other.octants && self.len == other.len
+    }
+}
+
+/// [`Display`] shows the biggest row of associated [`Tree`](crate::Tree) and octants from the root.
+impl<T> Display for OctantPath<T>
+where
+    T: TreeInterface,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "OctantPath::<{}>(", T::BIGGEST_ROW_SIZE)?;
+        for (i, octant) in self.octants().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            write!(f, "{separator}{octant}")?;
+        }
+        write!(f, " )")
+    }
+}
+
+impl<T> OctantPath<T>
+where
+    T: TreeInterface,
+{
+    /// Returns a path to the root.
+    pub fn root() -> Self {
+        Self {
+            octants: 0,
+            len: 0,
+            boo: PhantomData,
+        }
+    }
+
+    /// Returns amount of octants in the path.
+    pub fn len(self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the path leads to the root.
+    pub fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns depth of the node the path leads to.
+    pub fn depth(self) -> usize {
+        T::MAX_DEPTH_INDEX - self.len
+    }
+
+    /// Returns octant on `level` of the path, the first octant from the root is on level 0,
+    /// or [`None`] if the path is not that long.
+    pub fn get(self, level: usize) -> Option<usize> {
+        (level < self.len).then(|| (self.octants >> (3 * level) & 7) as usize)
+    }
+
+    /// Returns an iterator over octants from the root.
+    pub fn octants(self) -> impl Iterator<Item = usize> {
+        (0..self.len).map(move |level| (self.octants >> (3 * level) & 7) as usize)
+    }
+
+    /// Extends the path to child in `octant`.
+    ///
+    /// Expects `octant` to be less than 8 and the path not to lead to a leaf already.
+    pub fn push(&mut self, octant: usize) {
+        debug_assert!(octant < 8);
+        debug_assert!(self.len < T::MAX_DEPTH_INDEX);
+        self.octants |= (octant as u64) << (3 * self.len);
+        self.len += 1;
+    }
+
+    /// Shortens the path to the parrent and returns the removed octant,
+    /// or [`None`] if the path leads to the root.
+    pub fn pop(&mut self) -> Option<usize> {
+        let octant = self.get(self.len.checked_sub(1)?)?;
+        self.len -= 1;
+        self.octants &= !(7 << (3 * self.len));
+        Some(octant)
+    }
+
+    /// Shortens the path to the ancestor in `depth`,
+    /// does nothing if the path already leads to `depth` or deeper.
+    pub fn truncate(&mut self, depth: usize) {
+        while self.depth() < depth {
+            self.pop();
+        }
+    }
+}
+
+impl<T> From<NodePosition<T>> for OctantPath<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: NodePosition<T>) -> Self {
+        let mut path = Self::root();
+        for bit in (value.depth..T::MAX_DEPTH_INDEX).rev() {
+            path.push((value.x >> bit & 1) | (value.y >> bit & 1) << 1 | (value.z >> bit & 1) << 2);
+        }
+        path
+    }
+}
+
+impl<T> From<NodeIndex<T>> for OctantPath<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: NodeIndex<T>) -> Self {
+        NodePosition::from(value).into()
+    }
+}
+
+impl<T> From<OctantPath<T>> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: OctantPath<T>) -> Self {
+        let (mut x, mut y, mut z) = (0, 0, 0);
+        for (level, octant) in value.octants().enumerate() {
+            let bit = T::MAX_DEPTH_INDEX - 1 - level;
+            x |= (octant & 1) << bit;
+            y |= (octant >> 1 & 1) << bit;
+            z |= (octant >> 2) << bit;
+        }
+        NodePosition::new(x, y, z, value.depth())
+    }
+}
+
+impl<T> From<OctantPath<T>> for NodeIndex<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: OctantPath<T>) -> Self {
+        NodePosition::from(value).into()
+    }
+}
+
+#[cfg(test)]
+mod octant_path_tests {
+    use crate::{NodeIndex, NodePosition, Tree};
+
+    use super::OctantPath;
+
+    type TestTree = Tree<usize, 73>;
+    type TestPath = OctantPath<TestTree>;
+
+    #[test]
+    fn push_pop() {
+        let mut path = TestPath::root();
+        assert!(path.is_empty());
+        assert_eq!(path.depth(), 2);
+        path.push(3);
+        path.push(5);
+        assert_eq!(path.len(), 2);
+        assert_eq!(path.depth(), 0);
+        assert_eq!(path.octants().collect::<Vec<_>>(), vec![3, 5]);
+        assert_eq!(path.get(1), Some(5));
+        assert_eq!(path.get(2), None);
+        assert_eq!(path.to_string(), "OctantPath::<4>( 3, 5 )");
+
+        let mut truncated = path;
+        truncated.truncate(1);
+        assert_eq!(truncated.octants().collect::<Vec<_>>(), vec![3]);
+        truncated.truncate(0);
+        assert_eq!(truncated.len(), 1);
+
+        assert_eq!(path.pop(), Some(5));
+        assert_eq!(path, truncated);
+        assert_eq!(path.pop(), Some(3));
+        assert_eq!(path.pop(), None);
+        assert_eq!(path, TestPath::root());
+    }
+
+    #[test]
+    fn conversions() {
+        let mut path = TestPath::root();
+        path.push(3);
+        path.push(5);
+        assert_eq!(NodePosition::from(path), NodePosition::new(3, 2, 1, 0));
+        assert_eq!(TestPath::from(NodePosition::new(3, 2, 1, 0)), path);
+
+        path.pop();
+        assert_eq!(NodePosition::from(path), NodePosition::new(2, 2, 0, 1));
+        assert_eq!(NodeIndex::from(TestPath::root()), NodeIndex::new(72));
+
+        for index in NodeIndex::<TestTree>::iter_all() {
+            assert_eq!(NodeIndex::from(TestPath::from(index)), index);
+        }
+    }
+}
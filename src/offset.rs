@@ -0,0 +1,167 @@
+use std::ops::{Add, Neg, Sub};
+
+use crate::{Direction, LayerPosition, NodePosition, TreeInterface};
+
+/// Signed difference of positions on `x`, `y` and `z` axes.
+///
+/// Adding an [`Offset`] to [`LayerPosition`] moves it by nodes of its layer, while adding it
+/// to [`NodePosition`] moves it by absolute coordinates. Both return [`None`] when the result
+/// is not a valid position in the same layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Offset {
+    /// Difference on `x` axis.
+    pub dx: isize,
+    /// Difference on `y` axis.
+    pub dy: isize,
+    /// Difference on `z` axis.
+    pub dz: isize,
+}
+
+impl Offset {
+    /// Creates a new [`Offset`].
+    pub const fn new(dx: isize, dy: isize, dz: isize) -> Self {
+        Self { dx, dy, dz }
+    }
+
+    /// Returns differences on `x`, `y` and `z` as an array.
+    pub const fn to_array(self) -> [isize; 3] {
+        [self.dx, self.dy, self.dz]
+    }
+}
+
+impl From<[isize; 3]> for Offset {
+    fn from([dx, dy, dz]: [isize; 3]) -> Self {
+        Self::new(dx, dy, dz)
+    }
+}
+
+impl From<Direction> for Offset {
+    fn from(direction: Direction) -> Self {
+        direction.offset().into()
+    }
+}
+
+impl Neg for Offset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.dx, -self.dy, -self.dz)
+    }
+}
+
+impl Add for Offset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.dx + rhs.dx, self.dy + rhs.dy, self.dz + rhs.dz)
+    }
+}
+
+impl Sub for Offset {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T> Add<Offset> for LayerPosition<T>
+where
+    T: TreeInterface,
+{
+    type Output = Option<Self>;
+
+    fn add(self, rhs: Offset) -> Self::Output {
+        self.checked_offset(rhs.to_array())
+    }
+}
+
+impl<T> Sub<Offset> for LayerPosition<T>
+where
+    T: TreeInterface,
+{
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Offset) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T> Add<Offset> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    type Output = Option<Self>;
+
+    fn add(self, rhs: Offset) -> Self::Output {
+        let [x, y, z] = [(self.x, rhs.dx), (self.y, rhs.dy), (self.z, rhs.dz)]
+            .map(|(coordinate, offset)| coordinate.checked_add_signed(offset));
+        let (x, y, z) = (x?, y?, z?);
+        Self::is_valid_position(x, y, z, self.depth).then(|| Self::new(x, y, z, self.depth))
+    }
+}
+
+impl<T> Sub<Offset> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    type Output = Option<Self>;
+
+    fn sub(self, rhs: Offset) -> Self::Output {
+        self + -rhs
+    }
+}
+
+#[cfg(test)]
+mod offset_tests {
+    use crate::{Direction, LayerPosition, NodePosition, Tree};
+
+    use super::Offset;
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn offset() {
+        let offset = Offset::new(1, -2, 3);
+        assert_eq!(-offset, Offset::new(-1, 2, -3));
+        assert_eq!(offset - offset, Offset::default());
+        assert_eq!(Offset::from(Direction::Back), Offset::new(0, 0, 1));
+        assert_eq!(Offset::from([1, -2, 3]), offset);
+    }
+
+    #[test]
+    fn layer_position() {
+        let position = LayerPosition::<TestTree>::new(1, 3, 0, 0);
+        assert_eq!(
+            position + Offset::new(2, -3, 1),
+            Some(LayerPosition::new(3, 0, 1, 0))
+        );
+        assert_eq!(
+            position - Offset::new(1, 1, 0),
+            Some(LayerPosition::new(0, 2, 0, 0))
+        );
+        assert_eq!(position + Offset::new(0, 1, 0), None);
+        assert_eq!(position - Offset::new(0, 0, 1), None);
+        assert_eq!(
+            LayerPosition::<TestTree>::new(1, 1, 1, 1) + Offset::new(-1, 0, 0),
+            Some(LayerPosition::new(0, 1, 1, 1))
+        );
+    }
+
+    #[test]
+    fn node_position() {
+        let position = NodePosition::<TestTree>::new(2, 0, 2, 1);
+        assert_eq!(
+            position + Offset::new(-2, 2, 0),
+            Some(NodePosition::new(0, 2, 2, 1))
+        );
+        // Result has to be aligned to nodes of the same layer.
+        assert_eq!(position + Offset::new(1, 0, 0), None);
+        assert_eq!(position + Offset::new(2, 0, 0), None);
+        assert_eq!(position - Offset::new(0, 2, 0), None);
+        assert_eq!(
+            NodePosition::<TestTree>::new(3, 3, 3, 0) - Offset::new(3, 1, 2),
+            Some(NodePosition::new(0, 2, 1, 0))
+        );
+    }
+}
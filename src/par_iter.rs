@@ -0,0 +1,181 @@
+use std::fmt::Debug;
+
+use rayon::prelude::*;
+
+use crate::{Depth, DiffReport, Node, NodeIndex, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug + Send + Sync,
+{
+    /// Returns a parallel iterator over all [`nodes`](Node) together with their
+    /// [`indexes`](NodeIndex), same as [`iter`](Tree::iter).
+    pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (NodeIndex<Self>, &Node<T>)> {
+        self.stored
+            .par_iter()
+            .enumerate()
+            .map(|(index, node)| (NodeIndex::new(index), node))
+    }
+
+    /// Returns a parallel iterator over mutable references to all [`nodes`](Node)
+    /// together with their [`indexes`](NodeIndex), same as [`iter_mut`](Tree::iter_mut).
+    pub fn par_iter_mut(
+        &mut self,
+    ) -> impl IndexedParallelIterator<Item = (NodeIndex<Self>, &mut Node<T>)> {
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+        self.stored
+            .par_iter_mut()
+            .enumerate()
+            .map(|(index, node)| (NodeIndex::new(index), node))
+    }
+
+    /// Returns a parallel iterator over [`nodes`](Node) of layer in `depth`
+    /// together with their [`indexes`](NodeIndex).
+    pub fn par_layer_iter(
+        &self,
+        depth: Depth<Self>,
+    ) -> impl IndexedParallelIterator<Item = (NodeIndex<Self>, &Node<T>)> {
+        let depth = depth.get();
+        let start = Self::layer_start(depth);
+        self.stored[start..start + Self::layer_size(depth).get()]
+            .par_iter()
+            .enumerate()
+            .map(move |(index, node)| (NodeIndex::new(start + index), node))
+    }
+
+    /// Returns a parallel iterator over mutable references to [`nodes`](Node) of layer in `depth`
+    /// together with their [`indexes`](NodeIndex).
+    pub fn par_layer_iter_mut(
+        &mut self,
+        depth: Depth<Self>,
+    ) -> impl IndexedParallelIterator<Item = (NodeIndex<Self>, &mut Node<T>)> {
+        let depth = depth.get();
+        let start = Self::layer_start(depth);
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate(start);
+        }
+        if depth == 0 {
+            self.invalidate_value_filters();
+        }
+        self.stored[start..start + Self::layer_size(depth).get()]
+            .par_iter_mut()
+            .enumerate()
+            .map(move |(index, node)| (NodeIndex::new(start + index), node))
+    }
+
+    /// Compares all nodes with nodes of `other` in parallel and returns the same
+    /// [`DiffReport`] as [`diff_report`](Tree::diff_report).
+    ///
+    /// Layers with equal known [layer hashes](Tree::enable_layer_hashes) in both trees
+    /// are skipped, the others are split between threads.
+    pub fn par_diff_report<'a>(&'a self, other: &'a Self) -> DiffReport<'a, T, SIZE>
+    where
+        T: PartialEq,
+    {
+        let mut differing = Vec::new();
+        for depth in 0..Self::DEPTH {
+            if self.layer_hashes_match(other, depth) {
+                continue;
+            }
+            let layer = self.layer_range(depth);
+            let start = layer.start;
+            differing.par_extend(
+                self.stored[layer.clone()]
+                    .par_iter()
+                    .zip(&other.stored[layer])
+                    .enumerate()
+                    .filter(|(_, (left, right))| left != right)
+                    .map(move |(index, _)| NodeIndex::new(start + index)),
+            );
+        }
+        DiffReport::new(self, other, differing)
+    }
+}
+
+#[cfg(test)]
+mod par_iter_tests {
+    use rayon::prelude::*;
+    use rayon::ThreadPoolBuilder;
+
+    use crate::bench_fixtures::{scattered_indexes, terrain};
+    use crate::implemented_tree_sizes::TREE_32;
+    use crate::{Depth, Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn par_iter() {
+        let mut tree = TestTree::new();
+        tree.par_iter_mut()
+            .for_each(|(index, node)| *node = Node::Filled(index.raw()));
+        assert_eq!(tree.par_iter().count(), 73);
+        assert!(tree
+            .par_iter()
+            .all(|(index, node)| node == &Node::Filled(index.raw())));
+    }
+
+    #[test]
+    fn par_diff_report() {
+        let mut tree = TestTree::new();
+        let other = tree.clone();
+        assert!(tree.par_diff_report(&other).is_empty());
+        for index in [0, 5, 63, 64, 72] {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        assert_eq!(
+            tree.par_diff_report(&other).indexes(),
+            tree.diff_report(&other).indexes()
+        );
+        assert_eq!(tree.par_diff_report(&other).len(), 5);
+    }
+
+    #[test]
+    fn par_layer_iter() {
+        let mut tree = TestTree::new();
+        tree.par_layer_iter_mut(Depth::new(1))
+            .for_each(|(index, node)| *node = Node::Filled(index.raw()));
+        assert_eq!(tree.get(NodeIndex::new(63)), &Node::Empty);
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Filled(64));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Empty);
+
+        let indexes = tree
+            .par_layer_iter(Depth::new(1))
+            .map(|(index, _)| index.raw())
+            .collect::<Vec<_>>();
+        assert_eq!(indexes, (64..72).collect::<Vec<_>>());
+        assert_eq!(tree.par_layer_iter(Depth::new(0)).len(), 64);
+    }
+
+    #[test]
+    fn thread_count_independence() {
+        let mut tree = terrain::<TREE_32>();
+        let other = tree.clone();
+        for index in scattered_indexes(100) {
+            tree.set(index, Node::Filled(7));
+        }
+        let run = |threads| {
+            let pool = ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| {
+                let nodes = tree.par_iter().collect::<Vec<_>>();
+                let layer = tree.par_layer_iter(Depth::new(1)).collect::<Vec<_>>();
+                let diff = tree.par_diff_report(&other).indexes().to_vec();
+                (nodes, layer, diff)
+            })
+        };
+        let (nodes, layer, diff) = run(1);
+        assert_eq!(nodes, tree.iter().collect::<Vec<_>>());
+        assert_eq!(
+            layer,
+            tree.iter()
+                .filter(|(index, _)| index.depth() == 1)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(diff, tree.diff_report(&other).indexes());
+        assert_eq!(run(4), (nodes, layer, diff));
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::{BrickedLeaves, LayerPosition, MicroGrid, Node, NodeIndex, Tree, TreeInterface};
+
+/// Row size of a block of leaves.
+const LEAF_BLOCK: usize = 8;
+/// Amount of nodes of deeper layers in one block.
+const UPPER_BLOCK_LEN: usize = 512;
+
+/// Immutable octree with the same layout as [`Tree`], where every update returns a new tree
+/// sharing all unchanged blocks with the original one.
+///
+/// Leaves are split into cubic blocks with row size 8 and deeper layers into blocks of 512
+/// nodes in the order in which they are stored. Every block is behind an [`Arc`], so an update
+/// copies only a single block and pointers to the others.
+/// [Refinements](Tree::refine_leaf) are kept in one more shared map.
+#[derive(Debug)]
+pub struct PersistentTree<T, const SIZE: usize> {
+    blocks: Vec<Arc<Vec<Node<T>>>>,
+    /// Raw indexes of refined leaves mapped to their [`MicroGrid`], same as in [`Tree`].
+    pub(crate) refinements: Arc<BTreeMap<usize, MicroGrid>>,
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T, const SIZE: usize> Clone for PersistentTree<T, SIZE> {
+    fn clone(&self) -> Self {
+        Self {
+            blocks: self.blocks.clone(),
+            refinements: self.refinements.clone(),
+        }
+    }
+}
+
+impl<T, const SIZE: usize> PersistentTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone,
+{
+    /// Creates a new [`PersistentTree`] with all [`nodes`](Node) set to [`Empty`](Node::Empty).
+    pub fn new() -> Self {
+        let leaf_blocks = Tree::<T, SIZE>::LEAF_LAYER_LEN / Self::leaf_block_len();
+        let upper = SIZE - Tree::<T, SIZE>::LEAF_LAYER_LEN;
+        let blocks = (0..leaf_blocks)
+            .map(|_| Self::leaf_block_len())
+            .chain(
+                (0..upper)
+                    .step_by(UPPER_BLOCK_LEN)
+                    .map(|start| UPPER_BLOCK_LEN.min(upper - start)),
+            )
+            .map(|len| Arc::new(vec![Node::Empty; len]))
+            .collect();
+        Self {
+            blocks,
+            refinements: Arc::default(),
+        }
+    }
+
+    /// Creates a new [`PersistentTree`] holding the same nodes and refinements as `tree`.
+    pub fn from_tree(tree: &Tree<T, SIZE>) -> Self {
+        let mut persistent = Self::new();
+        for (index, node) in tree.stored.iter().enumerate() {
+            let (block, offset) = Self::locate(NodeIndex::new(index));
+            // No block is shared yet, so this never copies.
+            Arc::make_mut(&mut persistent.blocks[block])[offset] = node.clone();
+        }
+        persistent.refinements = Arc::new(tree.refinements.clone());
+        persistent
+    }
+
+    /// Returns a new [`Tree`] holding the same nodes and refinements.
+    pub fn to_tree(&self) -> Tree<T, SIZE> {
+        let mut tree = Tree::new();
+        for (index, node) in tree.stored.iter_mut().enumerate() {
+            *node = self.get(NodeIndex::new(index)).clone();
+        }
+        tree.refinements = self.refinements.as_ref().clone();
+        tree
+    }
+
+    /// Returns a reference to a [`Node`] on `position`.
+    pub fn get<P>(&self, position: P) -> &Node<T>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let (block, offset) = Self::locate(position.into());
+        &self.blocks[block][offset]
+    }
+
+    /// Returns [`MicroGrid`] attached to the leaf on `position`, if there is any.
+    pub fn refinement<P>(&self, position: P) -> Option<MicroGrid>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        self.refinements.get(&position.into().raw()).copied()
+    }
+
+    /// Returns a new [`PersistentTree`] with the node on `position` set to `node`,
+    /// sharing all other blocks with this one.
+    pub fn update<P>(&self, position: P, node: Node<T>) -> Self
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let mut updated = self.clone();
+        let (block, offset) = Self::locate(position.into());
+        Arc::make_mut(&mut updated.blocks[block])[offset] = node;
+        updated
+    }
+
+    /// Returns a new [`PersistentTree`] holding the same nodes and refinements as `tree`,
+    /// sharing all blocks in which no node differs from this one.
+    pub fn update_from(&self, tree: &Tree<T, SIZE>) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut updated = self.clone();
+        for (index, node) in tree.stored.iter().enumerate() {
+            let (block, offset) = Self::locate(NodeIndex::new(index));
+            if updated.blocks[block][offset] != *node {
+                // Only the first change of a block copies it.
+                Arc::make_mut(&mut updated.blocks[block])[offset] = node.clone();
+            }
+        }
+        if *updated.refinements != tree.refinements {
+            updated.refinements = Arc::new(tree.refinements.clone());
+        }
+        updated
+    }
+
+    /// Returns amount of blocks which are shared between this tree and `other`.
+    pub fn shared_blocks(&self, other: &Self) -> usize {
+        self.blocks
+            .iter()
+            .zip(&other.blocks)
+            .filter(|(block, other)| Arc::ptr_eq(block, other))
+            .count()
+    }
+
+    /// Returns all blocks, so memory shared between trees can be accounted for.
+    pub(crate) fn blocks(&self) -> &[Arc<Vec<Node<T>>>] {
+        &self.blocks
+    }
+
+    /// Returns amount of leaves in one block.
+    fn leaf_block_len() -> usize {
+        BrickedLeaves::<T, SIZE, LEAF_BLOCK>::BRICK_SIZE
+    }
+
+    /// Returns index of block holding node on `index` and offset of the node in it.
+    fn locate(index: NodeIndex<Tree<T, SIZE>>) -> (usize, usize) {
+        let leaves = Tree::<T, SIZE>::LEAF_LAYER_LEN;
+        if index.raw() < leaves {
+            let bricked = BrickedLeaves::<T, SIZE, LEAF_BLOCK>::index(LayerPosition::from(index));
+            (
+                bricked / Self::leaf_block_len(),
+                bricked % Self::leaf_block_len(),
+            )
+        } else {
+            let upper = index.raw() - leaves;
+            let leaf_blocks = leaves / Self::leaf_block_len();
+            (
+                leaf_blocks + upper / UPPER_BLOCK_LEN,
+                upper % UPPER_BLOCK_LEN,
+            )
+        }
+    }
+}
+
+impl<T, const SIZE: usize> Default for PersistentTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const SIZE: usize> From<&Tree<T, SIZE>> for PersistentTree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone,
+{
+    fn from(tree: &Tree<T, SIZE>) -> Self {
+        Self::from_tree(tree)
+    }
+}
+
+impl<T, const SIZE: usize> From<&PersistentTree<T, SIZE>> for Tree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone,
+{
+    fn from(tree: &PersistentTree<T, SIZE>) -> Self {
+        tree.to_tree()
+    }
+}
+
+#[cfg(test)]
+mod persistent_tests {
+    use crate::implemented_tree_sizes::TREE_32;
+    use crate::{LayerPosition, MicroGrid, Node, NodeIndex, Tree};
+
+    use super::PersistentTree;
+
+    type TestTree = Tree<usize, TREE_32>;
+
+    #[test]
+    fn update() {
+        let empty = PersistentTree::<usize, TREE_32>::new();
+        let blocks = empty.blocks.len();
+        assert_eq!(blocks, 64 + 10);
+
+        let first = empty.update(LayerPosition::new(9, 17, 30, 0), Node::Filled(1));
+        let second = first.update(NodeIndex::new(TREE_32 - 1), Node::Reduced);
+        assert_eq!(empty.get(LayerPosition::new(9, 17, 30, 0)), &Node::Empty);
+        assert_eq!(
+            first.get(LayerPosition::new(9, 17, 30, 0)),
+            &Node::Filled(1)
+        );
+        assert_eq!(first.get(NodeIndex::new(TREE_32 - 1)), &Node::Empty);
+        assert_eq!(
+            second.get(LayerPosition::new(9, 17, 30, 0)),
+            &Node::Filled(1)
+        );
+        assert_eq!(second.get(NodeIndex::new(TREE_32 - 1)), &Node::Reduced);
+
+        assert_eq!(empty.shared_blocks(&first), blocks - 1);
+        assert_eq!(first.shared_blocks(&second), blocks - 1);
+        assert_eq!(empty.shared_blocks(&second), blocks - 2);
+    }
+
+    #[test]
+    fn update_from() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(0), Node::Filled(0));
+        let first = PersistentTree::from(&tree);
+        let blocks = first.blocks.len();
+
+        tree.set(NodeIndex::new(1), Node::Filled(1));
+        tree.set(NodeIndex::new(TREE_32 - 1), Node::Reduced);
+        let second = first.update_from(&tree);
+        assert_eq!(first.shared_blocks(&second), blocks - 2);
+        assert_eq!(Tree::from(&second), tree);
+        assert_eq!(second.update_from(&tree).shared_blocks(&second), blocks);
+    }
+
+    #[test]
+    fn conversion() {
+        let mut tree = TestTree::new();
+        for index in [0, 7, 8, 1000, 32767, 32768, 36000, TREE_32 - 1] {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        let persistent = PersistentTree::from(&tree);
+        assert_eq!(persistent.get(NodeIndex::new(1000)), &Node::Filled(1000));
+        assert_eq!(Tree::from(&persistent), tree);
+
+        let updated = persistent.update(NodeIndex::new(7), Node::Empty);
+        tree.set(NodeIndex::new(7), Node::Empty);
+        assert_eq!(updated.to_tree(), tree);
+    }
+
+    #[test]
+    fn refinements() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(7), Node::Filled(7));
+        tree.refine_leaf(NodeIndex::new(7), MicroGrid::new(0b1011));
+        let first = PersistentTree::from(&tree);
+        assert_eq!(
+            first.refinement(NodeIndex::new(7)),
+            Some(MicroGrid::new(0b1011))
+        );
+        assert_eq!(first.to_tree(), tree);
+
+        tree.unrefine_leaf(NodeIndex::new(7));
+        let second = first.update_from(&tree);
+        assert_eq!(second.refinement(NodeIndex::new(7)), None);
+        assert_eq!(second.shared_blocks(&first), first.blocks.len());
+        assert_eq!(second.to_tree(), tree);
+    }
+}
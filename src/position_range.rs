@@ -0,0 +1,356 @@
+use std::ops::{Range, RangeInclusive};
+
+use crate::{LayerPosition, NodeIndex, NodeIndexRange, TreeInterface};
+
+/// Axis-aligned box of [`positions`](LayerPosition) in one layer between `min` and `max`,
+/// both inclusive.
+///
+/// The range is empty if any coordinate of `min` is greater than the one of `max`.
+/// It can be created from `min..=max` and iterated in the order in which nodes are stored,
+/// i.e. `x` changes the fastest.
+#[derive(Debug)]
+pub struct PositionRange<T> {
+    min: LayerPosition<T>,
+    max: LayerPosition<T>,
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Clone for PositionRange<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// [`Copy`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Copy for PositionRange<T> {}
+
+/// [`PartialEq`] is implemented manually, so there is no requirement on `T` to also implement [`PartialEq`].
+impl<T> PartialEq for PositionRange<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.min == other.min && self.max == other.max
+    }
+}
+
+impl<T> From<RangeInclusive<LayerPosition<T>>> for PositionRange<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: RangeInclusive<LayerPosition<T>>) -> Self {
+        let (min, max) = value.into_inner();
+        Self::new(min, max)
+    }
+}
+
+/// Creates a range of the shallowest layer from ranges of `x`, `y` and `z`,
+/// clamped to the layer.
+impl<T> From<[Range<usize>; 3]> for PositionRange<T>
+where
+    T: TreeInterface,
+{
+    fn from(value: [Range<usize>; 3]) -> Self {
+        let [xs, ys, zs] = value.map(|range| range.start..range.end.min(T::LEAF_ROW_LEN));
+        if xs.is_empty() || ys.is_empty() || zs.is_empty() {
+            // Bounds of an empty range do not have to be valid positions.
+            return Self {
+                min: LayerPosition::new_unchecked(1, 1, 1, 0),
+                max: LayerPosition::new_unchecked(0, 0, 0, 0),
+            };
+        }
+        Self::new(
+            LayerPosition::new(xs.start, ys.start, zs.start, 0),
+            LayerPosition::new(xs.end - 1, ys.end - 1, zs.end - 1, 0),
+        )
+    }
+}
+
+impl<T> PositionRange<T>
+where
+    T: TreeInterface,
+{
+    /// Creates a new [`PositionRange`] between `min` and `max`, both inclusive.
+    ///
+    /// Expects `min` and `max` to be in the same layer.
+    pub fn new(min: LayerPosition<T>, max: LayerPosition<T>) -> Self {
+        debug_assert_eq!(min.depth, max.depth);
+        Self { min, max }
+    }
+
+    /// Returns a range covering the whole layer in `depth`.
+    ///
+    /// Expects in-bounds `depth`.
+    pub fn layer(depth: usize) -> Self {
+        let last = T::row_size(depth).get() - 1;
+        Self::new(
+            LayerPosition::new(0, 0, 0, depth),
+            LayerPosition::new(last, last, last, depth),
+        )
+    }
+
+    /// Returns the lowest position of the range.
+    pub fn min(self) -> LayerPosition<T> {
+        self.min
+    }
+
+    /// Returns the highest position of the range.
+    pub fn max(self) -> LayerPosition<T> {
+        self.max
+    }
+
+    /// Returns depth of the layer of the range.
+    pub fn depth(self) -> usize {
+        self.min.depth
+    }
+
+    /// Returns `true` if there are no positions in the range.
+    pub fn is_empty(self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Returns amount of positions in the range.
+    pub fn len(self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        (self.max.x - self.min.x + 1)
+            * (self.max.y - self.min.y + 1)
+            * (self.max.z - self.min.z + 1)
+    }
+
+    /// Returns `true` if `position` is inside of the range.
+    pub fn contains(self, position: LayerPosition<T>) -> bool {
+        position.depth == self.depth()
+            && (self.min.x..=self.max.x).contains(&position.x)
+            && (self.min.y..=self.max.y).contains(&position.y)
+            && (self.min.z..=self.max.z).contains(&position.z)
+    }
+
+    /// Returns positions which are in both ranges, the result is empty if they do not overlap.
+    ///
+    /// Expects both ranges to be in the same layer.
+    pub fn intersection(self, other: Self) -> Self {
+        debug_assert_eq!(self.depth(), other.depth());
+        // Bounds are valid positions unless the result is empty.
+        Self::new(
+            LayerPosition::new_unchecked(
+                self.min.x.max(other.min.x),
+                self.min.y.max(other.min.y),
+                self.min.z.max(other.min.z),
+                self.depth(),
+            ),
+            LayerPosition::new_unchecked(
+                self.max.x.min(other.max.x),
+                self.max.y.min(other.max.y),
+                self.max.z.min(other.max.z),
+                self.depth(),
+            ),
+        )
+    }
+
+    /// Returns ranges of `x`, `y` and `z` of leaves covered by nodes in the range.
+    pub fn leaf_region(self) -> [Range<usize>; 3] {
+        let depth = self.depth();
+        [
+            (self.min.x, self.max.x),
+            (self.min.y, self.max.y),
+            (self.min.z, self.max.z),
+        ]
+        .map(|(min, max)| {
+            let start = min << depth;
+            start..((max + 1) << depth).max(start)
+        })
+    }
+
+    /// Returns an iterator over positions in the range, `x` changes the fastest.
+    pub fn iter(self) -> PositionRangeIter<T> {
+        PositionRangeIter {
+            range: self,
+            next: (!self.is_empty()).then_some(self.min),
+        }
+    }
+
+    /// Returns an iterator over ranges of [`indexes`](NodeIndex) of nodes in the range,
+    /// one for every row along `x`, as each row is stored contiguously.
+    pub fn index_runs(self) -> impl Iterator<Item = NodeIndexRange<T>> {
+        // Used only for rows of non-empty ranges, where `min.x` is at most `max.x`.
+        let len = self.max.x.wrapping_sub(self.min.x).wrapping_add(1);
+        self.rows().map(move |start| {
+            let start = NodeIndex::from(start);
+            NodeIndexRange::new(start..=NodeIndex::new(start.raw() + len - 1))
+        })
+    }
+
+    /// Returns an iterator over the first position of every row along `x`.
+    fn rows(self) -> impl Iterator<Item = LayerPosition<T>> {
+        let (min, max) = (self.min, self.max);
+        let zs = (!self.is_empty()).then_some(min.z..=max.z);
+        zs.into_iter().flatten().flat_map(move |z| {
+            (min.y..=max.y).map(move |y| LayerPosition::new(min.x, y, z, min.depth))
+        })
+    }
+}
+
+impl<T> IntoIterator for PositionRange<T>
+where
+    T: TreeInterface,
+{
+    type Item = LayerPosition<T>;
+    type IntoIter = PositionRangeIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over [`positions`](LayerPosition) in [`PositionRange`], created by
+/// [`PositionRange::iter`].
+#[derive(Debug)]
+pub struct PositionRangeIter<T> {
+    range: PositionRange<T>,
+    /// Position of the next node, [`None`] when all positions were visited.
+    next: Option<LayerPosition<T>>,
+}
+
+impl<T> Iterator for PositionRangeIter<T>
+where
+    T: TreeInterface,
+{
+    type Item = LayerPosition<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (min, max) = (self.range.min, self.range.max);
+        let position = self.next?;
+        let mut next = position;
+        if next.x < max.x {
+            next.x += 1;
+        } else if next.y < max.y {
+            next.x = min.x;
+            next.y += 1;
+        } else if next.z < max.z {
+            next.x = min.x;
+            next.y = min.y;
+            next.z += 1;
+        }
+        self.next = (next != position).then_some(next);
+        Some(position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, max) = (self.range.min, self.range.max);
+        let remaining = self.next.map_or(0, |next| {
+            let width = max.x - min.x + 1;
+            let height = max.y - min.y + 1;
+            let planes = (max.z - next.z) * width * height;
+            let rows = (max.y - next.y) * width;
+            planes + rows + max.x - next.x + 1
+        });
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for PositionRangeIter<T> where T: TreeInterface {}
+
+#[cfg(test)]
+mod position_range_tests {
+    use crate::{LayerPosition, NodeIndex, Tree};
+
+    use super::PositionRange;
+
+    type TestTree = Tree<usize, 73>;
+    type TestRange = PositionRange<TestTree>;
+    type TestLayerPosition = LayerPosition<TestTree>;
+
+    #[test]
+    fn iter() {
+        let range = TestRange::from(
+            TestLayerPosition::new(1, 0, 2, 0)..=TestLayerPosition::new(2, 1, 2, 0),
+        );
+        assert_eq!(range.len(), 4);
+        assert_eq!(
+            range.iter().collect::<Vec<_>>(),
+            vec![
+                TestLayerPosition::new(1, 0, 2, 0),
+                TestLayerPosition::new(2, 0, 2, 0),
+                TestLayerPosition::new(1, 1, 2, 0),
+                TestLayerPosition::new(2, 1, 2, 0),
+            ]
+        );
+        assert_eq!(range.into_iter().count(), 4);
+        assert_eq!(
+            range.index_runs().map(Vec::from_iter).collect::<Vec<_>>(),
+            vec![
+                vec![NodeIndex::new(33), NodeIndex::new(34)],
+                vec![NodeIndex::new(37), NodeIndex::new(38)]
+            ]
+        );
+        for run in range.index_runs() {
+            for index in run {
+                assert!(range.contains(index.into()));
+            }
+        }
+
+        assert_eq!(TestRange::layer(0).len(), 64);
+        assert_eq!(
+            TestRange::layer(2)
+                .index_runs()
+                .flatten()
+                .collect::<Vec<_>>(),
+            vec![NodeIndex::new(72)]
+        );
+        assert!(!TestRange::layer(1).contains(TestLayerPosition::new(0, 0, 0, 0)));
+    }
+
+    #[test]
+    fn intersection() {
+        let a = TestRange::from(
+            TestLayerPosition::new(0, 0, 0, 0)..=TestLayerPosition::new(2, 2, 2, 0),
+        );
+        let b = TestRange::from(
+            TestLayerPosition::new(1, 2, 0, 0)..=TestLayerPosition::new(3, 3, 3, 0),
+        );
+        let intersection = a.intersection(b);
+        assert_eq!(
+            intersection,
+            TestRange::new(
+                TestLayerPosition::new(1, 2, 0, 0),
+                TestLayerPosition::new(2, 2, 2, 0)
+            )
+        );
+        assert_eq!(intersection.len(), 6);
+
+        let c = TestRange::from(
+            TestLayerPosition::new(3, 3, 3, 0)..=TestLayerPosition::new(3, 3, 3, 0),
+        );
+        let empty = a.intersection(c);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert_eq!(empty.iter().count(), 0);
+        assert_eq!(empty.index_runs().count(), 0);
+    }
+
+    #[test]
+    fn leaf_region() {
+        let range = TestRange::from([1..3, 0..1, 2..9]);
+        assert_eq!(
+            range,
+            TestRange::new(
+                TestLayerPosition::new(1, 0, 2, 0),
+                TestLayerPosition::new(2, 0, 3, 0)
+            )
+        );
+        assert_eq!(range.leaf_region(), [1..3, 0..1, 2..4]);
+        assert_eq!(range.iter().len(), 4);
+
+        let empty = TestRange::from([1..3, 2..2, 0..4]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.iter().len(), 0);
+        assert!(Tree::<usize, 1>::new()
+            .iter_region([0..0, 0..1, 0..1])
+            .next()
+            .is_none());
+
+        let upper = TestRange::from(
+            TestLayerPosition::new(1, 0, 0, 1)..=TestLayerPosition::new(1, 1, 0, 1),
+        );
+        assert_eq!(upper.leaf_region(), [2..4, 0..4, 0..2]);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Hints the processor to load `node` into cache, as it is likely to be read soon.
+///
+/// Does nothing unless the `perf` feature is enabled on `x86_64`.
+#[inline(always)]
+pub(crate) fn prefetch<T>(node: &Node<T>) {
+    #[cfg(all(feature = "perf", target_arch = "x86_64"))]
+    {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        // SAFETY: prefetch is only a hint, it never faults and `node` is a valid reference anyway.
+        #[allow(unused_unsafe)]
+        unsafe {
+            _mm_prefetch::<_MM_HINT_T0>((node as *const Node<T>).cast());
+        }
+    }
+    #[cfg(not(all(feature = "perf", target_arch = "x86_64")))]
+    let _ = node;
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+{
+    /// Prefetches `children` of one node before they are visited.
+    ///
+    /// Children next to each other on `x` are stored next to each other, so only every second
+    /// child is prefetched.
+    #[inline(always)]
+    pub(crate) fn prefetch_children(&self, children: &[NodeIndex<Self>; 8]) {
+        for child in children.iter().step_by(2) {
+            prefetch(&self.stored[*child]);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Range;
+
+use crate::{Node, NodeIndex, NodePosition, PositionRange, Tree, TreeInterface};
+
+/// Wrapper around [`Tree`] which memoizes results of queries over regions of the tree.
+///
+/// Every cached result is stored under a key together with a region of the shallowest layer
+/// it depends on. Changes have to be made through [`set`](QueryCache::set), which forgets
+/// all results whose region intersects the changed node.
+#[derive(Debug)]
+pub struct QueryCache<T, K, V, const SIZE: usize> {
+    tree: Tree<T, SIZE>,
+    /// Cached results together with ranges of `x`, `y` and `z` they depend on.
+    entries: HashMap<K, ([Range<usize>; 3], V)>,
+}
+
+impl<T, K, V, const SIZE: usize> QueryCache<T, K, V, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+    K: Hash + Eq,
+{
+    /// Creates a new [`QueryCache`] around `tree` without any cached results.
+    pub fn new(tree: Tree<T, SIZE>) -> Self {
+        Self {
+            tree,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Tree`].
+    pub fn tree(&self) -> &Tree<T, SIZE> {
+        &self.tree
+    }
+
+    /// Returns the wrapped [`Tree`], dropping all cached results.
+    pub fn into_inner(self) -> Tree<T, SIZE> {
+        self.tree
+    }
+
+    /// Returns a result cached under `key`, or computes it by `query` and caches it.
+    ///
+    /// `region` is a [`PositionRange`] or ranges of `x`, `y` and `z` in the shallowest layer
+    /// which `query` reads, it is used only when the result is computed.
+    pub fn get_or_insert_with<R, F>(&mut self, key: K, region: R, query: F) -> &V
+    where
+        R: Into<PositionRange<Tree<T, SIZE>>>,
+        F: FnOnce(&Tree<T, SIZE>) -> V,
+    {
+        let tree = &self.tree;
+        &self
+            .entries
+            .entry(key)
+            .or_insert_with(|| (region.into().leaf_region(), query(tree)))
+            .1
+    }
+
+    /// Returns a result cached under `key` if there is one.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    /// Sets the node on `position` to provided [`node`](Node), forgets all results whose region
+    /// intersects it and returns a [`Node`] previously stored on `position`.
+    pub fn set<P>(&mut self, position: P, node: Node<T>) -> Node<T>
+    where
+        P: Into<NodeIndex<Tree<T, SIZE>>>,
+    {
+        let index = position.into();
+        let position = NodePosition::from(index);
+        let extent = 1 << position.depth;
+        let changed = [position.x, position.y, position.z].map(|start| start..start + extent);
+        self.entries.retain(|_, (region, _)| {
+            !region
+                .iter()
+                .zip(&changed)
+                .all(|(region, changed)| region.start < changed.end && changed.start < region.end)
+        });
+        self.tree.set(index, node)
+    }
+
+    /// Forgets all cached results.
+    pub fn invalidate_all(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod query_cache_tests {
+    use crate::{LayerPosition, Node, Tree};
+
+    use super::QueryCache;
+
+    type TestTree = Tree<usize, 73>;
+
+    fn count_filled(tree: &TestTree) -> usize {
+        tree.filled().count()
+    }
+
+    #[test]
+    fn query_cache() {
+        let mut cache = QueryCache::new(TestTree::new());
+        cache.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+
+        let mut calls = 0;
+        let mut query = |tree: &TestTree| {
+            calls += 1;
+            count_filled(tree)
+        };
+        assert_eq!(
+            *cache.get_or_insert_with("low", [0..2, 0..2, 0..2], &mut query),
+            1
+        );
+        assert_eq!(
+            *cache.get_or_insert_with("low", [0..2, 0..2, 0..2], &mut query),
+            1
+        );
+        assert_eq!(
+            *cache.get_or_insert_with("high", [2..4, 2..4, 2..4], &mut query),
+            1
+        );
+        assert_eq!(calls, 2);
+
+        // Outside of both regions.
+        cache.set(LayerPosition::new(3, 0, 0, 0), Node::Filled(2));
+        assert_eq!(cache.get(&"low"), Some(&1));
+        assert_eq!(cache.get(&"high"), Some(&1));
+
+        cache.set(LayerPosition::new(1, 1, 1, 0), Node::Filled(3));
+        assert_eq!(cache.get(&"low"), None);
+        assert_eq!(cache.get(&"high"), Some(&1));
+
+        // Node in depth 1 covers `x`, `y` and `z` in range `2..4`.
+        cache.set(LayerPosition::new(1, 1, 1, 1), Node::Filled(4));
+        assert_eq!(cache.get(&"high"), None);
+
+        assert_eq!(
+            *cache.get_or_insert_with("low", [0..2, 0..2, 0..2], count_filled),
+            4
+        );
+        cache.invalidate_all();
+        assert_eq!(cache.get(&"low"), None);
+        assert_eq!(cache.into_inner().filled().count(), 4);
+    }
+}
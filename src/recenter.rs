@@ -0,0 +1,131 @@
+use std::fmt::Debug;
+
+use crate::{LayerPosition, NodeIndex, NodePosition, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug + Clone,
+{
+    /// Returns a new [`Tree`] with content of the shallowest layer shifted so `focus` ends up
+    /// in the center of the tree, together with applied offset on `x`, `y` and `z` axes.
+    ///
+    /// Nodes shifted outside of the tree are dropped and uncovered nodes are
+    /// [`Empty`](crate::Node::Empty). [Refinements](Tree::refine_leaf) are shifted with their leaves. Only the shallowest layer is shifted, upper layers of returned
+    /// tree are [`Empty`](crate::Node::Empty) and need to be [built](Tree::build) again.
+    pub fn recenter(&self, focus: NodePosition<Self>) -> (Self, [isize; 3]) {
+        let center = (Self::BIGGEST_ROW_SIZE / 2) as isize;
+        let offset = [
+            center - focus.x as isize,
+            center - focus.y as isize,
+            center - focus.z as isize,
+        ];
+
+        let mut tree = Self::new();
+        let row_size = Self::row_size(0);
+        let size = Self::BIGGEST_ROW_SIZE as isize;
+
+        // Range of `x` in destination rows which is covered by source rows.
+        let start_x = offset[0].clamp(0, size);
+        let end_x = (size + offset[0]).clamp(0, size);
+        if start_x >= end_x {
+            return (tree, offset);
+        }
+        let len = (end_x - start_x) as usize;
+        let source_x = (start_x - offset[0]) as usize;
+
+        for z in 0..size {
+            let source_z = z - offset[2];
+            if !(0..size).contains(&source_z) {
+                continue;
+            }
+            for y in 0..size {
+                let source_y = y - offset[1];
+                if !(0..size).contains(&source_y) {
+                    continue;
+                }
+                let source = row_size.stride(source_y as usize, source_z as usize) + source_x;
+                let destination = row_size.stride(y as usize, z as usize) + start_x as usize;
+                tree.stored[destination..destination + len]
+                    .clone_from_slice(&self.stored[source..source + len]);
+            }
+        }
+        for (index, grid) in &self.refinements {
+            let position = LayerPosition::<Self>::from(NodeIndex::new(*index));
+            let source = [position.x, position.y, position.z];
+            let [x, y, z] = [0, 1, 2].map(|axis| source[axis] as isize + offset[axis]);
+            if [x, y, z]
+                .iter()
+                .all(|coordinate| (0..size).contains(coordinate))
+            {
+                let position = LayerPosition::<Self>::new(x as usize, y as usize, z as usize, 0);
+                tree.refinements
+                    .insert(NodeIndex::from(position).raw(), *grid);
+            }
+        }
+        (tree, offset)
+    }
+}
+
+#[cfg(test)]
+mod recenter_tests {
+    use crate::{LayerPosition, MicroGrid, Node, NodePosition, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn recenter() {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        tree.set(LayerPosition::new(3, 3, 3, 0), Node::Filled(2));
+        tree.set(LayerPosition::new(1, 2, 0, 0), Node::Filled(3));
+        tree.set(LayerPosition::new(0, 0, 0, 2), Node::Filled(4));
+
+        let (recentered, offset) = tree.recenter(NodePosition::new(0, 0, 0, 0));
+        assert_eq!(offset, [2, 2, 2]);
+        assert_eq!(
+            recentered.get(LayerPosition::new(2, 2, 2, 0)),
+            &Node::Filled(1)
+        );
+        assert_eq!(recentered.get(LayerPosition::new(3, 3, 2, 0)), &Node::Empty);
+        assert_eq!(recentered.get(LayerPosition::new(0, 0, 0, 2)), &Node::Empty);
+        assert_eq!(
+            recentered
+                .iter()
+                .filter(|(_, node)| **node != Node::Empty)
+                .count(),
+            1
+        );
+
+        let (recentered, offset) = tree.recenter(NodePosition::new(3, 3, 1, 0));
+        assert_eq!(offset, [-1, -1, 1]);
+        assert_eq!(recentered.get(LayerPosition::new(2, 2, 3, 0)), &Node::Empty);
+        assert_eq!(
+            recentered.get(LayerPosition::new(0, 1, 1, 0)),
+            &Node::Filled(3)
+        );
+
+        let (recentered, offset) = tree.recenter(NodePosition::new(2, 2, 2, 0));
+        assert_eq!(offset, [0, 0, 0]);
+        assert_eq!(
+            recentered.get(LayerPosition::new(3, 3, 3, 0)),
+            &Node::Filled(2)
+        );
+    }
+
+    #[test]
+    fn recenter_refinements() {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(0, 0, 0, 0), Node::Filled(1));
+        tree.refine_leaf(LayerPosition::new(0, 0, 0, 0), MicroGrid::new(1));
+        tree.refine_leaf(LayerPosition::new(3, 0, 0, 0), MicroGrid::FULL);
+
+        let (recentered, _) = tree.recenter(NodePosition::new(0, 0, 0, 0));
+        assert_eq!(
+            recentered.refinement(LayerPosition::new(2, 2, 2, 0)),
+            Some(MicroGrid::new(1))
+        );
+        assert_eq!(recentered.refinement(LayerPosition::new(0, 0, 0, 0)), None);
+        assert_eq!(recentered.refinements.len(), 1);
+    }
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use crate::{LayerPosition, Node, NodeIndex, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Replaces every value which is a key of `map` by its value and returns amount of changed nodes.
+    ///
+    /// Leaf layer is scanned as a whole, but in deeper layers only ancestors of changed leaves
+    /// are remapped, as values of deeper layers are expected to be taken from their descendants.
+    /// Changes are not recorded as tombstones.
+    pub fn remap_values(&mut self, map: &HashMap<T, T>) -> usize
+    where
+        T: Eq + Hash + Clone,
+    {
+        if map.is_empty() {
+            return 0;
+        }
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+
+        let mut changed = 0;
+        let mut dirty = Vec::new();
+        for index in 0..Self::LEAF_LAYER_LEN {
+            if self.remap_node(NodeIndex::new(index), map) {
+                changed += 1;
+                dirty.push(NodeIndex::<Self>::new(index));
+            }
+        }
+        for _ in 1..Self::DEPTH {
+            dirty = dirty
+                .into_iter()
+                .filter_map(|index| {
+                    Some(NodeIndex::from(
+                        LayerPosition::from(index).parrent_position()?,
+                    ))
+                })
+                .collect();
+            dirty.sort_unstable_by_key(|index| index.raw());
+            dirty.dedup();
+            changed += dirty
+                .iter()
+                .filter(|index| self.remap_node(**index, map))
+                .count();
+        }
+        changed
+    }
+
+    /// Remaps value of node on `index` and returns `true` if it changed.
+    fn remap_node(&mut self, index: NodeIndex<Self>, map: &HashMap<T, T>) -> bool
+    where
+        T: Eq + Hash + Clone,
+    {
+        if let Node::Filled(value) = &mut self.stored[index.raw()] {
+            if let Some(new) = map.get(value) {
+                *value = new.clone();
+                return true;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod remap_tests {
+    use std::collections::HashMap;
+
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<u16, 73>;
+
+    /// Returns the same value if all children hold it.
+    fn combine_rule(children: &[&Node<u16>]) -> Node<u16> {
+        match children[0] {
+            Node::Filled(value) if children.iter().all(|child| *child == children[0]) => {
+                Node::Filled(*value)
+            }
+            _ => Node::Reduced,
+        }
+    }
+
+    #[test]
+    fn remap_values() {
+        let mut tree = TestTree::new();
+        for index in 0..64 {
+            tree.set(NodeIndex::new(index), Node::Filled(index as u16 % 3));
+        }
+        // The first brick of leaves is filled by the same value, so its parrent is as well.
+        for index in [0, 1, 4, 5, 16, 17, 20, 21] {
+            tree.set(NodeIndex::new(index), Node::Filled(2));
+        }
+        tree.build(combine_rule);
+        let map = HashMap::from([(2, 5), (1, 2)]);
+
+        let mut expected = tree.clone();
+        for index in 0..64 {
+            if let Node::Filled(value) = tree.get(NodeIndex::new(index)) {
+                let value = map.get(value).copied().unwrap_or(*value);
+                expected.set(NodeIndex::new(index), Node::Filled(value));
+            }
+        }
+        expected.build(combine_rule);
+
+        let changed = tree.remap_values(&map);
+        assert_eq!(tree, expected);
+        // 8 leaves of the first brick and their parrent and 36 other leaves holding 1 or 2.
+        assert_eq!(changed, 8 + 1 + 36);
+        assert_eq!(tree.remap_values(&HashMap::new()), 0);
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt::Debug;
+
+use crate::{Axis, LayerPosition, Node, Tree, TreeInterface};
+
+/// Color with red, green, blue and alpha channels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgba {
+    /// Red channel.
+    pub r: u8,
+    /// Green channel.
+    pub g: u8,
+    /// Blue channel.
+    pub b: u8,
+    /// Alpha channel.
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Fully transparent color, used for pixels where no node was hit.
+    pub const TRANSPARENT: Rgba = Rgba::new(0, 0, 0, 0);
+
+    /// Creates a new [Rgba].
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Renders the shallowest layer of [`Tree`] by marching rays along `axis`,
+    /// producing an image `resolution` pixels wide and high.
+    ///
+    /// Rays start at coordinate 0 on `axis` and stop at the first [`Filled`](Node::Filled) node,
+    /// which is shaded by `shade` together with distance of the hit along `axis`.
+    /// Pixels where nothing was hit are [`Rgba::TRANSPARENT`]. Rays skip whole
+    /// [`Empty`](Node::Empty) nodes in upper layers, so upper layers are expected to be
+    /// [built](Tree::build).
+    ///
+    /// Pixels are stored row by row. Image axes are the remaining tree axes in `x`, `y`, `z` order,
+    /// the first of them being horizontal.
+    pub fn render_orthographic<F>(&self, axis: Axis, resolution: usize, shade: F) -> Vec<Rgba>
+    where
+        F: Fn(&T, usize) -> Rgba,
+    {
+        let mut image = Vec::with_capacity(resolution * resolution);
+        for v in 0..resolution {
+            for u in 0..resolution {
+                let u = u * Self::BIGGEST_ROW_SIZE / resolution;
+                let v = v * Self::BIGGEST_ROW_SIZE / resolution;
+                image.push(self.march(axis, u, v, &shade));
+            }
+        }
+        image
+    }
+
+    /// Marches a single ray along `axis` through the shallowest layer.
+    fn march<F>(&self, axis: Axis, u: usize, v: usize, shade: &F) -> Rgba
+    where
+        F: Fn(&T, usize) -> Rgba,
+    {
+        let mut distance = 0;
+        'ray: while distance < Self::BIGGEST_ROW_SIZE {
+            let (x, y, z) = match axis {
+                Axis::X => (distance, u, v),
+                Axis::Y => (u, distance, v),
+                Axis::Z => (u, v, distance),
+            };
+            for depth in (0..Self::DEPTH).rev() {
+                let position = LayerPosition::new(x >> depth, y >> depth, z >> depth, depth);
+                match self.get(position) {
+                    Node::Empty => {
+                        // Skip to the end of this node along the ray.
+                        distance = ((distance >> depth) + 1) << depth;
+                        continue 'ray;
+                    }
+                    Node::Filled(value) if depth == 0 => return shade(value, distance),
+                    _ => {}
+                }
+            }
+            distance += 1;
+        }
+        Rgba::TRANSPARENT
+    }
+}
+
+#[cfg(test)]
+mod render_tests {
+    use crate::{Axis, LayerPosition, Node, Tree};
+
+    use super::Rgba;
+
+    type TestTree = Tree<u8, 73>;
+
+    fn tree() -> TestTree {
+        let mut tree = TestTree::new();
+        tree.set(LayerPosition::new(1, 2, 3, 0), Node::Filled(10));
+        tree.set(LayerPosition::new(1, 2, 1, 0), Node::Filled(20));
+        tree.build(|children| {
+            if children.iter().all(|child| matches!(child, Node::Empty)) {
+                Node::Empty
+            } else {
+                Node::Reduced
+            }
+        });
+        tree
+    }
+
+    #[test]
+    fn render_orthographic() {
+        let tree = tree();
+        let shade = |value: &u8, distance: usize| Rgba::new(*value, distance as u8, 0, 255);
+
+        let image = tree.render_orthographic(Axis::Z, 4, shade);
+        assert_eq!(image.len(), 16);
+        assert_eq!(image[2 * 4 + 1], Rgba::new(20, 1, 0, 255));
+        assert_eq!(
+            image
+                .iter()
+                .filter(|pixel| **pixel == Rgba::TRANSPARENT)
+                .count(),
+            15
+        );
+
+        let image = tree.render_orthographic(Axis::Y, 4, shade);
+        assert_eq!(image[3 * 4 + 1], Rgba::new(10, 2, 0, 255));
+        assert_eq!(image[4 + 1], Rgba::new(20, 2, 0, 255));
+
+        let image = tree.render_orthographic(Axis::X, 4, shade);
+        assert_eq!(image[3 * 4 + 2], Rgba::new(10, 1, 0, 255));
+        assert_eq!(image[4 + 2], Rgba::new(20, 1, 0, 255));
+
+        let image = tree.render_orthographic(Axis::X, 2, shade);
+        assert_eq!(image, vec![Rgba::TRANSPARENT; 4]);
+    }
+}
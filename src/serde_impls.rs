@@ -0,0 +1,183 @@
+//! [`Serialize`] and [`Deserialize`] of coordinate types.
+//!
+//! Coordinates are stored as plain numbers, or tuples of them, and are validated against
+//! the associated [`Tree`](crate::Tree) when deserialized.
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Depth, LayerIndex, LayerPosition, NodeIndex, NodePosition, TreeInterface};
+
+/// Serialized as the raw index.
+impl<T> Serialize for NodeIndex<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        usize::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NodeIndex<T>
+where
+    T: TreeInterface,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::new_checked(usize::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as a tuple of `x`, `y`, `z` and `depth`.
+impl<T> Serialize for NodePosition<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.x, self.y, self.z, self.depth).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for NodePosition<T>
+where
+    T: TreeInterface,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (x, y, z, depth) = Deserialize::deserialize(deserializer)?;
+        Self::new_checked(x, y, z, depth).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as a tuple of `index` and `depth`.
+impl<T> Serialize for LayerIndex<T>
+where
+    T: TreeInterface,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.get_raw().serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LayerIndex<T>
+where
+    T: TreeInterface,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (index, depth) = Deserialize::deserialize(deserializer)?;
+        Self::new_checked(index, depth).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as a tuple of `x`, `y`, `z` and `depth`.
+impl<T> Serialize for LayerPosition<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        (self.x, self.y, self.z, self.depth).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LayerPosition<T>
+where
+    T: TreeInterface,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (x, y, z, depth) = Deserialize::deserialize(deserializer)?;
+        Self::new_checked(x, y, z, depth).map_err(D::Error::custom)
+    }
+}
+
+/// Serialized as the depth.
+impl<T> Serialize for Depth<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        usize::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Depth<T>
+where
+    T: TreeInterface,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::try_new(usize::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod serde_tests {
+    use crate::{Depth, LayerIndex, LayerPosition, NodeIndex, NodePosition, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn round_trip() {
+        let index = NodeIndex::<TestTree>::new(42);
+        let json = serde_json::to_string(&index).unwrap();
+        assert_eq!(json, "42");
+        assert_eq!(
+            serde_json::from_str::<NodeIndex<TestTree>>(&json).unwrap(),
+            index
+        );
+
+        let position = NodePosition::<TestTree>::new(2, 0, 2, 1);
+        let json = serde_json::to_string(&position).unwrap();
+        assert_eq!(json, "[2,0,2,1]");
+        assert_eq!(
+            serde_json::from_str::<NodePosition<TestTree>>(&json).unwrap(),
+            position
+        );
+
+        let position = LayerPosition::<TestTree>::new(1, 0, 1, 1);
+        let json = serde_json::to_string(&position).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LayerPosition<TestTree>>(&json).unwrap(),
+            position
+        );
+
+        let index = LayerIndex::<TestTree>::new(7, 1);
+        let json = serde_json::to_string(&index).unwrap();
+        assert_eq!(json, "[7,1]");
+        assert_eq!(
+            serde_json::from_str::<LayerIndex<TestTree>>(&json).unwrap(),
+            index
+        );
+
+        let depth = Depth::<TestTree>::MAX;
+        assert_eq!(serde_json::to_string(&depth).unwrap(), "2");
+        assert_eq!(serde_json::from_str::<Depth<TestTree>>("2").unwrap(), depth);
+    }
+
+    #[test]
+    fn validation() {
+        let error = serde_json::from_str::<NodeIndex<TestTree>>("73").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid index: expected less than 73, got 73"
+        );
+        assert!(serde_json::from_str::<NodePosition<TestTree>>("[1,0,0,1]").is_err());
+        assert!(serde_json::from_str::<LayerPosition<TestTree>>("[2,0,0,1]").is_err());
+        assert!(serde_json::from_str::<LayerIndex<TestTree>>("[8,1]").is_err());
+        assert!(serde_json::from_str::<Depth<TestTree>>("3").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt::Display;
+
+/// Amount of nodes in one row of a specific layer of [`Tree`](crate::Tree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RowSize(usize);
+
+impl RowSize {
+    /// Creates a new [RowSize].
+    pub const fn new(row_size: usize) -> Self {
+        Self(row_size)
+    }
+
+    /// Returns row size as [`usize`].
+    pub const fn get(self) -> usize {
+        self.0
+    }
+
+    /// Returns amount of nodes in one plane of the layer, i.e. row size squared.
+    pub const fn plane(self) -> usize {
+        self.0 * self.0
+    }
+
+    /// Returns [LayerSize] of layer with this row size, i.e. row size cubed.
+    pub const fn cells(self) -> LayerSize {
+        LayerSize(self.0 * self.0 * self.0)
+    }
+
+    /// Returns offset of the first node in row at `y` and `z` from the start of the layer.
+    pub const fn stride(self, y: usize, z: usize) -> usize {
+        y * self.0 + z * self.0 * self.0
+    }
+}
+
+/// [`Display`] shows the inner value.
+impl Display for RowSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<RowSize> for usize {
+    fn from(value: RowSize) -> Self {
+        value.0
+    }
+}
+
+/// Amount of nodes in a specific layer of [`Tree`](crate::Tree).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LayerSize(usize);
+
+impl LayerSize {
+    /// Creates a new [LayerSize].
+    pub const fn new(layer_size: usize) -> Self {
+        Self(layer_size)
+    }
+
+    /// Returns layer size as [`usize`].
+    pub const fn get(self) -> usize {
+        self.0
+    }
+}
+
+/// [`Display`] shows the inner value.
+impl Display for LayerSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<LayerSize> for usize {
+    fn from(value: LayerSize) -> Self {
+        value.0
+    }
+}
+
+#[cfg(test)]
+mod size_tests {
+    use super::{LayerSize, RowSize};
+
+    #[test]
+    fn row_size() {
+        let row_size = RowSize::new(4);
+        assert_eq!(row_size.get(), 4);
+        assert_eq!(row_size.plane(), 16);
+        assert_eq!(row_size.cells(), LayerSize::new(64));
+        assert_eq!(row_size.stride(0, 0), 0);
+        assert_eq!(row_size.stride(1, 0), 4);
+        assert_eq!(row_size.stride(3, 3), 60);
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use crate::{Node, PersistentTree, Tree, TreeInterface};
+
+/// Named snapshots of a [`Tree`] kept within a memory budget.
+///
+/// Snapshots are stored as [`PersistentTree`]s sharing unchanged blocks with the snapshot saved
+/// before them, so memory is counted once for every block no matter how many snapshots share it.
+/// When the budget is exceeded, the least recently used snapshots are evicted, except the newest.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore<T, const SIZE: usize> {
+    /// Maximal amount of bytes taken by nodes of all snapshots.
+    budget: usize,
+    /// Snapshots ordered from the least recently used one.
+    snapshots: Vec<(String, PersistentTree<T, SIZE>)>,
+    /// Snapshot saved most recently, which is used as a base of the next one.
+    latest: Option<PersistentTree<T, SIZE>>,
+}
+
+impl<T, const SIZE: usize> SnapshotStore<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug + Clone + PartialEq,
+{
+    /// Creates a new [`SnapshotStore`] keeping nodes of at most `budget` bytes.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            snapshots: Vec::new(),
+            latest: None,
+        }
+    }
+
+    /// Returns budget of the store in bytes.
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Saves snapshot of `tree` under `name`, replacing a snapshot saved under the same name,
+    /// and evicts the least recently used snapshots if the budget is exceeded.
+    pub fn save<S>(&mut self, name: S, tree: &Tree<T, SIZE>)
+    where
+        S: Into<String>,
+    {
+        let name = name.into();
+        let snapshot = match &self.latest {
+            Some(latest) => latest.update_from(tree),
+            None => PersistentTree::from_tree(tree),
+        };
+        self.remove(&name);
+        self.snapshots.push((name, snapshot.clone()));
+        self.latest = Some(snapshot);
+        while self.memory_usage() > self.budget && self.snapshots.len() > 1 {
+            self.snapshots.remove(0);
+        }
+    }
+
+    /// Returns a new [`Tree`] restored from snapshot saved under `name`,
+    /// or [`None`] if there is no such snapshot.
+    ///
+    /// Marks the snapshot as the most recently used one.
+    pub fn restore(&mut self, name: &str) -> Option<Tree<T, SIZE>> {
+        let position = self.position(name)?;
+        let snapshot = self.snapshots.remove(position);
+        let tree = snapshot.1.to_tree();
+        self.snapshots.push(snapshot);
+        Some(tree)
+    }
+
+    /// Removes snapshot saved under `name` and returns `true` if it was present.
+    pub fn remove(&mut self, name: &str) -> bool {
+        match self.position(name) {
+            Some(position) => {
+                self.snapshots.remove(position);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `true` if there is a snapshot saved under `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Returns an iterator over names of snapshots, from the least recently used one.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.snapshots.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns amount of snapshots.
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    /// Returns `true` if there are no snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    /// Returns amount of bytes taken by nodes of all snapshots, counting shared blocks once.
+    ///
+    /// The most recently saved snapshot is kept as a base of the next one even after it is
+    /// [removed](SnapshotStore::remove), so its blocks are always counted.
+    pub fn memory_usage(&self) -> usize {
+        let mut seen = HashSet::new();
+        self.snapshots
+            .iter()
+            .map(|(_, snapshot)| snapshot)
+            .chain(&self.latest)
+            .flat_map(|snapshot| snapshot.blocks())
+            .filter(|block| seen.insert(block.as_ptr()))
+            .map(|block| block.len() * std::mem::size_of::<Node<T>>())
+            .sum()
+    }
+
+    /// Returns position of snapshot saved under `name` in `snapshots`.
+    fn position(&self, name: &str) -> Option<usize> {
+        self.snapshots
+            .iter()
+            .position(|(snapshot, _)| snapshot == name)
+    }
+}
+
+#[cfg(test)]
+mod snapshot_store_tests {
+    use crate::implemented_tree_sizes::TREE_32;
+    use crate::{MicroGrid, Node, NodeIndex, Tree};
+
+    use super::SnapshotStore;
+
+    type TestTree = Tree<usize, TREE_32>;
+
+    #[test]
+    fn save_restore() {
+        let mut store = SnapshotStore::new(usize::MAX);
+        let mut tree = TestTree::new();
+        store.save("empty", &tree);
+        let full_size = store.memory_usage();
+        assert_eq!(full_size, TREE_32 * std::mem::size_of::<Node<usize>>());
+
+        tree.set(NodeIndex::new(0), Node::Filled(1));
+        store.save("first", &tree);
+        assert!(store.memory_usage() < full_size * 2);
+
+        tree.set(NodeIndex::new(0), Node::Filled(2));
+        assert_eq!(
+            store.restore("first").unwrap().get(NodeIndex::new(0)),
+            &Node::Filled(1)
+        );
+        assert_eq!(store.restore("empty"), Some(TestTree::new()));
+        assert_eq!(store.restore("missing"), None);
+        assert_eq!(store.names().collect::<Vec<_>>(), vec!["first", "empty"]);
+
+        store.save("first", &tree);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.restore("first"), Some(tree));
+        assert!(store.remove("empty"));
+        assert!(!store.contains("empty"));
+    }
+
+    #[test]
+    fn refinements() {
+        let mut store = SnapshotStore::new(usize::MAX);
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(0), Node::Filled(1));
+        tree.refine_leaf(NodeIndex::new(0), MicroGrid::new(1));
+        store.save("refined", &tree);
+        assert_eq!(store.restore("refined"), Some(tree));
+    }
+
+    #[test]
+    fn removed_latest() {
+        let mut store = SnapshotStore::new(usize::MAX);
+        let mut tree = TestTree::new();
+        store.save("a", &tree);
+        tree.set(NodeIndex::new(0), Node::Filled(1));
+        store.save("b", &tree);
+        let usage = store.memory_usage();
+
+        // `b` is still the base of the next snapshot, so its blocks stay in memory.
+        assert!(store.remove("b"));
+        assert_eq!(store.memory_usage(), usage);
+        assert!(store.remove("a"));
+        assert!(store.is_empty());
+        assert_eq!(
+            store.memory_usage(),
+            TREE_32 * std::mem::size_of::<Node<usize>>()
+        );
+    }
+
+    #[test]
+    fn eviction() {
+        let mut tree = TestTree::new();
+        let mut store = SnapshotStore::new(0);
+        store.save("a", &tree);
+        tree.set(NodeIndex::new(0), Node::Filled(1));
+        store.save("b", &tree);
+        assert_eq!(store.names().collect::<Vec<_>>(), vec!["b"]);
+
+        // Room for all nodes and two more blocks of 512 nodes.
+        let budget = (TREE_32 + 2 * 512) * std::mem::size_of::<Node<usize>>();
+        let mut store = SnapshotStore::new(budget);
+        store.save("a", &TestTree::new());
+        store.save("b", &tree);
+        store.restore("a");
+        // Every snapshot copies one block, `b` and `a` have to be evicted to fit `d`.
+        tree.set(NodeIndex::new(31), Node::Filled(2));
+        store.save("c", &tree);
+        tree.set(NodeIndex::new(31 * 32), Node::Filled(3));
+        store.save("d", &tree);
+        assert!(store.memory_usage() <= store.budget());
+        assert_eq!(store.names().collect::<Vec<_>>(), vec!["c", "d"]);
+    }
+}
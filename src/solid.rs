@@ -0,0 +1,382 @@
+use crate::{MicroGrid, Node, NodeIndex, Tree, TreeInterface};
+
+/// Octree storing only whether space is solid, with the same layout as [`Tree`].
+///
+/// Deeper layers are kept up to date on every change, a node is [`Filled`](Node::Filled) if the
+/// whole node is solid, [`Empty`](Node::Empty) if none of it is and [`Reduced`](Node::Reduced)
+/// otherwise, so integral quantities and CSG operations skip homogeneous subtrees.
+///
+/// Partially solid leaves are [`Reduced`](Node::Reduced) and [refined](SolidTree::refine) by
+/// a [`MicroGrid`], they are not solid for [`inside`](SolidTree::inside) and everything built
+/// on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SolidTree<const SIZE: usize> {
+    tree: Tree<(), SIZE>,
+}
+
+impl<const SIZE: usize> Default for SolidTree<SIZE>
+where
+    Tree<(), SIZE>: TreeInterface,
+{
+    fn default() -> Self {
+        Self { tree: Tree::new() }
+    }
+}
+
+impl<const SIZE: usize> From<SolidTree<SIZE>> for Tree<(), SIZE> {
+    fn from(value: SolidTree<SIZE>) -> Self {
+        value.tree
+    }
+}
+
+/// Returns summary of `children`.
+fn summarize(children: &[&Node<()>]) -> Node<()> {
+    if children
+        .iter()
+        .all(|child| matches!(child, Node::Filled(_)))
+    {
+        Node::Filled(())
+    } else if children.iter().all(|child| matches!(child, Node::Empty)) {
+        Node::Empty
+    } else {
+        Node::Reduced
+    }
+}
+
+impl<const SIZE: usize> SolidTree<SIZE>
+where
+    Tree<(), SIZE>: TreeInterface,
+{
+    /// Creates a new [`SolidTree`] with no solid space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns underlying [`Tree`].
+    pub fn as_tree(&self) -> &Tree<(), SIZE> {
+        &self.tree
+    }
+
+    /// Sets leaf on `position` to be `solid` and updates its ancestors.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn set<P>(&mut self, position: P, solid: bool)
+    where
+        P: Into<NodeIndex<Tree<(), SIZE>>>,
+    {
+        let grid = if solid {
+            MicroGrid::FULL
+        } else {
+            MicroGrid::EMPTY
+        };
+        self.refine(position, grid);
+    }
+
+    /// Sets only micro voxels filled in `grid` of the leaf on `position` to be solid
+    /// and updates its ancestors.
+    ///
+    /// Full and empty `grid` are the same as [`set`](SolidTree::set).
+    /// Expects `position` to be in depth 0.
+    pub fn refine<P>(&mut self, position: P, grid: MicroGrid)
+    where
+        P: Into<NodeIndex<Tree<(), SIZE>>>,
+    {
+        let index: NodeIndex<Tree<(), SIZE>> = position.into();
+        debug_assert_eq!(index.depth(), 0);
+        if !self.set_leaf(index, grid) {
+            return;
+        }
+        for ancestor in index.ancestors() {
+            // `Children` are always present for ancestors.
+            let children = self.tree.children(ancestor).unwrap();
+            let summary = summarize(&children.map(|child| self.tree.get(child)));
+            if self.tree.set(ancestor, summary.clone()) == summary {
+                break;
+            }
+        }
+    }
+
+    /// Returns `true` if the whole node on `position` is solid.
+    pub fn inside<P>(&self, position: P) -> bool
+    where
+        P: Into<NodeIndex<Tree<(), SIZE>>>,
+    {
+        matches!(self.tree.get(position), Node::Filled(_))
+    }
+
+    /// Returns solid micro voxels of the leaf on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn micro_grid<P>(&self, position: P) -> MicroGrid
+    where
+        P: Into<NodeIndex<Tree<(), SIZE>>>,
+    {
+        let index = position.into();
+        match self.tree.get(index) {
+            Node::Filled(_) => MicroGrid::FULL,
+            // Only partially solid leaves are reduced and they are always refined.
+            Node::Reduced => self.tree.refinement(index).unwrap(),
+            Node::Empty => MicroGrid::EMPTY,
+        }
+    }
+
+    /// Returns an iterator over [`indexes`](NodeIndex) of solid leaves which share a face
+    /// with space which is not solid or with the border of the tree.
+    pub fn boundary_leaves(&self) -> impl Iterator<Item = NodeIndex<Tree<(), SIZE>>> + '_ {
+        self.tree.surface().map(|(index, _)| index)
+    }
+
+    /// Returns amount of solid leaves, partially solid leaves are not counted.
+    pub fn volume(&self) -> usize {
+        let mut volume = 0;
+        let mut stack = vec![NodeIndex::<Tree<(), SIZE>>::new(SIZE - 1)];
+        while let Some(index) = stack.pop() {
+            match self.tree.get(index) {
+                Node::Filled(_) => volume += 1 << (3 * index.depth()),
+                Node::Reduced => stack.extend(self.tree.children(index).into_iter().flatten()),
+                Node::Empty => {}
+            }
+        }
+        volume
+    }
+
+    /// Returns amount of solid micro voxels, including those of partially solid leaves.
+    pub fn micro_volume(&self) -> usize {
+        let leaf_volume = MicroGrid::FULL.count() as usize;
+        let partial = self
+            .tree
+            .refinements
+            .values()
+            .map(|grid| grid.count() as usize);
+        self.volume() * leaf_volume + partial.sum::<usize>()
+    }
+
+    /// Returns amount of faces of solid leaves which do not touch another solid leaf.
+    pub fn surface_area(&self) -> usize {
+        self.boundary_leaves()
+            .map(|index| {
+                let covered = self
+                    .tree
+                    .neighbors6(index)
+                    .filter(|(_, neighbor)| self.inside(*neighbor))
+                    .count();
+                6 - covered
+            })
+            .sum()
+    }
+
+    /// Makes solid all space which is solid in `other`.
+    pub fn union(&mut self, other: &Self) {
+        self.combine(
+            other,
+            |this, other| match (this, other) {
+                (_, Node::Empty) | (Node::Filled(_), _) => Some(None),
+                (_, Node::Filled(_)) => Some(Some(true)),
+                _ => None,
+            },
+            |this, other| this | other,
+        );
+    }
+
+    /// Keeps solid only space which is solid in `other` as well.
+    pub fn intersection(&mut self, other: &Self) {
+        self.combine(
+            other,
+            |this, other| match (this, other) {
+                (_, Node::Filled(_)) | (Node::Empty, _) => Some(None),
+                (_, Node::Empty) => Some(Some(false)),
+                _ => None,
+            },
+            |this, other| this & other,
+        );
+    }
+
+    /// Removes all space which is solid in `other`.
+    pub fn difference(&mut self, other: &Self) {
+        self.combine(
+            other,
+            |this, other| match (this, other) {
+                (_, Node::Empty) | (Node::Empty, _) => Some(None),
+                (_, Node::Filled(_)) => Some(Some(false)),
+                _ => None,
+            },
+            |this, other| this & !other,
+        );
+    }
+
+    /// Walks both trees from the root and fills subtrees for which `rule` returns `Some(Some(_))`.
+    ///
+    /// `Some(None)` keeps the subtree as it is and [`None`] descends into children. Leaves
+    /// for which `rule` returns [`None`] get bits of both [`MicroGrid`]s combined by `micro`.
+    fn combine<F, M>(&mut self, other: &Self, rule: F, micro: M)
+    where
+        F: Fn(&Node<()>, &Node<()>) -> Option<Option<bool>>,
+        M: Fn(u64, u64) -> u64,
+    {
+        let mut stack = vec![NodeIndex::<Tree<(), SIZE>>::new(SIZE - 1)];
+        while let Some(index) = stack.pop() {
+            match rule(self.tree.get(index), other.tree.get(index)) {
+                Some(Some(solid)) => self.fill(index, solid),
+                Some(None) => {}
+                None => match self.tree.children(index) {
+                    Some(children) => stack.extend(children),
+                    None => {
+                        let bits = micro(
+                            self.micro_grid(index).bits(),
+                            other.micro_grid(index).bits(),
+                        );
+                        self.set_leaf(index, MicroGrid::new(bits));
+                    }
+                },
+            }
+        }
+        self.tree.build(summarize);
+    }
+
+    /// Sets node on `index` and all its descendants to be `solid`.
+    fn fill(&mut self, index: NodeIndex<Tree<(), SIZE>>, solid: bool) {
+        let mut stack = vec![index];
+        while let Some(index) = stack.pop() {
+            let node = if solid { Node::Filled(()) } else { Node::Empty };
+            self.tree.set(index, node);
+            match self.tree.children(index) {
+                Some(children) => stack.extend(children),
+                None => {
+                    self.tree.unrefine_leaf(index);
+                }
+            }
+        }
+    }
+
+    /// Sets leaf on `index` to hold `grid` without updating its ancestors and returns `true`
+    /// if the leaf node changed.
+    fn set_leaf(&mut self, index: NodeIndex<Tree<(), SIZE>>, grid: MicroGrid) -> bool {
+        let node = match grid {
+            MicroGrid::FULL => Node::Filled(()),
+            MicroGrid::EMPTY => Node::Empty,
+            _ => Node::Reduced,
+        };
+        if node == Node::Reduced {
+            self.tree.refine_leaf(index, grid);
+        } else {
+            self.tree.unrefine_leaf(index);
+        }
+        self.tree.set(index, node.clone()) != node
+    }
+}
+
+#[cfg(test)]
+mod solid_tree_tests {
+    use crate::{LayerPosition, MicroGrid, NodeIndex};
+
+    use super::SolidTree;
+
+    type TestTree = SolidTree<73>;
+
+    /// Returns a tree with solid box with corners in `start` and `end`, both inclusive.
+    fn solid_box(start: [usize; 3], end: [usize; 3]) -> TestTree {
+        let mut tree = TestTree::new();
+        for z in start[2]..=end[2] {
+            for y in start[1]..=end[1] {
+                for x in start[0]..=end[0] {
+                    tree.set(LayerPosition::new(x, y, z, 0), true);
+                }
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn set() {
+        let mut tree = solid_box([0, 0, 0], [1, 1, 1]);
+        assert!(tree.inside(LayerPosition::new(0, 0, 0, 1)));
+        assert!(!tree.inside(NodeIndex::new(72)));
+        assert_eq!(tree.volume(), 8);
+        assert_eq!(tree.surface_area(), 24);
+        assert_eq!(tree.boundary_leaves().count(), 8);
+
+        tree.set(LayerPosition::new(1, 1, 1, 0), false);
+        assert!(!tree.inside(LayerPosition::new(0, 0, 0, 1)));
+        assert_eq!(tree.volume(), 7);
+        assert_eq!(tree.surface_area(), 24);
+    }
+
+    #[test]
+    fn csg() {
+        let full = solid_box([0, 0, 0], [3, 3, 3]);
+        assert_eq!(full.volume(), 64);
+        assert_eq!(full.surface_area(), 96);
+        assert_eq!(full.boundary_leaves().count(), 56);
+
+        let mut tree = solid_box([0, 0, 0], [1, 1, 1]);
+        tree.union(&solid_box([3, 3, 3], [3, 3, 3]));
+        assert_eq!(tree.volume(), 9);
+        assert_eq!(tree.surface_area(), 30);
+
+        let mut intersection = tree.clone();
+        intersection.intersection(&solid_box([1, 0, 0], [3, 3, 3]));
+        assert_eq!(intersection.volume(), 5);
+        assert_eq!(intersection, {
+            let mut expected = solid_box([1, 0, 0], [1, 1, 1]);
+            expected.set(LayerPosition::new(3, 3, 3, 0), true);
+            expected
+        });
+
+        let mut difference = full.clone();
+        difference.difference(&tree);
+        assert_eq!(difference.volume(), 55);
+        difference.union(&tree);
+        assert_eq!(difference, full);
+    }
+
+    #[test]
+    fn refine() {
+        let mut tree = solid_box([0, 0, 0], [1, 1, 1]);
+        let slab = MicroGrid::new(0xffff);
+        tree.refine(LayerPosition::new(1, 1, 1, 0), slab);
+        assert!(!tree.inside(LayerPosition::new(1, 1, 1, 0)));
+        assert!(!tree.inside(LayerPosition::new(0, 0, 0, 1)));
+        assert_eq!(tree.micro_grid(LayerPosition::new(1, 1, 1, 0)), slab);
+        assert_eq!(tree.volume(), 7);
+        assert_eq!(tree.micro_volume(), 7 * 64 + 16);
+        // The partially solid leaf is not solid itself, its neighbours lose a covered face.
+        assert_eq!(tree.boundary_leaves().count(), 7);
+        assert_eq!(tree.surface_area(), 24);
+
+        tree.refine(LayerPosition::new(1, 1, 1, 0), MicroGrid::FULL);
+        assert_eq!(tree, solid_box([0, 0, 0], [1, 1, 1]));
+    }
+
+    #[test]
+    fn csg_refinements() {
+        let mut tree = TestTree::new();
+        tree.refine(LayerPosition::new(0, 0, 0, 0), MicroGrid::new(0b0011));
+        let mut other = TestTree::new();
+        other.refine(LayerPosition::new(0, 0, 0, 0), MicroGrid::new(0b0110));
+
+        let mut union = tree.clone();
+        union.union(&other);
+        assert_eq!(
+            union.micro_grid(LayerPosition::new(0, 0, 0, 0)),
+            MicroGrid::new(0b0111)
+        );
+        let mut intersection = tree.clone();
+        intersection.intersection(&other);
+        assert_eq!(
+            intersection.micro_grid(LayerPosition::new(0, 0, 0, 0)),
+            MicroGrid::new(0b0010)
+        );
+        let mut difference = tree.clone();
+        difference.difference(&other);
+        assert_eq!(
+            difference.micro_grid(LayerPosition::new(0, 0, 0, 0)),
+            MicroGrid::new(0b0001)
+        );
+
+        difference.difference(&solid_box([0, 0, 0], [0, 0, 0]));
+        assert_eq!(difference, TestTree::new());
+        let mut full = solid_box([0, 0, 0], [0, 0, 0]);
+        full.intersection(&tree);
+        assert_eq!(full, tree);
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Debug;
+use std::ops::Range;
+
+use crate::{MicroGrid, Node, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Greedily merges [`Filled`](Node::Filled) nodes of the shallowest layer into axis-aligned
+    /// boxes and returns at most `max_boxes` of them, ordered from the biggest by volume.
+    ///
+    /// Every box holds ranges of `x`, `y` and `z` it covers. Boxes are grown from the first
+    /// uncovered node along `x` first, then `y` and `z`, so they never overlap.
+    ///
+    /// When there are more boxes than `max_boxes`, the smallest box is repeatedly merged into
+    /// the box whose bounding box grows the least. Merged boxes are conservative, they may
+    /// overlap and cover nodes which are not filled, but no filled node is ever left out.
+    /// `max_boxes` of 0 is treated as 1.
+    ///
+    /// Leaves [refined](Tree::refine_leaf) by an empty [`MicroGrid`] hold nothing and are skipped,
+    /// leaves refined by a partially filled one are covered whole.
+    pub fn solid_boxes(&self, max_boxes: usize) -> Vec<[Range<usize>; 3]> {
+        let size = Self::LEAF_ROW_LEN;
+        let [xs, ys, zs] = Self::leaf_extent();
+        let row_size = Self::row_size(0);
+        let mut covered = vec![false; Self::LEAF_LAYER_LEN];
+        let is_free = |covered: &[bool], index: usize| {
+            !covered[index]
+                && matches!(self.stored[index], Node::Filled(_))
+                && self.refinements.get(&index) != Some(&MicroGrid::EMPTY)
+        };
+
+        let mut boxes = Vec::new();
+        for z in zs {
+            for y in ys.clone() {
+                for x in xs.clone() {
+                    let start = row_size.stride(y, z) + x;
+                    if !is_free(&covered, start) {
+                        continue;
+                    }
+
+                    let mut end_x = x + 1;
+                    while end_x < size && is_free(&covered, start + end_x - x) {
+                        end_x += 1;
+                    }
+                    let row_free = |covered: &[bool], y: usize, z: usize| {
+                        let row = row_size.stride(y, z);
+                        (x..end_x).all(|x| is_free(covered, row + x))
+                    };
+                    let mut end_y = y + 1;
+                    while end_y < size && row_free(&covered, end_y, z) {
+                        end_y += 1;
+                    }
+                    let mut end_z = z + 1;
+                    while end_z < size && (y..end_y).all(|y| row_free(&covered, y, end_z)) {
+                        end_z += 1;
+                    }
+
+                    for z in z..end_z {
+                        for y in y..end_y {
+                            let row = row_size.stride(y, z);
+                            covered[row + x..row + end_x].fill(true);
+                        }
+                    }
+                    boxes.push([x..end_x, y..end_y, z..end_z]);
+                }
+            }
+        }
+
+        boxes.sort_by_key(|solid_box| std::cmp::Reverse(volume(solid_box)));
+        while boxes.len() > max_boxes.max(1) {
+            // `unwrap` will never fail as there are at least two boxes.
+            let smallest = boxes.pop().unwrap();
+            let (target, _) = boxes
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, solid_box)| {
+                    volume(&bounding_box(solid_box, &smallest)) - volume(solid_box)
+                })
+                .unwrap();
+            boxes[target] = bounding_box(&boxes[target], &smallest);
+            boxes.sort_by_key(|solid_box| std::cmp::Reverse(volume(solid_box)));
+        }
+        boxes
+    }
+}
+
+/// Returns amount of nodes in `solid_box`.
+fn volume(solid_box: &[Range<usize>; 3]) -> usize {
+    solid_box.iter().map(Range::len).product()
+}
+
+/// Returns the smallest box containing both `a` and `b`.
+fn bounding_box(a: &[Range<usize>; 3], b: &[Range<usize>; 3]) -> [Range<usize>; 3] {
+    [0, 1, 2].map(|axis| a[axis].start.min(b[axis].start)..a[axis].end.max(b[axis].end))
+}
+
+#[cfg(test)]
+mod solid_boxes_tests {
+    use crate::{LayerPosition, MicroGrid, Node, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn solid_boxes() {
+        let mut tree = TestTree::new();
+        assert!(tree.solid_boxes(10).is_empty());
+
+        for z in 0..2 {
+            for y in 0..4 {
+                for x in 1..3 {
+                    tree.set(LayerPosition::new(x, y, z, 0), Node::Filled(1));
+                }
+            }
+        }
+        tree.set(LayerPosition::new(3, 3, 3, 0), Node::Filled(1));
+        tree.set(LayerPosition::new(0, 0, 3, 0), Node::Reduced);
+
+        assert_eq!(
+            tree.solid_boxes(10),
+            vec![[1..3, 0..4, 0..2], [3..4, 3..4, 3..4]]
+        );
+        // Boxes over the budget are merged instead of dropped.
+        assert_eq!(tree.solid_boxes(1), vec![[1..4, 0..4, 0..4]]);
+        assert_eq!(tree.solid_boxes(0), tree.solid_boxes(1));
+    }
+
+    #[test]
+    fn solid_boxes_merged() {
+        let mut tree = TestTree::new();
+        for (x, y, z) in [(0, 0, 0), (3, 3, 3), (3, 2, 3), (0, 3, 0)] {
+            tree.set(LayerPosition::new(x, y, z, 0), Node::Filled(1));
+        }
+
+        let boxes = tree.solid_boxes(2);
+        assert_eq!(boxes.len(), 2);
+        for (x, y, z) in [(0, 0, 0), (3, 3, 3), (3, 2, 3), (0, 3, 0)] {
+            assert!(boxes
+                .iter()
+                .any(|[xs, ys, zs]| { xs.contains(&x) && ys.contains(&y) && zs.contains(&z) }));
+        }
+    }
+
+    #[test]
+    fn solid_boxes_disjoint() {
+        let mut tree = TestTree::new();
+        for (x, y, z) in [(0, 0, 0), (1, 0, 0), (0, 1, 0), (0, 0, 1), (1, 0, 1)] {
+            tree.set(LayerPosition::new(x, y, z, 0), Node::Filled(1));
+        }
+
+        let boxes = tree.solid_boxes(10);
+        assert_eq!(boxes[0], [0..2, 0..1, 0..2]);
+        assert_eq!(
+            boxes
+                .iter()
+                .map(|solid_box| solid_box.iter().map(|range| range.len()).product::<usize>())
+                .sum::<usize>(),
+            5
+        );
+    }
+
+    #[test]
+    fn solid_boxes_refinements() {
+        let mut tree = TestTree::new();
+        for x in 0..3 {
+            tree.set(LayerPosition::new(x, 0, 0, 0), Node::Filled(1));
+        }
+        tree.refine_leaf(LayerPosition::new(1, 0, 0, 0), MicroGrid::new(1));
+        assert_eq!(tree.solid_boxes(10), vec![[0..3, 0..1, 0..1]]);
+
+        tree.refine_leaf(LayerPosition::new(1, 0, 0, 0), MicroGrid::EMPTY);
+        assert_eq!(
+            tree.solid_boxes(10),
+            vec![[0..1, 0..1, 0..1], [2..3, 0..1, 0..1]]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Debug;
+
+use crate::{LayerPosition, Node, Tree, TreeInterface};
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Creates a new [`Tree`] from `leaves` of the shallowest layer in the order in which they
+    /// are stored and builds it the same way as [`build`](Tree::build) with `combine_rule`.
+    ///
+    /// Parrents are combined as soon as all their children are read, so besides the tree itself
+    /// no other layer is held in memory. Missing leaves are [`Empty`](Node::Empty) and leaves
+    /// above the [layer size](TreeInterface::LEAF_LAYER_LEN) are ignored.
+    ///
+    /// Returned tree has [face coverage](Tree::enable_face_coverage) disabled, so there is
+    /// no coverage which could be stale.
+    pub fn build_streaming<I, F>(leaves: I, combine_rule: F) -> Self
+    where
+        I: IntoIterator<Item = Node<T>>,
+        F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+    {
+        let nodes: Vec<Node<T>> = (0..SIZE).map(|_| Node::Empty).collect();
+        // `unwrap` will never fail as size of `nodes` is guaranteed to be `SIZE`.
+        let mut tree = Self::from_nodes(nodes.try_into().unwrap());
+
+        let plane_size = Self::row_size(0).plane();
+        let mut leaves = leaves.into_iter();
+        for index in 0..Self::LEAF_LAYER_LEN {
+            if let Some(leaf) = leaves.next() {
+                tree.stored[index] = leaf;
+            }
+            let z = index / plane_size;
+            if (index + 1) % plane_size == 0 && z % 2 == 1 {
+                tree.build_plane(1, z / 2, combine_rule);
+            }
+        }
+        tree
+    }
+
+    /// Combines all parrents in plane `z` of layer in `depth` and continues with a plane
+    /// of the layer below if this one completed it.
+    fn build_plane<F>(&mut self, depth: usize, z: usize, combine_rule: F)
+    where
+        F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
+    {
+        let row_size = Self::row_size(depth).get();
+        for y in 0..row_size {
+            for x in 0..row_size {
+                let position = LayerPosition::new(x, y, z, depth);
+                // Only layers with children are built, so this never panics.
+                let children = self.children(position).unwrap();
+                let node = combine_rule(&children.map(|index| self.get(index)));
+                self.set(position, node);
+            }
+        }
+        if depth < Self::MAX_DEPTH_INDEX && z % 2 == 1 {
+            self.build_plane(depth + 1, z / 2, combine_rule);
+        }
+    }
+}
+
+#[cfg(test)]
+mod streaming_tests {
+    use crate::{Node, NodeIndex, NodesRaw, Tree, TreeInterface};
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+            Node::Empty
+        } else if nodes.iter().all(|node| matches!(node, Node::Filled(_))) {
+            Node::Filled(nodes.iter().map(|node| node.value_or_default()).sum())
+        } else {
+            Node::Reduced
+        }
+    }
+
+    fn built<const SIZE: usize>(count: usize) -> Tree<usize, SIZE>
+    where
+        Tree<usize, SIZE>: TreeInterface,
+    {
+        let mut nodes = NodesRaw::new();
+        for leaf in leaves(count) {
+            nodes.push(leaf);
+        }
+        let mut tree = Tree::from(nodes);
+        tree.build(combine_rule);
+        tree
+    }
+
+    fn leaves(count: usize) -> impl Iterator<Item = Node<usize>> {
+        (0..count).map(|i| {
+            if i % 3 == 0 || i >= 256 {
+                Node::Filled(i)
+            } else {
+                Node::Empty
+            }
+        })
+    }
+
+    #[test]
+    fn build_streaming() {
+        let tree = Tree::<usize, 4681>::build_streaming(leaves(4096), combine_rule);
+        assert_eq!(tree, built(4096));
+        assert_eq!(tree.get(NodeIndex::new(4680)), &Node::Reduced);
+    }
+
+    #[test]
+    fn build_streaming_short() {
+        let tree = Tree::<usize, 73>::build_streaming(leaves(5), combine_rule);
+        assert_eq!(tree, built(5));
+
+        let tree = Tree::<usize, 1>::build_streaming(leaves(2), combine_rule);
+        assert_eq!(tree.get(NodeIndex::new(0)), &Node::Filled(0));
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+
+use crate::{Axis, NodeIndex, NodePosition, Tree, TreeError, TreeInterface};
+
+/// Mapping of axes of one coordinate convention to another, used by [`Tree::swizzled`]
+/// and [`NodePosition::swizzle`].
+///
+/// Every axis of the result takes coordinate of one source axis, which can be flipped.
+/// Every source axis is used exactly once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisMapping {
+    /// Source axis of `x`, `y` and `z` of the result.
+    sources: [Axis; 3],
+    /// Whether coordinate on `x`, `y` and `z` of the result is flipped.
+    flips: [bool; 3],
+}
+
+impl AxisMapping {
+    /// Mapping which keeps all coordinates unchanged.
+    pub const IDENTITY: Self = Self {
+        sources: [Axis::X, Axis::Y, Axis::Z],
+        flips: [false; 3],
+    };
+
+    /// Mapping from a convention with `y` going up to one with `z` going up,
+    /// keeping handedness.
+    pub const Y_UP_TO_Z_UP: Self = Self {
+        sources: [Axis::X, Axis::Z, Axis::Y],
+        flips: [false, true, false],
+    };
+
+    /// Mapping from a convention with `z` going up to one with `y` going up,
+    /// keeping handedness. Inverse of [`Y_UP_TO_Z_UP`](AxisMapping::Y_UP_TO_Z_UP).
+    pub const Z_UP_TO_Y_UP: Self = Self {
+        sources: [Axis::X, Axis::Z, Axis::Y],
+        flips: [false, false, true],
+    };
+
+    /// Creates a new [`AxisMapping`] taking `x`, `y` and `z` of the result from `sources`,
+    /// flipped where `flips` is `true`.
+    ///
+    /// Returns [`TreeError::InvalidAxisMapping`] if any axis is not used exactly once.
+    pub fn new(sources: [Axis; 3], flips: [bool; 3]) -> Result<Self, TreeError> {
+        for axis in [Axis::X, Axis::Y, Axis::Z] {
+            if !sources.contains(&axis) {
+                return Err(TreeError::InvalidAxisMapping);
+            }
+        }
+        Ok(Self { sources, flips })
+    }
+
+    /// Returns mapping which reverts this one.
+    pub fn inverse(self) -> Self {
+        let mut inverse = Self::IDENTITY;
+        for (target, (source, flip)) in self.sources.iter().zip(self.flips).enumerate() {
+            let source = *source as usize;
+            inverse.sources[source] = [Axis::X, Axis::Y, Axis::Z][target];
+            inverse.flips[source] = flip;
+        }
+        inverse
+    }
+
+    /// Returns `true` if the mapping changes handedness of the coordinate system.
+    pub fn is_mirroring(self) -> bool {
+        // Swap of two axes and a single flip both mirror, a rotation of all three does not.
+        let swaps = (0..3)
+            .filter(|&axis| self.sources[axis] as usize != axis)
+            .count();
+        let odd_permutation = swaps == 2;
+        let odd_flips = self.flips.iter().filter(|flip| **flip).count() % 2 == 1;
+        odd_permutation != odd_flips
+    }
+
+    /// Returns coordinates mapped from `[x, y, z]` of a node which covers `extent` nodes
+    /// of a layer with `row_size` on every axis.
+    pub(crate) fn map(self, coordinates: [usize; 3], extent: usize, row_size: usize) -> [usize; 3] {
+        [0, 1, 2].map(|axis| {
+            let coordinate = coordinates[self.sources[axis] as usize];
+            if self.flips[axis] {
+                row_size - coordinate - extent
+            } else {
+                coordinate
+            }
+        })
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug + Clone,
+{
+    /// Returns a new [`Tree`] with all layers remapped by `mapping`, so node on a position
+    /// ends up on the position [swizzled](NodePosition::swizzle) by `mapping`.
+    ///
+    /// Every layer is remapped the same way, [refinements](Tree::refine_leaf) are swizzled
+    /// together with their leaves. Built upper layers stay consistent only if the rule
+    /// does not depend on order of children, otherwise the tree has to be built again.
+    pub fn swizzled(&self, mapping: AxisMapping) -> Self {
+        let mut tree = Self::new();
+        for (index, node) in self.stored.iter().enumerate() {
+            let position = NodePosition::<Self>::from(NodeIndex::new(index)).swizzle(mapping);
+            tree.stored[NodeIndex::from(position)] = node.clone();
+        }
+        for (index, grid) in &self.refinements {
+            let position = NodePosition::<Self>::from(NodeIndex::new(*index)).swizzle(mapping);
+            tree.refinements
+                .insert(NodeIndex::from(position).raw(), grid.swizzle(mapping));
+        }
+        tree
+    }
+}
+
+#[cfg(test)]
+mod swizzle_tests {
+    use crate::{Axis, MicroGrid, Node, NodePosition, Tree, TreeError};
+
+    use super::AxisMapping;
+
+    type TestTree = Tree<usize, 73>;
+
+    fn combine_rule(nodes: &[&Node<usize>]) -> Node<usize> {
+        if nodes.iter().all(|node| matches!(node, Node::Empty)) {
+            Node::Empty
+        } else {
+            Node::Reduced
+        }
+    }
+
+    #[test]
+    fn axis_mapping() {
+        assert_eq!(
+            AxisMapping::new([Axis::X, Axis::X, Axis::Z], [false; 3]),
+            Err(TreeError::InvalidAxisMapping)
+        );
+        let rotation = AxisMapping::new([Axis::Y, Axis::Z, Axis::X], [false; 3]).unwrap();
+        assert!(!rotation.is_mirroring());
+        assert!(AxisMapping::new([Axis::Y, Axis::X, Axis::Z], [false; 3])
+            .unwrap()
+            .is_mirroring());
+        assert!(!AxisMapping::Y_UP_TO_Z_UP.is_mirroring());
+        assert_eq!(
+            AxisMapping::Y_UP_TO_Z_UP.inverse(),
+            AxisMapping::Z_UP_TO_Y_UP
+        );
+        assert_eq!(rotation.inverse().inverse(), rotation);
+    }
+
+    #[test]
+    fn swizzle() {
+        let position = NodePosition::<TestTree>::new(1, 2, 3, 0);
+        assert_eq!(
+            position.swizzle(AxisMapping::Y_UP_TO_Z_UP),
+            NodePosition::new(1, 0, 2, 0)
+        );
+        assert_eq!(
+            position
+                .swizzle(AxisMapping::Y_UP_TO_Z_UP)
+                .swizzle(AxisMapping::Z_UP_TO_Y_UP),
+            position
+        );
+        assert_eq!(
+            NodePosition::<TestTree>::new(0, 2, 0, 1).swizzle(AxisMapping::Y_UP_TO_Z_UP),
+            NodePosition::new(0, 2, 2, 1)
+        );
+    }
+
+    #[test]
+    fn swizzled() {
+        let mut tree = TestTree::new();
+        tree.set(NodePosition::new(1, 2, 3, 0), Node::Filled(1));
+        tree.set(NodePosition::new(3, 0, 0, 0), Node::Filled(2));
+        tree.build(combine_rule);
+
+        let swizzled = tree.swizzled(AxisMapping::Y_UP_TO_Z_UP);
+        assert_eq!(
+            swizzled.get(NodePosition::new(1, 0, 2, 0)),
+            &Node::Filled(1)
+        );
+        assert_eq!(
+            swizzled.get(NodePosition::new(3, 3, 0, 0)),
+            &Node::Filled(2)
+        );
+        let mut built = swizzled.clone();
+        built.build(combine_rule);
+        assert_eq!(swizzled, built);
+        assert_eq!(swizzled.swizzled(AxisMapping::Z_UP_TO_Y_UP), tree);
+    }
+
+    #[test]
+    fn swizzled_refinements() {
+        let mut tree = TestTree::new();
+        tree.set(NodePosition::new(1, 2, 3, 0), Node::Filled(1));
+        let mut grid = MicroGrid::EMPTY;
+        grid.set(0, 3, 1, true);
+        tree.refine_leaf(NodePosition::new(1, 2, 3, 0), grid);
+
+        let swizzled = tree.swizzled(AxisMapping::Y_UP_TO_Z_UP);
+        let mut expected = MicroGrid::EMPTY;
+        expected.set(0, 2, 3, true);
+        assert_eq!(
+            swizzled.refinement(NodePosition::new(1, 0, 2, 0)),
+            Some(expected)
+        );
+        assert_eq!(swizzled.refinement(NodePosition::new(1, 2, 3, 0)), None);
+        assert_eq!(swizzled.swizzled(AxisMapping::Z_UP_TO_Y_UP), tree);
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use crate::{Node, NodeIndex, Tree, TreeInterface};
+
+/// Records which nodes were cleared and in which generation.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Tombstones {
+    /// Generation of the last recorded removal.
+    generation: u64,
+    /// Raw node index mapped to generation in which it was cleared.
+    removed: BTreeMap<usize, u64>,
+}
+
+impl Tombstones {
+    /// Updates tombstones after node on `index` changed from `previous` to `current`.
+    pub(crate) fn record<T>(&mut self, index: usize, previous: &Node<T>, current: &Node<T>) {
+        match (previous, current) {
+            (Node::Empty, Node::Empty) => {}
+            (_, Node::Empty) => {
+                self.generation += 1;
+                self.removed.insert(index, self.generation);
+            }
+            (_, _) => {
+                self.removed.remove(&index);
+            }
+        }
+    }
+
+    /// Returns generation in which node on `index` was cleared, [`None`] if it has no tombstone,
+    /// together with generation of the last recorded removal.
+    pub(crate) fn state(&self, index: usize) -> (Option<u64>, u64) {
+        (self.removed.get(&index).copied(), self.generation)
+    }
+
+    /// Restores state of node on `index` and the last generation to what they were before
+    /// a change was recorded, without recording a new removal.
+    pub(crate) fn restore(&mut self, index: usize, removed: Option<u64>, generation: u64) {
+        match removed {
+            Some(removed) => self.removed.insert(index, removed),
+            None => self.removed.remove(&index),
+        };
+        self.generation = generation;
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Starts recording tombstones, i.e. every time [`set`](Tree::set) replaces a node which is not
+    /// [`Empty`](Node::Empty) with [`Empty`](Node::Empty) node, its removal is recorded together
+    /// with a new generation. Setting a non-empty node again forgets its tombstone.
+    ///
+    /// Changes made through [`get_mut`](Tree::get_mut) or iterators are not recorded.
+    /// Does nothing if tombstones are already recorded.
+    pub fn enable_tombstones(&mut self) {
+        if self.tombstones.is_none() {
+            self.tombstones = Some(Box::default());
+        }
+    }
+
+    /// Stops recording tombstones and forgets all recorded ones.
+    pub fn disable_tombstones(&mut self) {
+        self.tombstones = None;
+    }
+
+    /// Returns generation of the last recorded removal,
+    /// or [`None`] if tombstones are not [enabled](Tree::enable_tombstones).
+    pub fn generation(&self) -> Option<u64> {
+        self.tombstones
+            .as_ref()
+            .map(|tombstones| tombstones.generation)
+    }
+
+    /// Sets node on `position` to [`Empty`](Node::Empty) and returns a [`Node`]
+    /// previously stored on `position`.
+    pub fn clear<P>(&mut self, position: P) -> Node<T>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        self.set(position, Node::Empty)
+    }
+
+    /// Returns [`indexes`](NodeIndex) of nodes which were cleared after `generation` and were not
+    /// filled again since, ordered by index.
+    ///
+    /// Returns an empty [`Vec`] if tombstones are not [enabled](Tree::enable_tombstones).
+    pub fn removed_since(&self, generation: u64) -> Vec<NodeIndex<Self>> {
+        self.tombstones
+            .iter()
+            .flat_map(|tombstones| tombstones.removed.iter())
+            .filter(|(_, removed)| **removed > generation)
+            .map(|(index, _)| NodeIndex::new(*index))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tombstone_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn removed_since() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(1), Node::Filled(1));
+        tree.clear(NodeIndex::new(1));
+        assert_eq!(tree.generation(), None);
+        assert!(tree.removed_since(0).is_empty());
+
+        tree.enable_tombstones();
+        tree.set(NodeIndex::new(1), Node::Filled(1));
+        tree.set(NodeIndex::new(2), Node::Filled(2));
+        tree.set(NodeIndex::new(3), Node::Filled(3));
+        assert_eq!(tree.generation(), Some(0));
+
+        // Clearing node which never existed is not a removal.
+        tree.clear(NodeIndex::new(4));
+        assert_eq!(tree.generation(), Some(0));
+
+        tree.clear(NodeIndex::new(2));
+        tree.clear(NodeIndex::new(1));
+        assert_eq!(tree.generation(), Some(2));
+        assert_eq!(
+            tree.removed_since(0),
+            vec![NodeIndex::new(1), NodeIndex::new(2)]
+        );
+        assert_eq!(tree.removed_since(1), vec![NodeIndex::new(1)]);
+
+        tree.set(NodeIndex::new(1), Node::Reduced);
+        assert_eq!(tree.removed_since(0), vec![NodeIndex::new(2)]);
+
+        tree.disable_tombstones();
+        assert!(tree.removed_since(0).is_empty());
+    }
+
+    #[test]
+    fn eq_ignores_tombstones() {
+        let mut tree = TestTree::new();
+        tree.enable_tombstones();
+        tree.set(NodeIndex::new(1), Node::Filled(1));
+        tree.clear(NodeIndex::new(1));
+        assert_eq!(tree, TestTree::new());
+    }
+}
@@ -1,18 +1,56 @@
-use std::{fmt::Debug, ops::Range};
-
-use crate::{LayerPosition, Node, NodeIndex, NodePosition, NodesRaw};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::ops::{Index, IndexMut, Range};
+
+use crate::face_coverage::FaceCoverages;
+use crate::layer_hash::LayerHashes;
+use crate::tombstone::Tombstones;
+use crate::value_filter::ValueFilters;
+
+use crate::{
+    Ancestors, Axis, Bfs, Children, CombineContext, CoordinateError, Depth, DescendFilled,
+    DrainFilled, EmptyOctants, EnumeratePositions, FaceNeighbors, Filled, FilledMut, ImportIssue,
+    IntoIter, Iter, IterMut, LayerIter, LayerPairs, LayerPosition, LayerSize, LeafMorton,
+    MicroGrid, Neighbors, Node, NodeIndex, NodePosition, NodesRaw, OctantGroups, PositionRange,
+    Reducer, Region, Row, RowSize, Shell, Siblings, Surface, TreeError, TryIntoIndex,
+};
 
 /// Stores data in **non**-sparse octree.
 ///
 /// This storage type allows to use benefits of linear storage as is fast insert
 /// and also provides advantages of spatial datastructure for cost of memory efficiency.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Tree<T, const SIZE: usize> {
     /// Stored data are in [boxed](Box) `array` as for bigger data sets stack would be insufficient.
     ///
     /// Constant sized `array` allows for constant modification speed and also signifies that size of
     /// this data will not change.
-    stored: Box<[Node<T>; SIZE]>,
+    pub(crate) stored: Box<[Node<T>; SIZE]>,
+    /// Removals recorded when [tombstones are enabled](Tree::enable_tombstones).
+    pub(crate) tombstones: Option<Box<Tombstones>>,
+    /// Hashes of layers kept when [enabled](Tree::enable_layer_hashes).
+    pub(crate) hashes: Option<Box<LayerHashes<T>>>,
+    /// Filters of values of leaves kept when [enabled](Tree::enable_value_filters).
+    pub(crate) value_filters: Option<Box<ValueFilters<T>>>,
+    /// Coverage of faces of parrents computed by build when [enabled](Tree::enable_face_coverage).
+    pub(crate) face_coverage: Option<Box<FaceCoverages>>,
+    /// Raw indexes of [refined](Tree::refine_leaf) leaves mapped to their [`MicroGrid`].
+    pub(crate) refinements: BTreeMap<usize, MicroGrid>,
+}
+
+/// [`PartialEq`] is implemented manually, so only stored nodes and their
+/// [refinements](Tree::refine_leaf) are compared and recorded tombstones are ignored. Differing [layer hashes](Tree::enable_layer_hashes)
+/// short-circuit the comparison.
+impl<T, const SIZE: usize> PartialEq for Tree<T, SIZE>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        !self.layer_hashes_differ(other)
+            && self.stored == other.stored
+            && self.refinements == other.refinements
+    }
 }
 
 impl<T, const SIZE: usize> Default for Tree<T, SIZE>
@@ -23,6 +61,11 @@ where
         Self {
             // `unwrap` will never fail as size of `vec` is guaranteed to be `SIZE`.
             stored: vec![Node::Empty; SIZE].try_into().unwrap(),
+            tombstones: None,
+            hashes: None,
+            value_filters: None,
+            face_coverage: None,
+            refinements: BTreeMap::new(),
         }
     }
 }
@@ -43,6 +86,69 @@ where
     }
 }
 
+/// Consumes the [Tree] and yields its [`nodes`](Node) in the order in which they are stored.
+impl<T, const SIZE: usize> IntoIterator for Tree<T, SIZE> {
+    type Item = Node<T>;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.stored)
+    }
+}
+
+impl<'a, T, const SIZE: usize> IntoIterator for &'a Tree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a Node<T>);
+    type IntoIter = Iter<'a, T, SIZE>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const SIZE: usize> IntoIterator for &'a mut Tree<T, SIZE>
+where
+    Tree<T, SIZE>: TreeInterface,
+    T: Debug,
+{
+    type Item = (NodeIndex<Tree<T, SIZE>>, &'a mut Node<T>);
+    type IntoIter = IterMut<'a, T, SIZE>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// [Tree] can be indexed by anything convertible into [NodeIndex], e.g. `tree[LayerPosition::new(3, 1, 2, 0)]`,
+/// the same way as [`get`](Tree::get). Position is expected to be always valid.
+impl<T, const SIZE: usize, P> Index<P> for Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+    P: Into<NodeIndex<Self>>,
+{
+    type Output = Node<T>;
+
+    fn index(&self, position: P) -> &Self::Output {
+        self.get(position)
+    }
+}
+
+/// Mutable indexing behaves the same way as [`get_mut`](Tree::get_mut).
+impl<T, const SIZE: usize, P> IndexMut<P> for Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+    P: Into<NodeIndex<Self>>,
+{
+    fn index_mut(&mut self, position: P) -> &mut Self::Output {
+        self.get_mut(position)
+    }
+}
+
 /// Amount of stored elements in [Tree] with biggest row size of 128.  
 pub const TREE_128: usize = 128 * 128 * 128
     + 64 * 64 * 64
@@ -77,130 +183,107 @@ impl<T> TreeInterface for Tree<T, TREE_128> {
     const SIZE: usize = TREE_128;
     const BIGGEST_ROW_SIZE: usize = 128;
     const DEPTH: usize = 8;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![128, 64, 32, 16, 8, 4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![2097152, 262144, 32768, 4096, 512, 64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_64> {
     const SIZE: usize = TREE_64;
     const BIGGEST_ROW_SIZE: usize = 64;
     const DEPTH: usize = 7;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![64, 32, 16, 8, 4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![262144, 32768, 4096, 512, 64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_32> {
     const SIZE: usize = TREE_32;
     const BIGGEST_ROW_SIZE: usize = 32;
     const DEPTH: usize = 6;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![32, 16, 8, 4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![32768, 4096, 512, 64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_16> {
     const SIZE: usize = TREE_16;
     const BIGGEST_ROW_SIZE: usize = 16;
     const DEPTH: usize = 5;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![16, 8, 4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![4096, 512, 64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_8> {
     const SIZE: usize = TREE_8;
     const BIGGEST_ROW_SIZE: usize = 8;
     const DEPTH: usize = 4;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![8, 4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![512, 64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_4> {
     const SIZE: usize = TREE_4;
     const BIGGEST_ROW_SIZE: usize = 4;
     const DEPTH: usize = 3;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![4, 2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![64, 8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_2> {
     const SIZE: usize = TREE_2;
     const BIGGEST_ROW_SIZE: usize = 2;
     const DEPTH: usize = 2;
-
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![2, 1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![8, 1]
-    }
 }
 
 impl<T> TreeInterface for Tree<T, TREE_1> {
     const SIZE: usize = TREE_1;
     const BIGGEST_ROW_SIZE: usize = 1;
     const DEPTH: usize = 1;
+}
 
-    #[inline(always)]
-    fn rows_sizes() -> Vec<usize> {
-        vec![1]
-    }
-
-    #[inline(always)]
-    fn layers_sizes() -> Vec<usize> {
-        vec![1]
-    }
+/// Asserts at compile time that [`Tree`] has rows of the given sizes, listed from the leaf layer
+/// to the root, so code depending on geometry of a tree fails to compile when it changes.
+///
+/// Checks [`SIZE`](TreeInterface::SIZE), [`BIGGEST_ROW_SIZE`](TreeInterface::BIGGEST_ROW_SIZE)
+/// and [`DEPTH`](TreeInterface::DEPTH) and that every row is half of the previous one.
+///
+/// ```
+/// use packed_tree::{assert_tree_shape, implemented_tree_sizes::TREE_8, Tree};
+///
+/// assert_tree_shape!(Tree<u8, TREE_8>, rows = [8, 4, 2, 1]);
+/// ```
+///
+/// ```compile_fail
+/// use packed_tree::{assert_tree_shape, implemented_tree_sizes::TREE_8, Tree};
+///
+/// assert_tree_shape!(Tree<u8, TREE_8>, rows = [8, 4, 2]);
+/// ```
+#[macro_export]
+macro_rules! assert_tree_shape {
+    ($tree:ty, rows = [$($row:expr),+ $(,)?]) => {
+        const _: () = {
+            const ROWS: &[usize] = &[$($row),+];
+            assert!(
+                <$tree as $crate::TreeInterface>::DEPTH == ROWS.len(),
+                "tree has different amount of layers"
+            );
+            assert!(
+                <$tree as $crate::TreeInterface>::BIGGEST_ROW_SIZE == ROWS[0],
+                "tree has different size of the biggest row"
+            );
+            let mut size = 0;
+            let mut i = 0;
+            while i < ROWS.len() {
+                assert!(
+                    i == 0 || ROWS[i] * 2 == ROWS[i - 1],
+                    "every row has to be half of the previous one"
+                );
+                size += ROWS[i] * ROWS[i] * ROWS[i];
+                i += 1;
+            }
+            assert!(
+                <$tree as $crate::TreeInterface>::SIZE == size,
+                "tree has different amount of nodes"
+            );
+        };
+    };
 }
 
+crate::assert_tree_shape!(Tree<(), TREE_128>, rows = [128, 64, 32, 16, 8, 4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_64>, rows = [64, 32, 16, 8, 4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_32>, rows = [32, 16, 8, 4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_16>, rows = [16, 8, 4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_8>, rows = [8, 4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_4>, rows = [4, 2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_2>, rows = [2, 1]);
+crate::assert_tree_shape!(Tree<(), TREE_1>, rows = [1]);
+
 impl<T, const SIZE: usize> Tree<T, SIZE>
 where
     Self: TreeInterface,
@@ -219,7 +302,64 @@ where
     /// When createting a new [`Tree`] from existing nodes use of [`TryFrom<NodesRaw>`]
     /// is prefered as it provides more convinient usage.
     pub fn from_nodes(nodes: Box<[Node<T>; SIZE]>) -> Self {
-        Self { stored: nodes }
+        Self {
+            stored: nodes,
+            tombstones: None,
+            hashes: None,
+            value_filters: None,
+            face_coverage: None,
+            refinements: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a new [`Tree`] from `nodes` without padding them.
+    ///
+    /// Unlike [`From<NodesRaw>`](Tree::from) this refuses `nodes` which length is not exactly
+    /// [tree size](TreeInterface::SIZE) and returns [`TreeError::InvalidNodesLength`] instead.
+    pub fn from_nodes_raw_strict(nodes: NodesRaw<T, Self>) -> Result<Self, TreeError> {
+        let len = nodes.len();
+        let vec: Vec<Node<T>> = nodes.into();
+        let nodes: Box<[Node<T>; SIZE]> =
+            vec.try_into().map_err(|_| TreeError::InvalidNodesLength {
+                expected: SIZE,
+                actual: len,
+            })?;
+        Ok(Self::from_nodes(nodes))
+    }
+
+    /// Creates a new [`Tree`] from `nodes` given together with `x`, `y`, `z` and depth
+    /// of their [`NodePosition`], the rest of nodes is [`Empty`](Node::Empty).
+    ///
+    /// Unlike other constructors this never panics nor drops nodes silently, nodes on invalid
+    /// or already used positions are skipped and an [`ImportIssue`] is returned for each of them.
+    pub fn from_nodes_lossy<I>(nodes: I) -> (Self, Vec<ImportIssue>)
+    where
+        I: IntoIterator<Item = ([usize; 3], usize, Node<T>)>,
+        T: Clone,
+    {
+        let mut tree = Self::new();
+        let mut issues = Vec::new();
+        let mut sources = HashMap::new();
+        for (item, ([x, y, z], depth, node)) in nodes.into_iter().enumerate() {
+            let index = match NodePosition::<Self>::new_checked(x, y, z, depth) {
+                Ok(position) => NodeIndex::from(position),
+                Err(error) => {
+                    issues.push(ImportIssue::InvalidPosition { item, error });
+                    continue;
+                }
+            };
+            match sources.entry(index.raw()) {
+                Entry::Occupied(first) => issues.push(ImportIssue::DuplicatePosition {
+                    item,
+                    first: *first.get(),
+                }),
+                Entry::Vacant(entry) => {
+                    entry.insert(item);
+                    tree.stored[index.raw()] = node;
+                }
+            }
+        }
+        (tree, issues)
     }
 
     /// Builds [`Tree`] from bottom up, determining [`Node`] state of each node by taking its
@@ -228,27 +368,39 @@ where
     where
         F: FnOnce(&[&Node<T>]) -> Node<T> + Copy,
     {
-        // This replaces four nested for loops.
-        let iter = Self::rows_sizes()
-            .into_iter()
-            .enumerate()
-            .flat_map(|(depth, row_size)| {
-                (0..row_size).flat_map(move |z| {
-                    (0..row_size).flat_map(move |y| {
-                        (0..row_size).map(move |x| LayerPosition::new(x, y, z, depth))
-                    })
-                })
-            });
+        self.build_with_context(|context| combine_rule(context.children()));
+    }
+
+    /// Builds [`Tree`] from bottom up same as [`build`](Tree::build), but `combine_rule`
+    /// receives a [`CombineContext`] which besides children also exposes their octants,
+    /// position of the parent and value stored in the parent before the combination.
+    pub fn build_with_context<F>(&mut self, mut combine_rule: F)
+    where
+        F: FnMut(&CombineContext<'_, T, Self>) -> Node<T>,
+    {
+        let iter = (0..Self::DEPTH).flat_map(LayerPosition::iter_layer);
 
         for position in iter {
             if let Some(children) = self.children(position) {
-                let children_data = children
-                    .into_iter()
-                    .map(|index| self.get(index))
-                    .collect::<Vec<&Node<T>>>();
-                self.set(position, combine_rule(&children_data));
+                let context = CombineContext::new(
+                    position,
+                    self.get(position),
+                    children.map(|index| self.get(index)),
+                );
+                let node = combine_rule(&context);
+                self.set(position, node);
             }
         }
+        self.refresh_face_coverage();
+    }
+
+    /// Builds [`Tree`] from bottom up same as [`build`](Tree::build), combining children
+    /// by `reducer`, which keeps its state after the build.
+    pub fn build_with_reducer<R>(&mut self, reducer: &mut R)
+    where
+        R: Reducer<T, Self>,
+    {
+        self.build_with_context(|context| reducer.reduce(context.children(), context.position()));
     }
 
     /// Returns a reference to an [Node] on `position`.
@@ -262,6 +414,69 @@ where
         &self.stored[index]
     }
 
+    /// Returns a reference to an [Node] on `position` if it is valid,
+    /// otherwise [`CoordinateError`] describing the problem is returned.
+    ///
+    /// Unlike [`get`](Tree::get) this never panics, so it is suited for untrusted positions.
+    pub fn try_get<P>(&self, position: P) -> Result<&Node<T>, CoordinateError>
+    where
+        P: TryIntoIndex<Self>,
+    {
+        Ok(&self.stored[position.try_into_index()?])
+    }
+
+    /// Returns a value of [Node] on `position`, treating nodes which are not
+    /// [`Filled`](Node::Filled) as [`T::default`](Default::default).
+    ///
+    /// [NodeIndex] is expected to be always valid.
+    pub fn get_value<P>(&self, position: P) -> T
+    where
+        P: Into<NodeIndex<Self>>,
+        T: Default + Clone,
+    {
+        self.get(position).value_or_default()
+    }
+
+    /// Returns values of all nodes in layer in `depth` in the order in which they are stored,
+    /// treating nodes which are not [`Filled`](Node::Filled) as [`T::default`](Default::default).
+    pub fn layer_values(&self, depth: Depth<Self>) -> Vec<T>
+    where
+        T: Default + Clone,
+    {
+        let depth = depth.get();
+        let start = Self::layer_start(depth);
+        let len = Self::layer_size(depth).get();
+        self.stored[start..start + len]
+            .iter()
+            .map(Node::value_or_default)
+            .collect()
+    }
+
+    /// Calls `f` on every node in layer in `depth` together with its [`LayerPosition`],
+    /// in the order in which nodes are stored.
+    ///
+    /// Positions are advanced incrementally instead of being converted from each index.
+    /// Changes are not recorded as tombstones.
+    pub fn map_layer_in_place<F>(&mut self, depth: Depth<Self>, mut f: F)
+    where
+        F: FnMut(LayerPosition<Self>, &mut Node<T>),
+    {
+        let depth = depth.get();
+        let start = Self::layer_start(depth);
+        let len = Self::layer_size(depth).get();
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate(start);
+        }
+        if depth == 0 {
+            self.invalidate_value_filters();
+        }
+        for (position, node) in
+            LayerPosition::iter_layer(depth).zip(&mut self.stored[start..start + len])
+        {
+            f(position, node);
+        }
+    }
+
     /// Returns a mutable reference to an [Node] on `position`.
     ///
     /// [NodeIndex] is expected to be always valid.
@@ -270,9 +485,49 @@ where
         P: Into<NodeIndex<Self>>,
     {
         let index = position.into();
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate(index.raw());
+        }
+        if index.depth() == 0 {
+            self.invalidate_value_filters();
+        }
         &mut self.stored[index]
     }
 
+    /// Returns a mutable reference to an [Node] on `position` if it is valid,
+    /// otherwise [`CoordinateError`] describing the problem is returned.
+    ///
+    /// Unlike [`get_mut`](Tree::get_mut) this never panics, so it is suited for untrusted positions.
+    pub fn try_get_mut<P>(&mut self, position: P) -> Result<&mut Node<T>, CoordinateError>
+    where
+        P: TryIntoIndex<Self>,
+    {
+        Ok(self.get_mut(position.try_into_index()?))
+    }
+
+    /// Applies `f` to [Node] on `position`, then combines all its ancestors again by `combine_rule`
+    /// from its parrent up to the root and returns result of `f`.
+    ///
+    /// The node is changed the same way as through [`get_mut`](Tree::get_mut) and its ancestors
+    /// are [set](Tree::set). Children are passed to `combine_rule` in the same order
+    /// as [`build`](Tree::build) passes them.
+    pub fn modify<P, F, R, C>(&mut self, position: P, f: F, mut combine_rule: C) -> R
+    where
+        P: Into<NodeIndex<Self>>,
+        F: FnOnce(&mut Node<T>) -> R,
+        C: FnMut(&[&Node<T>]) -> Node<T>,
+    {
+        let index = position.into();
+        let result = f(self.get_mut(index));
+        for ancestor in index.ancestors() {
+            // Ancestors always have children.
+            let children = self.children(ancestor).unwrap();
+            let node = combine_rule(&children.map(|child| self.get(child)));
+            self.set(ancestor, node);
+        }
+        result
+    }
+
     /// Returns an [`index`](NodeIndex) of parrent of [`Node`] on `position`
     /// if such node has a parrent, i.e. does not have `depth` equal to [TreeParameters::MAX_DEPTH_INDEX],
     /// in that case [`None`] is returned.
@@ -284,6 +539,56 @@ where
         Some(LayerPosition::from(index).parrent_position()?.into())
     }
 
+    /// Returns an iterator over [`indexes`](NodeIndex) of ancestors of [`Node`] on `position`,
+    /// i.e. its parrent, grandparrent and so on up to the root.
+    pub fn ancestors<P>(&self, position: P) -> Ancestors<Self>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        position.into().ancestors()
+    }
+
+    /// Returns the deepest common ancestor of nodes on `a` and `b` together with its [`Depth`],
+    /// see [`NodeIndex::common_ancestor`].
+    pub fn common_ancestor<P, Q>(&self, a: P, b: Q) -> (NodeIndex<Self>, Depth<Self>)
+    where
+        P: Into<NodeIndex<Self>>,
+        Q: Into<NodeIndex<Self>>,
+    {
+        a.into().common_ancestor(b.into())
+    }
+
+    /// Returns an iterator over [`indexes`](NodeIndex) of siblings of [`Node`] on `position`,
+    /// i.e. the other children of its parrent. The root has no siblings.
+    ///
+    /// Siblings are yielded in the same order as [`children`](Tree::children) returns them.
+    pub fn siblings<P>(&self, position: P) -> Siblings<Self>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        Siblings::new(position.into())
+    }
+
+    /// Returns an iterator over [`indexes`](NodeIndex) of nodes in the same layer which share
+    /// a face with [`Node`] on `position`, together with [`Direction`](crate::Direction)
+    /// towards them. Neighbours outside of the layer are skipped.
+    pub fn neighbors6<P>(&self, position: P) -> FaceNeighbors<Self>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        FaceNeighbors::new(position.into())
+    }
+
+    /// Returns an iterator over [`indexes`](NodeIndex) of nodes in the same layer which share
+    /// a face, an edge or a corner with [`Node`] on `position`, together with offset on `x`, `y`
+    /// and `z` towards them. Neighbours outside of the layer are skipped.
+    pub fn neighbors26<P>(&self, position: P) -> Neighbors<Self>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        Neighbors::new(position.into())
+    }
+
     /// Returns an [`indexes`](NodeIndex) of children of [`Node`] on `position`
     /// if such node has a children, i.e. does not have `depth` equal to zero,
     /// in which case [`None`] is returned.
@@ -294,31 +599,202 @@ where
     ///
     /// `(0, 0, 0)`, `(1, 0, 0)`, `(0, 1, 0)`, `(1, 1, 0)`, `(0, 0, 1)`, `(1, 0, 1)`, `(0, 1, 1)`, `(1, 1, 1)`
     ///
-    // TODO: Maybe replace [NodeIndex; 8] with some datastructure.
     pub fn children<P>(&self, position: P) -> Option<[NodeIndex<Self>; 8]>
     where
         P: Into<NodeIndex<Self>>,
     {
-        let parrent_index: NodeIndex<Self> = position.into();
-        // Position of an child in bottom front left corner of parrent node.
-        let children_anchor: NodeIndex<Self> =
-            NodePosition::from(parrent_index).child_position()?.into();
-        // Row size of childrens layer.
-        let row_size = Self::row_size(children_anchor.depth());
-
-        let children: [NodeIndex<Self>; 8] = (0..2)
-            .flat_map(|z| {
-                (0..2).flat_map(move |y| {
-                    (0..2).map(move |x| {
-                        children_anchor + (x + (y * row_size) + (z * row_size * row_size))
-                    })
-                })
-            })
-            .collect::<Vec<NodeIndex<Self>>>()
-            .try_into()
-            .unwrap(); // Iterator bounds ensure that no panic will occur on this unwrap.
+        let mut children = self.children_iter(position)?;
+        // `Children` always yields exactly 8 indexes, so this never panics.
+        Some(std::array::from_fn(|_| children.next().unwrap()))
+    }
+
+    /// Returns an iterator over [`indexes`](NodeIndex) of children of [`Node`] on `position`
+    /// if such node has a children, otherwise [`None`] is returned.
+    ///
+    /// Children are yielded in the same order as [`children`](Tree::children) returns them.
+    pub fn children_iter<P>(&self, position: P) -> Option<Children<Self>>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        Children::new(position.into())
+    }
 
-        Some(children)
+    /// Returns all [`nodes`](Node) as a slice.
+    ///
+    /// Nodes are guaranteed to be stored contiguously, layers from the shallowest to the deepest
+    /// and nodes inside of a layer with `x` changing the fastest, then `y` and `z`, so slice
+    /// index of a node is its [raw index](NodeIndex::raw).
+    pub fn as_slice(&self) -> &[Node<T>] {
+        self.stored.as_slice()
+    }
+
+    /// Returns all [`nodes`](Node) as a mutable slice, ordered the same as by
+    /// [`as_slice`](Tree::as_slice).
+    ///
+    /// Changes are not recorded as tombstones and make layer hashes and value filters unknown.
+    pub fn as_mut_slice(&mut self) -> &mut [Node<T>] {
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+        self.stored.as_mut_slice()
+    }
+
+    /// Returns an iterator over all [`nodes`](Node) together with their [`indexes`](NodeIndex),
+    /// in the order in which they are stored.
+    pub fn iter(&self) -> Iter<'_, T, SIZE> {
+        Iter::new(&self.stored)
+    }
+
+    /// Returns an iterator over mutable references to all [`nodes`](Node)
+    /// together with their [`indexes`](NodeIndex), in the order in which they are stored.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, SIZE> {
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+        IterMut::new(&mut self.stored)
+    }
+
+    /// Returns an iterator moving values out of [`Filled`](Node::Filled) nodes together with their
+    /// [`indexes`](NodeIndex), leaving [`Empty`](Node::Empty) nodes behind.
+    ///
+    /// All filled nodes are emptied even if the iterator is not fully consumed.
+    pub fn drain_filled(&mut self) -> DrainFilled<'_, T, SIZE> {
+        self.invalidate_layer_hashes();
+        DrainFilled::new(&mut self.stored)
+    }
+
+    /// Returns an iterator over all [`nodes`](Node) together with their
+    /// [`positions`](crate::NodePosition), in the order in which they are stored.
+    pub fn enumerate_positions(&self) -> EnumeratePositions<'_, T, SIZE> {
+        EnumeratePositions::new(&self.stored)
+    }
+
+    /// Returns an iterator over [`nodes`](Node) of the shallowest layer together with their
+    /// [`indexes`](NodeIndex) in Morton (Z-order) order.
+    pub fn iter_leaf_morton(&self) -> LeafMorton<'_, T, SIZE> {
+        LeafMorton::new(&self.stored)
+    }
+
+    /// Returns an iterator over [`nodes`](Node) of layer in `depth` whose coordinate on `axis`
+    /// is equal to `coordinate`, together with their [`positions`](LayerPosition).
+    ///
+    /// Expects in-bounds `coordinate`.
+    pub fn slice_plane(
+        &self,
+        depth: Depth<Self>,
+        axis: Axis,
+        coordinate: usize,
+    ) -> LayerIter<'_, T, SIZE> {
+        LayerIter::plane(self, depth.get(), axis, coordinate)
+    }
+
+    /// Returns an iterator over [`nodes`](Node) of a row along `axis`, from `start` to the end of
+    /// the layer of `start`, together with their [`positions`](LayerPosition).
+    pub fn iter_row(&self, start: LayerPosition<Self>, axis: Axis) -> Row<'_, T, SIZE> {
+        Row::new(self, start, axis)
+    }
+
+    /// Returns an iterator over [`nodes`](Node) on the outer faces of layer in `depth`
+    /// together with their [`positions`](LayerPosition), without visiting the interior.
+    pub fn iter_shell(&self, depth: Depth<Self>) -> Shell<'_, T, SIZE> {
+        Shell::new(self, depth.get())
+    }
+
+    /// Returns an iterator over [`nodes`](Node) inside of an axis-aligned box of one layer,
+    /// e.g. `min..=max`, together with their [`positions`](LayerPosition).
+    ///
+    /// The box is empty if any coordinate of `min` is greater than the one of `max`.
+    pub fn iter_region<R>(&self, range: R) -> Region<'_, Self>
+    where
+        R: Into<PositionRange<Self>>,
+    {
+        Region::new(self, range.into())
+    }
+
+    /// Returns an iterator over values of [`Filled`](Node::Filled) nodes together with their
+    /// [`indexes`](NodeIndex), skipping [`Empty`](Node::Empty) and [`Reduced`](Node::Reduced) nodes.
+    pub fn filled(&self) -> Filled<'_, T, SIZE> {
+        Filled::new(&self.stored)
+    }
+
+    /// Returns an iterator over mutable references to values of [`Filled`](Node::Filled) nodes
+    /// together with their [`indexes`](NodeIndex).
+    pub fn filled_mut(&mut self) -> FilledMut<'_, T, SIZE> {
+        self.invalidate_layer_hashes();
+        self.invalidate_value_filters();
+        FilledMut::new(&mut self.stored)
+    }
+
+    /// Returns a breadth-first iterator over [`Node`] on `position` and all its descendants,
+    /// i.e. the node is visited first, then its children, then grandchildren and so on.
+    pub fn bfs_from<P>(&self, position: P) -> Bfs<'_, T, SIZE>
+    where
+        P: Into<NodeIndex<Self>>,
+    {
+        Bfs::new(self, position.into())
+    }
+
+    /// Returns a depth-first iterator over all nodes which are not [`Empty`](Node::Empty),
+    /// starting from the root.
+    ///
+    /// Subtrees of [`Empty`](Node::Empty) nodes are skipped entirely, so for mostly empty trees
+    /// the walk is proportional to occupied volume. Relies on upper layers being
+    /// [built](Tree::build).
+    pub fn descend_filled(&self) -> DescendFilled<'_, Self> {
+        DescendFilled::new(self)
+    }
+
+    /// Returns an iterator over pairs of consecutive layers as slices of [`nodes`](Node),
+    /// from the shallowest layer up, each pair holding a layer and the layer of its parrents.
+    ///
+    /// Nodes in the slices are in the order in which they are stored, so parrents are not
+    /// laid out next to their children, use [`children`](Tree::children) to find them.
+    pub fn layer_pairs(&self) -> LayerPairs<'_, T, SIZE> {
+        LayerPairs::new(&self.stored)
+    }
+
+    /// Returns layer in `depth` together with mutable layer of its parrents as slices
+    /// of [`nodes`](Node). Changes are not recorded as tombstones.
+    ///
+    /// Expects `depth` to not be [`Depth::MAX`].
+    pub fn layer_pair_mut(&mut self, depth: Depth<Self>) -> (&[Node<T>], &mut [Node<T>]) {
+        let depth = depth.get();
+        let start = Self::layer_start(depth);
+        let len = Self::layer_size(depth).get();
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate(start + len);
+        }
+        let (children, parrents) = self.stored[start..].split_at_mut(len);
+        (children, &mut parrents[..Self::layer_size(depth + 1).get()])
+    }
+
+    /// Returns an iterator over [`nodes`](Node) of layer in `depth` in groups of eight siblings,
+    /// together with [`index`](NodeIndex) of their common parrent.
+    ///
+    /// Groups can be passed directly to a combine rule when reducing the layer.
+    /// The root layer has no groups.
+    pub fn octant_groups(&self, depth: Depth<Self>) -> OctantGroups<'_, T, SIZE> {
+        OctantGroups::new(self, depth.get())
+    }
+
+    /// Returns an iterator over values of [`Filled`](Node::Filled) nodes of the shallowest
+    /// layer which share a face with an [`Empty`](Node::Empty) node or with the border
+    /// of the layer, together with their [`indexes`](NodeIndex).
+    ///
+    /// Subtrees of [`Empty`](Node::Empty) nodes and of [`Filled`](Node::Filled) nodes enclosed
+    /// by [`Filled`](Node::Filled) nodes are skipped. Relies on upper layers being
+    /// [built](Tree::build), with [`Filled`](Node::Filled) parrents having only filled children.
+    /// Leaves [refined](Tree::refine_leaf) by a [`MicroGrid`] which is not full are always on
+    /// the surface, enclosed subtrees are not skipped if any leaf is refined.
+    pub fn surface(&self) -> Surface<'_, Self> {
+        Surface::new(self)
+    }
+
+    /// Returns an iterator over the biggest [`Empty`](Node::Empty) nodes, i.e. nodes which
+    /// have no filled descendants, with `depth` at least `min_depth`.
+    ///
+    /// Descendants of a yielded node are not visited. Relies on upper layers being
+    /// [built](Tree::build), as only [`Empty`](Node::Empty) nodes are treated as empty subtrees.
+    pub fn iter_empty_octants(&self, min_depth: usize) -> EmptyOctants<'_, Self> {
+        EmptyOctants::new(self, min_depth)
     }
 
     /// Sets the node on `position` to provided [`node`](Node)
@@ -330,10 +806,42 @@ where
         let mut node = node;
         let index = position.into();
         std::mem::swap(&mut self.stored[index], &mut node);
+        if let Some(tombstones) = &mut self.tombstones {
+            tombstones.record(index.raw(), &node, &self.stored[index]);
+        }
+        if let Some(hashes) = &mut self.hashes {
+            hashes.record(index.raw(), &node, &self.stored[index]);
+        }
+        if let Some(filters) = &mut self.value_filters {
+            filters.record(index, &self.stored[index]);
+        }
         node
     }
+
+    /// Marks hashes of all layers as unknown, if they are kept.
+    pub(crate) fn invalidate_layer_hashes(&mut self) {
+        if let Some(hashes) = &mut self.hashes {
+            hashes.invalidate_all();
+        }
+    }
 }
 
+/// Offsets on `x`, `y` and `z` of a child from the child in bottom front left corner
+/// of the same parrent, indexed by octant.
+///
+/// Children are always ordered this way, e.g. by [`Tree::children`] and in [`Tree::build`],
+/// so octant of a child is `x | y << 1 | z << 2`.
+pub const OCTANT_OFFSETS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
 // TODO: find better name? Already changed from config and better documentation
 /// Common tree parameters.
 pub trait TreeInterface {
@@ -341,29 +849,99 @@ pub trait TreeInterface {
     const SIZE: usize;
     /// Size of the biggest row of tree.
     const BIGGEST_ROW_SIZE: usize;
+    /// Amount of elements in a row of the leaf layer, i.e. the layer with the most elements.
+    const LEAF_ROW_LEN: usize = Self::BIGGEST_ROW_SIZE;
+    /// Amount of elements in the leaf layer, which is stored first.
+    const LEAF_LAYER_LEN: usize = Self::LEAF_ROW_LEN * Self::LEAF_ROW_LEN * Self::LEAF_ROW_LEN;
     /// Amount of elements it the shallowest tree layer.
-    const CHUNK_SIZE: usize =
-        Self::BIGGEST_ROW_SIZE * Self::BIGGEST_ROW_SIZE * Self::BIGGEST_ROW_SIZE;
+    #[deprecated(note = "use `LEAF_LAYER_LEN` instead")]
+    const CHUNK_SIZE: usize = Self::LEAF_LAYER_LEN;
     /// Amount of elements it the shallowest tree layer.
-    const SHALLOWEST_LAYER_SIZE: usize = Self::CHUNK_SIZE;
+    #[deprecated(note = "use `LEAF_LAYER_LEN` instead")]
+    const SHALLOWEST_LAYER_SIZE: usize = Self::LEAF_LAYER_LEN;
     /// Amount of layers tree has.
     const DEPTH: usize = tree_depth(Self::BIGGEST_ROW_SIZE);
     /// Index of deepest layer.
     const MAX_DEPTH_INDEX: usize = Self::DEPTH - 1;
 
-    /// Returns a row_size in specified `depth`.
+    /// Returns a [RowSize] in specified `depth`.
+    ///
+    /// Row size is derived from [BIGGEST_ROW_SIZE](TreeInterface::BIGGEST_ROW_SIZE).
+    /// Panics if `depth` is out of bounds.
+    #[inline(always)]
+    fn row_size(depth: usize) -> RowSize {
+        assert!(
+            depth <= Self::MAX_DEPTH_INDEX,
+            "depth {depth} is out of bounds"
+        );
+        // Every layer has half of the row size of the layer above it.
+        RowSize::new(Self::BIGGEST_ROW_SIZE >> depth)
+    }
+
+    /// Returns a [LayerSize] in specified `depth`.
+    ///
+    /// Panics if `depth` is out of bounds.
+    #[inline(always)]
+    fn layer_size(depth: usize) -> LayerSize {
+        Self::row_size(depth).cells()
+    }
+
+    /// Returns a [RowSize] in specified `depth`,
+    /// or [`CoordinateError::InvalidDepth`] if `depth` is out of bounds.
+    fn try_row_size(depth: usize) -> Result<RowSize, CoordinateError> {
+        if depth > Self::MAX_DEPTH_INDEX {
+            return Err(CoordinateError::InvalidDepth {
+                depth,
+                max: Self::MAX_DEPTH_INDEX,
+            });
+        }
+        Ok(Self::row_size(depth))
+    }
+
+    /// Returns raw index of the first node in layer in `depth`,
+    /// i.e. amount of nodes in all shallower layers.
+    ///
+    /// Panics if `depth` is out of bounds.
+    #[inline(always)]
+    fn layer_start(depth: usize) -> usize {
+        assert!(
+            depth <= Self::MAX_DEPTH_INDEX,
+            "depth {depth} is out of bounds"
+        );
+        (0..depth).map(|depth| Self::layer_size(depth).get()).sum()
+    }
+
+    /// Returns ranges of `x`, `y` and `z` covering the whole leaf layer.
+    #[inline(always)]
+    fn leaf_extent() -> [Range<usize>; 3] {
+        [
+            0..Self::LEAF_ROW_LEN,
+            0..Self::LEAF_ROW_LEN,
+            0..Self::LEAF_ROW_LEN,
+        ]
+    }
+
+    /// Returns offset of raw index of a child in `octant` from raw index of the child in octant 0,
+    /// where children are in layer in `depth`. Octants are described by [`OCTANT_OFFSETS`].
     ///
-    /// Expects in-bounds `depth`.
+    /// Expects `octant` to be less than 8 and in-bounds `depth`.
     #[inline(always)]
-    fn row_size(depth: usize) -> usize {
-        debug_assert!(depth <= Self::MAX_DEPTH_INDEX);
-        Self::rows_sizes()[depth]
+    fn child_offset(octant: usize, depth: usize) -> usize {
+        let [x, y, z] = OCTANT_OFFSETS[octant];
+        x + Self::row_size(depth).stride(y, z)
+    }
+
+    /// Returns a [LayerSize] in specified `depth`,
+    /// or [`CoordinateError::InvalidDepth`] if `depth` is out of bounds.
+    fn try_layer_size(depth: usize) -> Result<LayerSize, CoordinateError> {
+        Ok(Self::try_row_size(depth)?.cells())
     }
 
     /// Returns row sizes of tree, from the shallowest to the deepest.
     ///
-    /// When implementing a for a new tree size it is better to implement this manually.
-    // TODO change this to a `const ROWS_SIZES` once `[usize; Self::DEPTH]` is allowed.
+    /// Row sizes are always derived from [BIGGEST_ROW_SIZE](TreeInterface::BIGGEST_ROW_SIZE),
+    /// overriding this function does not change them.
+    #[deprecated(note = "use `row_size` or `Depth::row_size` instead")]
     fn rows_sizes() -> Vec<usize> {
         let mut sizes = Vec::new();
         let mut row_size = Self::BIGGEST_ROW_SIZE;
@@ -379,13 +957,13 @@ pub trait TreeInterface {
     /// Returns layers sizes of tree, i.e. amount of elements in each layer,
     /// from the shallowest to the deepest.
     ///
-    /// When implementing a for a new tree size it is better to implement this manually.
+    /// Layer sizes are always derived from [BIGGEST_ROW_SIZE](TreeInterface::BIGGEST_ROW_SIZE),
+    /// overriding this function does not change them.
+    #[deprecated(note = "use `layer_size` or `Depth::layer_size` instead")]
     fn layers_sizes() -> Vec<usize> {
-        let mut sizes = Vec::new();
-        for row_size in Self::rows_sizes() {
-            sizes.push(row_size * row_size * row_size);
-        }
-        sizes
+        (0..Self::DEPTH)
+            .map(|depth| Self::layer_size(depth).get())
+            .collect()
     }
 }
 
@@ -403,7 +981,10 @@ const fn tree_depth(row_size: usize) -> usize {
 #[cfg(test)]
 mod tree_tests {
 
-    use crate::{Node, NodeIndex, NodesRaw};
+    use crate::{
+        CoordinateError, Depth, ImportIssue, LayerIndex, LayerPosition, Node, NodeIndex,
+        NodePosition, NodesRaw, Reducer, TreeError,
+    };
 
     use super::Tree;
 
@@ -437,6 +1018,100 @@ mod tree_tests {
             .unwrap_err();
     }
 
+    #[test]
+    fn from_nodes_raw_strict() {
+        assert!(TestTree::from_nodes_raw_strict(nodes_raw(73)).is_ok());
+        assert_eq!(
+            TestTree::from_nodes_raw_strict(nodes_raw(64)).unwrap_err(),
+            TreeError::InvalidNodesLength {
+                expected: 73,
+                actual: 64
+            }
+        );
+    }
+
+    #[test]
+    fn index() {
+        let mut tree = TestTree::new();
+        tree[LayerPosition::new(3, 1, 2, 0)] = Node::Filled(1);
+        assert_eq!(tree[NodeIndex::new(39)], Node::Filled(1));
+        assert_eq!(tree[NodePosition::new(3, 1, 2, 0)], Node::Filled(1));
+        assert_eq!(tree[(3, 1, 2, Depth::LEAF)], Node::Filled(1));
+
+        tree[(1, 0, 1, Depth::try_new(1).unwrap())] = Node::Filled(2);
+        assert_eq!(tree[LayerIndex::new(5, 1)], Node::Filled(2));
+        assert_eq!(tree.get(NodeIndex::new(69)), &Node::Filled(2));
+        tree[(0, 0, 0, Depth::MAX)] = Node::Reduced;
+        assert_eq!(tree[NodeIndex::new(72)], Node::Reduced);
+    }
+
+    #[test]
+    fn try_get() {
+        let mut tree = TestTree::new();
+        *tree.try_get_mut(LayerPosition::new(1, 2, 3, 0)).unwrap() = Node::Filled(1);
+        assert_eq!(tree.try_get(NodeIndex::new(57)), Ok(&Node::Filled(1)));
+        let mut position = LayerPosition::new(3, 0, 0, 0);
+        position.x = 4;
+        assert_eq!(
+            tree.try_get(position),
+            Err(CoordinateError::InvalidPosition {
+                position: [4, 0, 0],
+                depth: 0,
+                row_size: 4
+            })
+        );
+        let mut position = NodePosition::new(2, 0, 0, 1);
+        position.x = 1;
+        assert!(tree.try_get_mut(position).is_err());
+        assert!(tree.try_get(LayerIndex::new(7, 1)).is_ok());
+    }
+
+    #[test]
+    fn from_nodes_lossy() {
+        let nodes = vec![
+            ([1, 2, 3], 0, Node::Filled(1)),
+            ([4, 0, 0], 0, Node::Filled(2)),
+            ([2, 2, 2], 1, Node::Filled(3)),
+            ([1, 0, 0], 1, Node::Filled(4)),
+            ([1, 2, 3], 0, Node::Filled(5)),
+            ([0, 0, 0], 3, Node::Filled(6)),
+        ];
+        let (tree, issues) = TestTree::from_nodes_lossy(nodes);
+        assert_eq!(tree.get(LayerPosition::new(1, 2, 3, 0)), &Node::Filled(1));
+        assert_eq!(tree.get(LayerPosition::new(1, 1, 1, 1)), &Node::Filled(3));
+        assert_eq!(tree.filled().count(), 2);
+        assert_eq!(
+            issues,
+            vec![
+                ImportIssue::InvalidPosition {
+                    item: 1,
+                    error: CoordinateError::InvalidPosition {
+                        position: [4, 0, 0],
+                        depth: 0,
+                        row_size: 4
+                    }
+                },
+                ImportIssue::InvalidPosition {
+                    item: 3,
+                    error: CoordinateError::UnalignedPosition {
+                        position: [1, 0, 0],
+                        depth: 1,
+                        alignment: 2
+                    }
+                },
+                ImportIssue::DuplicatePosition { item: 4, first: 0 },
+                ImportIssue::InvalidPosition {
+                    item: 5,
+                    error: CoordinateError::InvalidDepth { depth: 3, max: 2 }
+                },
+            ]
+        );
+        assert_eq!(
+            issues[2].to_string(),
+            "node 4 skipped: position already set by node 0"
+        );
+    }
+
     #[test]
     fn children() {
         let nodes = nodes_raw(73);
@@ -501,6 +1176,19 @@ mod tree_tests {
         );
     }
 
+    #[test]
+    fn children_iter() {
+        let tree = TestTree::new();
+        assert!(tree.children_iter(NodeIndex::new(5)).is_none());
+
+        let children = tree.children_iter(NodeIndex::new(65)).unwrap();
+        assert_eq!(children.len(), 8);
+        assert_eq!(
+            children.collect::<Vec<_>>(),
+            tree.children(NodeIndex::new(65)).unwrap().to_vec()
+        );
+    }
+
     #[test]
     fn parrent() {
         let nodes = nodes_raw(73);
@@ -526,6 +1214,94 @@ mod tree_tests {
         assert_eq!(tree.get(NodeIndex::new(64)), &Node::Filled(64));
     }
 
+    #[test]
+    fn as_slice() {
+        let mut tree = TestTree::from(nodes_raw(73));
+        assert_eq!(tree.as_slice().len(), 73);
+        for (index, node) in tree.as_slice().iter().enumerate() {
+            assert_eq!(node, tree.get(NodeIndex::new(index)));
+        }
+
+        tree.as_mut_slice()[64] = Node::Reduced;
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Reduced);
+
+        let mut other = TestTree::new();
+        other.as_mut_slice().clone_from_slice(tree.as_slice());
+        assert_eq!(other, tree);
+    }
+
+    #[test]
+    fn get_value() {
+        let mut tree = Tree::<f32, 73>::new();
+        tree.set(NodeIndex::new(1), Node::Filled(0.5));
+        tree.set(NodeIndex::new(64), Node::Reduced);
+
+        assert_eq!(tree.get_value(NodeIndex::new(0)), 0.0);
+        assert_eq!(tree.get_value(NodeIndex::new(1)), 0.5);
+        assert_eq!(tree.get_value(NodeIndex::new(64)), 0.0);
+    }
+
+    #[test]
+    fn modify() {
+        let sum = |nodes: &[&Node<usize>]| {
+            Node::Filled(nodes.iter().map(|node| node.value_or_default()).sum())
+        };
+        let mut tree = TestTree::new();
+        tree.build(sum);
+
+        let previous = tree.modify(
+            NodeIndex::new(42),
+            |node| std::mem::replace(node, Node::Filled(3)),
+            sum,
+        );
+        assert_eq!(previous, Node::Empty);
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Filled(3));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(3));
+
+        let doubled = tree.modify(
+            NodeIndex::new(42),
+            |node| {
+                if let Node::Filled(value) = node {
+                    *value *= 2;
+                }
+                node.value_or_default()
+            },
+            sum,
+        );
+        assert_eq!(doubled, 6);
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Filled(0));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(6));
+    }
+
+    #[test]
+    fn layer_values() {
+        let tree = TestTree::from(nodes_raw(66));
+
+        assert_eq!(
+            tree.layer_values(Depth::new(0)),
+            (0..64).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            tree.layer_values(Depth::new(1)),
+            vec![64, 65, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(tree.layer_values(Depth::new(2)), vec![0]);
+    }
+
+    #[test]
+    fn map_layer_in_place() {
+        let mut tree = TestTree::from(nodes_raw(73));
+        tree.map_layer_in_place(Depth::new(1), |position, node| {
+            *node = Node::Filled(position.x + position.y * 10 + position.z * 100);
+        });
+
+        assert_eq!(tree.get(NodeIndex::new(63)), &Node::Filled(63));
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Filled(0));
+        assert_eq!(tree.get(NodeIndex::new(66)), &Node::Filled(10));
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Filled(111));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(72));
+    }
+
     #[test]
     fn build() {
         let mut nodes = nodes_raw(64);
@@ -576,6 +1352,73 @@ mod tree_tests {
         test_tree.set(NodeIndex::new(72), Node::Reduced);
         assert_eq!(tree, test_tree);
     }
+
+    #[test]
+    fn build_with_context() {
+        let mut nodes = nodes_raw(64);
+        nodes.push(Node::Filled(1000));
+        let mut tree = TestTree::from(nodes);
+
+        let mut calls = 0;
+        tree.build_with_context(|context| {
+            calls += 1;
+            assert_eq!(context.octants().count(), 8);
+            match context.previous() {
+                // Keep previous value if it is already filled.
+                Node::Filled(value) => Node::Filled(*value),
+                _ => match context.child(7) {
+                    Node::Filled(value) => Node::Filled(*value),
+                    _ => Node::Empty,
+                },
+            }
+        });
+
+        assert_eq!(calls, 9);
+        assert_eq!(tree.get(NodeIndex::new(64)), &Node::Filled(1000));
+        assert_eq!(tree.get(NodeIndex::new(65)), &Node::Filled(23));
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Filled(63));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(63));
+    }
+
+    /// Counts filled parents and marks every parent filled if any of its children is.
+    #[derive(Default)]
+    struct CountingReducer {
+        filled: usize,
+        positions: Vec<LayerPosition<TestTree>>,
+    }
+
+    impl Reducer<usize, TestTree> for CountingReducer {
+        fn reduce(
+            &mut self,
+            children: &[&Node<usize>; 8],
+            position: LayerPosition<TestTree>,
+        ) -> Node<usize> {
+            self.positions.push(position);
+            if children
+                .iter()
+                .any(|child| matches!(child, Node::Filled(_)))
+            {
+                self.filled += 1;
+                Node::Filled(1)
+            } else {
+                Node::Empty
+            }
+        }
+    }
+
+    #[test]
+    fn build_with_reducer() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(42), Node::Filled(1));
+
+        let mut reducer = CountingReducer::default();
+        tree.build_with_reducer(&mut reducer);
+        assert_eq!(reducer.filled, 2);
+        assert_eq!(reducer.positions.len(), 9);
+        assert_eq!(reducer.positions[8], LayerPosition::new(0, 0, 0, 2));
+        assert_eq!(tree.get(NodeIndex::new(71)), &Node::Filled(1));
+        assert_eq!(tree.get(NodeIndex::new(72)), &Node::Filled(1));
+    }
 }
 
 #[cfg(test)]
@@ -584,6 +1427,65 @@ mod tree_interface_tests {
         implemented_tree_sizes::{
             TREE_1, TREE_128, TREE_16, TREE_2, TREE_32, TREE_4, TREE_64, TREE_8,
         },
-        NodeIndex, Tree, TreeInterface,
+        NodeIndex, Tree, TreeInterface, OCTANT_OFFSETS,
     };
+
+    #[test]
+    fn child_offset() {
+        type TestTree = Tree<usize, 73>;
+        assert_eq!(TestTree::child_offset(0, 0), 0);
+        assert_eq!(TestTree::child_offset(3, 0), 5);
+        assert_eq!(TestTree::child_offset(7, 0), 21);
+        assert_eq!(TestTree::child_offset(6, 1), 6);
+
+        let tree = TestTree::new();
+        let children = tree.children(NodeIndex::<TestTree>::new(71)).unwrap();
+        for (octant, child) in children.into_iter().enumerate() {
+            assert_eq!(
+                child.raw(),
+                children[0].raw() + TestTree::child_offset(octant, 0)
+            );
+            let [x, y, z] = OCTANT_OFFSETS[octant];
+            assert_eq!(child.octant(), Some(x | y << 1 | z << 2));
+        }
+    }
+
+    #[allow(deprecated)]
+    fn sizes_match<U: TreeInterface>() {
+        let layers_sizes = U::layers_sizes();
+        assert_eq!(layers_sizes.len(), U::DEPTH);
+        assert_eq!(layers_sizes.iter().sum::<usize>(), U::SIZE);
+        assert_eq!(U::rows_sizes()[0], U::BIGGEST_ROW_SIZE);
+        assert_eq!(U::layers_sizes()[0], U::LEAF_LAYER_LEN);
+        assert_eq!(
+            U::leaf_extent(),
+            [0..U::LEAF_ROW_LEN, 0..U::LEAF_ROW_LEN, 0..U::LEAF_ROW_LEN]
+        );
+        for (depth, row_size) in U::rows_sizes().into_iter().enumerate() {
+            assert_eq!(U::row_size(depth).get(), row_size);
+            assert_eq!(U::layer_size(depth).get(), layers_sizes[depth]);
+            assert_eq!(
+                U::layer_start(depth),
+                layers_sizes[..depth].iter().sum::<usize>()
+            );
+        }
+    }
+
+    #[test]
+    fn implemented_sizes() {
+        sizes_match::<Tree<usize, TREE_1>>();
+        sizes_match::<Tree<usize, TREE_2>>();
+        sizes_match::<Tree<usize, TREE_4>>();
+        sizes_match::<Tree<usize, TREE_8>>();
+        sizes_match::<Tree<usize, TREE_16>>();
+        sizes_match::<Tree<usize, TREE_32>>();
+        sizes_match::<Tree<usize, TREE_64>>();
+        sizes_match::<Tree<usize, TREE_128>>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_size_out_of_bounds() {
+        Tree::<usize, 73>::row_size(5);
+    }
 }
@@ -0,0 +1,228 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+
+use crate::{
+    iter_region, Children, Node, NodeIndex, NodePosition, OctreeAccess, PositionRange, Tree,
+    TreeInterface,
+};
+
+/// Bloom filters of values of [`Filled`](Node::Filled) leaves under every parrent of [`Tree`],
+/// kept up to date by [`Tree::set`].
+///
+/// Values are only ever added to filters, so replaced values may still be reported as present
+/// until [`refresh_value_filters`](Tree::refresh_value_filters) is called.
+#[derive(Debug, Clone)]
+pub(crate) struct ValueFilters<T> {
+    /// Hashes a value.
+    hash: fn(&T) -> u64,
+    /// Filter of every parrent, indexed by raw index of the parrent without the leaf layer.
+    filters: Vec<u64>,
+    /// `false` if leaves were changed without being tracked.
+    valid: bool,
+}
+
+impl<T> ValueFilters<T> {
+    /// Returns bits set in a filter by `value`.
+    fn bits(&self, value: &T) -> u64 {
+        let hash = (self.hash)(value);
+        1 << (hash & 63) | 1 << ((hash >> 6) & 63)
+    }
+
+    /// Adds value of `node` on `index` to filters of all its ancestors,
+    /// if it is a [`Filled`](Node::Filled) leaf.
+    pub(crate) fn record<U>(&mut self, index: NodeIndex<U>, node: &Node<T>)
+    where
+        U: TreeInterface,
+    {
+        let Node::Filled(value) = node else {
+            return;
+        };
+        if index.depth() != 0 {
+            return;
+        }
+        let bits = self.bits(value);
+        for ancestor in index.ancestors() {
+            self.filters[ancestor.raw() - U::LEAF_LAYER_LEN] |= bits;
+        }
+    }
+
+    /// Marks filters as unknown.
+    pub(crate) fn invalidate(&mut self) {
+        self.valid = false;
+    }
+}
+
+/// Hashes `value`.
+fn hash_value<T>(value: &T) -> u64
+where
+    T: Hash,
+{
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Starts keeping a bloom filter of values of [`Filled`](Node::Filled) leaves under every
+    /// parrent, which is updated by every [`set`](Tree::set) and used by
+    /// [`may_contain_value`](Tree::may_contain_value).
+    ///
+    /// Changes of leaves made through [`get_mut`](Tree::get_mut) or mutable iterators make
+    /// filters unknown until [`refresh_value_filters`](Tree::refresh_value_filters) is called.
+    /// Does nothing if value filters are already kept.
+    pub fn enable_value_filters(&mut self)
+    where
+        T: Hash,
+    {
+        if self.value_filters.is_none() {
+            self.value_filters = Some(Box::new(ValueFilters {
+                hash: hash_value::<T>,
+                filters: Vec::new(),
+                valid: false,
+            }));
+            self.refresh_value_filters();
+        }
+    }
+
+    /// Stops keeping value filters.
+    pub fn disable_value_filters(&mut self) {
+        self.value_filters = None;
+    }
+
+    /// Recomputes value filters from current leaves, forgetting values which were replaced.
+    ///
+    /// Does nothing if value filters are not [enabled](Tree::enable_value_filters).
+    pub fn refresh_value_filters(&mut self) {
+        let Some(filters) = &mut self.value_filters else {
+            return;
+        };
+        filters.filters = vec![0; SIZE - Self::LEAF_LAYER_LEN];
+        for (index, node) in self.stored[..Self::LEAF_LAYER_LEN].iter().enumerate() {
+            filters.record(NodeIndex::<Self>::new(index), node);
+        }
+        filters.valid = true;
+    }
+
+    /// Returns `false` if no [`Filled`](Node::Filled) leaf inside of `region` holds `value`.
+    ///
+    /// `region` is a [`PositionRange`] or ranges of `x`, `y` and `z` in the shallowest layer.
+    /// Only filters of parrents are checked, so `true` means the value may be present.
+    /// Returns `true` if value filters are not [enabled](Tree::enable_value_filters)
+    /// or are unknown.
+    ///
+    /// Which values give false positives is not stable across builds and platforms,
+    /// see [determinism](crate#determinism).
+    pub fn may_contain_value<R>(&self, value: &T, region: R) -> bool
+    where
+        R: Into<PositionRange<Self>>,
+    {
+        let region = region.into().leaf_region();
+        let Some(filters) = self
+            .value_filters
+            .as_deref()
+            .filter(|filters| filters.valid)
+        else {
+            return true;
+        };
+        let bits = filters.bits(value);
+        let mut stack = vec![NodeIndex::<Self>::new(SIZE - 1)];
+        while let Some(index) = stack.pop() {
+            let position = NodePosition::from(index);
+            let extent = 1 << index.depth();
+            let overlaps = [position.x, position.y, position.z]
+                .iter()
+                .zip(&region)
+                .all(|(start, range)| *start < range.end && range.start < start + extent);
+            if !overlaps {
+                continue;
+            }
+            let Some(children) = Children::new(index) else {
+                // Tree without parrents has no filters.
+                return true;
+            };
+            if filters.filters[index.raw() - Self::LEAF_LAYER_LEN] & bits != bits {
+                continue;
+            }
+            if index.depth() == 1 {
+                return true;
+            }
+            stack.extend(children);
+        }
+        false
+    }
+
+    /// Marks value filters as unknown, if they are kept.
+    pub(crate) fn invalidate_value_filters(&mut self) {
+        if let Some(filters) = &mut self.value_filters {
+            filters.invalidate();
+        }
+    }
+}
+
+/// Returns `false` if no [`Filled`](Node::Filled) leaf of `access` inside of `region`
+/// holds `value`.
+///
+/// Works with any storage implementing [`OctreeAccess`]. Unlike [`Tree::may_contain_value`]
+/// there are no value filters to check, so leaves inside of `region` are compared with `value`
+/// and the result is exact.
+pub fn may_contain_value<A, R>(access: &A, value: &A::Value, region: R) -> bool
+where
+    A: OctreeAccess,
+    A::Value: PartialEq,
+    R: Into<PositionRange<A::Layout>>,
+{
+    let leaves = PositionRange::from(region.into().leaf_region());
+    iter_region(access, leaves)
+        .any(|(_, node)| matches!(node, Node::Filled(filled) if filled == value))
+}
+
+#[cfg(test)]
+mod value_filter_tests {
+    use crate::implemented_tree_sizes::TREE_8;
+    use crate::{LayerPosition, Node, NodeIndex, NodePosition, PositionRange, Tree};
+
+    type TestTree = Tree<u32, TREE_8>;
+
+    fn leaf(x: usize, y: usize, z: usize) -> NodeIndex<TestTree> {
+        NodePosition::new(x, y, z, 0).into()
+    }
+
+    #[test]
+    fn may_contain_value() {
+        let mut tree = TestTree::new();
+        assert!(tree.may_contain_value(&7, [0..8, 0..8, 0..8]));
+        tree.enable_value_filters();
+        assert!(!tree.may_contain_value(&7, [0..8, 0..8, 0..8]));
+
+        tree.set(leaf(5, 6, 1), Node::Filled(7));
+        assert!(tree.may_contain_value(&7, [0..8, 0..8, 0..8]));
+        assert!(tree.may_contain_value(&7, [4..6, 6..7, 0..2]));
+        assert!(!tree.may_contain_value(&7, [0..4, 0..8, 0..8]));
+        assert!(!tree.may_contain_value(&7, [0..8, 0..8, 2..8]));
+        let upper = LayerPosition::new(2, 3, 0, 1)..=LayerPosition::new(3, 3, 0, 1);
+        assert!(tree.may_contain_value(&7, PositionRange::from(upper)));
+        assert!(!tree.may_contain_value(
+            &7,
+            PositionRange::layer(1).intersection(PositionRange::from(
+                LayerPosition::new(0, 0, 1, 1)..=LayerPosition::new(3, 3, 3, 1)
+            ))
+        ));
+
+        // Replaced values stay in filters until they are refreshed.
+        tree.set(leaf(5, 6, 1), Node::Filled(1));
+        assert!(tree.may_contain_value(&7, [0..8, 0..8, 0..8]));
+        tree.refresh_value_filters();
+        assert!(!tree.may_contain_value(&7, [0..8, 0..8, 0..8]));
+
+        *tree.get_mut(leaf(0, 0, 0)) = Node::Filled(7);
+        assert!(tree.may_contain_value(&7, [0..1, 0..1, 0..1]));
+        tree.refresh_value_filters();
+        assert!(tree.may_contain_value(&7, [0..1, 0..1, 0..1]));
+        assert!(!tree.may_contain_value(&7, [2..8, 0..8, 0..8]));
+    }
+}
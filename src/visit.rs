@@ -0,0 +1,114 @@
+use std::fmt::Debug;
+
+use crate::{Node, NodeIndex, OctreeAccess, Tree, TreeInterface};
+
+/// Decides how [`Tree::visit`] continues after visiting a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitControl {
+    /// Visit children of the node.
+    Descend,
+    /// Do not visit children of the node, continue with the rest of the tree.
+    SkipChildren,
+    /// Stop visiting completely.
+    Stop,
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Visits nodes depth-first starting from the root, calling `visitor` on each of them.
+    ///
+    /// [`VisitControl`] returned by `visitor` decides whether children of the visited node
+    /// are visited as well. Children are visited in the order in which they are stored.
+    pub fn visit<F>(&self, visitor: F)
+    where
+        F: FnMut(NodeIndex<Self>, &Node<T>) -> VisitControl,
+    {
+        visit(self, visitor)
+    }
+}
+
+/// Visits nodes of `access` depth-first starting from the root, calling `visitor` on each of them.
+///
+/// Behaves the same as [`Tree::visit`], but works with any storage implementing [`OctreeAccess`].
+pub fn visit<A, F>(access: &A, mut visitor: F)
+where
+    A: OctreeAccess,
+    F: FnMut(NodeIndex<A::Layout>, &Node<A::Value>) -> VisitControl,
+{
+    let mut stack = vec![access.root()];
+    while let Some(index) = stack.pop() {
+        match visitor(index, access.node(index)) {
+            VisitControl::Descend => {
+                if let Some(children) = access.children(index) {
+                    let children: Vec<_> = children.collect();
+                    stack.extend(children.into_iter().rev());
+                }
+            }
+            VisitControl::SkipChildren => {}
+            VisitControl::Stop => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod visit_tests {
+    use crate::{Node, NodeIndex, Tree};
+
+    use super::VisitControl;
+
+    type TestTree = Tree<usize, 73>;
+
+    #[test]
+    fn visit() {
+        let tree = TestTree::new();
+
+        let mut visited = Vec::new();
+        tree.visit(|index, _| {
+            visited.push(index.raw());
+            VisitControl::Descend
+        });
+        assert_eq!(visited.len(), 73);
+        assert_eq!(&visited[0..3], &[72, 64, 0]);
+
+        let mut visited = Vec::new();
+        tree.visit(|index, _| {
+            visited.push(index.raw());
+            if index == NodeIndex::new(72) {
+                VisitControl::Descend
+            } else {
+                VisitControl::SkipChildren
+            }
+        });
+        assert_eq!(visited, vec![72, 64, 65, 66, 67, 68, 69, 70, 71]);
+
+        let mut visited = Vec::new();
+        tree.visit(|index, _| {
+            visited.push(index.raw());
+            if index == NodeIndex::new(1) {
+                VisitControl::Stop
+            } else {
+                VisitControl::Descend
+            }
+        });
+        assert_eq!(visited, vec![72, 64, 0, 1]);
+    }
+
+    #[test]
+    fn visit_generic() {
+        let mut tree = TestTree::new();
+        tree.set(NodeIndex::new(65), Node::Filled(1));
+        let scope = tree.scoped_edit();
+
+        let mut filled = Vec::new();
+        super::visit(&scope, |index, node| {
+            if let Node::Filled(_) = node {
+                filled.push(index.raw());
+            }
+            VisitControl::Descend
+        });
+        assert_eq!(filled, vec![65]);
+    }
+}
@@ -0,0 +1,255 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::{LayerPosition, Tree, TreeInterface};
+
+/// Point of a leaf which its world position refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeafOrigin {
+    /// World position of a leaf is its bottom front left corner,
+    /// so leaf `i` spans from `i` to `i + 1` voxels from the origin.
+    #[default]
+    Corner,
+    /// World position of a leaf is its center,
+    /// so leaf `i` spans from `i - 0.5` to `i + 0.5` voxels from the origin.
+    Center,
+}
+
+/// Mapping between world space and leaves of associated [`Tree`](crate::Tree).
+///
+/// World coordinates are converted to signed voxel coordinates by flooring, so points
+/// below the origin map to negative coordinates instead of being rounded towards the leaf
+/// `0`, and only voxel coordinates inside of the leaf layer map to leaves.
+#[derive(Debug)]
+pub struct VoxelSpace<T> {
+    /// World position of the leaf `(0, 0, 0)`, which point of the leaf it is depends on `leaf_origin`.
+    pub origin: [f32; 3],
+    /// Length of an edge of a leaf in world units.
+    pub voxel_size: f32,
+    /// Point of a leaf which its world position refers to.
+    pub leaf_origin: LeafOrigin,
+    /// Associated [`Tree`](crate::Tree).
+    boo: PhantomData<T>,
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Clone for VoxelSpace<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// [`Copy`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Copy for VoxelSpace<T> {}
+
+/// [`PartialEq`] is implemented manually, so there is no requirement on `T` to also implement [`PartialEq`].
+impl<T> PartialEq for VoxelSpace<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.origin == other.origin
+            && self.voxel_size == other.voxel_size
+            && self.leaf_origin == other.leaf_origin
+    }
+}
+
+impl<T> VoxelSpace<T>
+where
+    T: TreeInterface,
+{
+    /// Creates a new [`VoxelSpace`].
+    ///
+    /// Expects positive `voxel_size`.
+    pub fn new(origin: [f32; 3], voxel_size: f32, leaf_origin: LeafOrigin) -> Self {
+        debug_assert!(voxel_size > 0.0);
+        Self {
+            origin,
+            voxel_size,
+            leaf_origin,
+            boo: PhantomData,
+        }
+    }
+
+    /// Returns signed coordinates of the voxel containing `world`, which may be outside of the tree.
+    ///
+    /// Points on a border between two voxels belong to the one with higher coordinates.
+    pub fn world_to_voxel(&self, world: [f32; 3]) -> [i64; 3] {
+        let shift = match self.leaf_origin {
+            LeafOrigin::Corner => 0.0,
+            LeafOrigin::Center => 0.5,
+        };
+        [0, 1, 2].map(|i| ((world[i] - self.origin[i]) / self.voxel_size + shift).floor() as i64)
+    }
+
+    /// Returns position of the leaf containing `world`, or [`None`] if it is outside of the tree.
+    pub fn world_to_leaf(&self, world: [f32; 3]) -> Option<LayerPosition<T>> {
+        let [x, y, z] = self.world_to_voxel(world).map(|coordinate| {
+            usize::try_from(coordinate)
+                .ok()
+                .filter(|c| *c < T::LEAF_ROW_LEN)
+        });
+        Some(LayerPosition::new(x?, y?, z?, 0))
+    }
+
+    /// Returns world position of the voxel on signed `voxel` coordinates,
+    /// i.e. its corner or center according to `leaf_origin`.
+    pub fn voxel_to_world(&self, voxel: [i64; 3]) -> [f32; 3] {
+        [0, 1, 2].map(|i| self.origin[i] + voxel[i] as f32 * self.voxel_size)
+    }
+
+    /// Returns world position of the leaf on `position`, i.e. its corner or center
+    /// according to `leaf_origin`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn leaf_to_world(&self, position: LayerPosition<T>) -> [f32; 3] {
+        debug_assert_eq!(position.depth, 0);
+        self.voxel_to_world([position.x, position.y, position.z].map(|c| c as i64))
+    }
+
+    /// Returns world position of the center of the leaf on `position`, regardless of `leaf_origin`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn leaf_center(&self, position: LayerPosition<T>) -> [f32; 3] {
+        let (min, max) = self.leaf_bounds(position);
+        [0, 1, 2].map(|i| (min[i] + max[i]) * 0.5)
+    }
+
+    /// Returns world positions of the lowest and the highest corner of the leaf on `position`.
+    ///
+    /// Expects `position` to be in depth 0.
+    pub fn leaf_bounds(&self, position: LayerPosition<T>) -> ([f32; 3], [f32; 3]) {
+        let shift = match self.leaf_origin {
+            LeafOrigin::Corner => 0.0,
+            LeafOrigin::Center => self.voxel_size * 0.5,
+        };
+        let min = self.leaf_to_world(position).map(|c| c - shift);
+        (min, min.map(|c| c + self.voxel_size))
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Returns position of node in `depth` containing `world_point`, where `origin` is the bottom
+    /// front left corner of the tree and `voxel_size` is length of an edge of a leaf.
+    ///
+    /// This is inverse of [`NodePosition::world_aabb`](crate::NodePosition::world_aabb),
+    /// [`None`] is returned if the point is outside of the tree or `depth` is out of bounds.
+    pub fn position_at(
+        world_point: [f32; 3],
+        origin: [f32; 3],
+        voxel_size: f32,
+        depth: usize,
+    ) -> Option<LayerPosition<Self>> {
+        if depth > Self::MAX_DEPTH_INDEX {
+            return None;
+        }
+        let [x, y, z] = [0, 1, 2].map(|i| {
+            let leaf = ((world_point[i] - origin[i]) / voxel_size).floor();
+            (leaf >= 0.0 && leaf < Self::LEAF_ROW_LEN as f32).then_some(leaf as usize >> depth)
+        });
+        Some(LayerPosition::new(x?, y?, z?, depth))
+    }
+}
+
+#[cfg(test)]
+mod voxel_space_tests {
+    use crate::{LayerPosition, NodePosition, Tree};
+
+    use super::{LeafOrigin, VoxelSpace};
+
+    type TestTree = Tree<usize, 73>;
+    type TestSpace = VoxelSpace<TestTree>;
+
+    #[test]
+    fn corner_origin() {
+        let space = TestSpace::new([-1.0, 0.0, 2.0], 0.5, LeafOrigin::Corner);
+        assert_eq!(
+            space.world_to_leaf([-1.0, 0.0, 2.0]),
+            Some(LayerPosition::new(0, 0, 0, 0))
+        );
+        assert_eq!(
+            space.world_to_leaf([-0.75, 0.49, 3.99]),
+            Some(LayerPosition::new(0, 0, 3, 0))
+        );
+        assert_eq!(space.world_to_leaf([-1.01, 0.0, 2.0]), None);
+        assert_eq!(space.world_to_leaf([1.0, 0.0, 2.0]), None);
+        assert_eq!(space.world_to_voxel([-1.01, -0.25, 4.0]), [-1, -1, 4]);
+
+        let position = LayerPosition::new(1, 2, 3, 0);
+        assert_eq!(space.leaf_to_world(position), [-0.5, 1.0, 3.5]);
+        assert_eq!(space.leaf_center(position), [-0.25, 1.25, 3.75]);
+        assert_eq!(
+            space.leaf_bounds(position),
+            ([-0.5, 1.0, 3.5], [0.0, 1.5, 4.0])
+        );
+        assert_eq!(
+            space.world_to_leaf(space.leaf_center(position)),
+            Some(position)
+        );
+    }
+
+    #[test]
+    fn center_origin() {
+        let space = TestSpace::new([0.0; 3], 1.0, LeafOrigin::Center);
+        assert_eq!(
+            space.world_to_leaf([-0.5, 0.49, 3.49]),
+            Some(LayerPosition::new(0, 0, 3, 0))
+        );
+        assert_eq!(space.world_to_leaf([-0.51, 0.0, 0.0]), None);
+        assert_eq!(space.world_to_leaf([0.0, 0.0, 3.5]), None);
+        assert_eq!(space.world_to_voxel([-0.51, 0.5, -1.5]), [-1, 1, -1]);
+
+        let position = LayerPosition::new(1, 2, 3, 0);
+        assert_eq!(space.leaf_to_world(position), [1.0, 2.0, 3.0]);
+        assert_eq!(space.leaf_center(position), [1.0, 2.0, 3.0]);
+        assert_eq!(
+            space.leaf_bounds(position),
+            ([0.5, 1.5, 2.5], [1.5, 2.5, 3.5])
+        );
+        assert_eq!(
+            space.world_to_leaf(space.leaf_center(position)),
+            Some(position)
+        );
+
+        let corner = TestSpace::new([0.0; 3], 1.0, LeafOrigin::Corner);
+        assert_eq!(
+            space.leaf_center(position),
+            corner.leaf_center(position).map(|c| c - 0.5)
+        );
+    }
+
+    #[test]
+    fn position_at() {
+        let origin = [-1.0, 0.0, 2.0];
+        assert_eq!(
+            TestTree::position_at([-1.0, 0.0, 2.0], origin, 0.5, 0),
+            Some(LayerPosition::new(0, 0, 0, 0))
+        );
+        assert_eq!(
+            TestTree::position_at([0.9, 1.2, 2.6], origin, 0.5, 0),
+            Some(LayerPosition::new(3, 2, 1, 0))
+        );
+        assert_eq!(
+            TestTree::position_at([0.9, 1.2, 2.6], origin, 0.5, 1),
+            Some(LayerPosition::new(1, 1, 0, 1))
+        );
+        assert_eq!(
+            TestTree::position_at([0.9, 1.2, 2.6], origin, 0.5, 2),
+            Some(LayerPosition::new(0, 0, 0, 2))
+        );
+        assert_eq!(TestTree::position_at([0.9, 1.2, 2.6], origin, 0.5, 3), None);
+        assert_eq!(
+            TestTree::position_at([-1.01, 0.0, 2.0], origin, 0.5, 0),
+            None
+        );
+        assert_eq!(TestTree::position_at([1.0, 0.0, 2.0], origin, 0.5, 0), None);
+
+        let position = NodePosition::<TestTree>::new(2, 0, 2, 1);
+        let (min, _) = position.world_aabb(origin, 0.5);
+        assert_eq!(
+            TestTree::position_at(min, origin, 0.5, 1),
+            Some(LayerPosition::from(position))
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Debug;
+
+use crate::{Direction, LayerPosition, Node, Offset, Tree, TreeInterface};
+
+/// How [`Tree::window3`] treats nodes of the window outside of the layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoundsPolicy {
+    /// Nodes outside of the layer are missing.
+    Skip,
+    /// Nodes outside of the layer are replaced by the closest node inside of it.
+    Clamp,
+    /// Coordinates outside of the layer wrap around to the opposite side.
+    Wrap,
+}
+
+/// Neighborhood of 3×3×3 nodes in one layer, created by [`Tree::window3`].
+///
+/// Nodes are addressed by offset from the center on `x`, `y` and `z`, each in `-1..=1`.
+#[derive(Debug)]
+pub struct Window3<'a, T> {
+    /// Nodes ordered in the same way as they are stored, i.e. `x` changes the fastest.
+    nodes: [Option<&'a Node<T>>; 27],
+}
+
+/// [`Clone`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Clone for Window3<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// [`Copy`] is implemented manually, so there is no requirement on `T` to also implement [`Clone`].
+impl<T> Copy for Window3<'_, T> {}
+
+impl<'a, T> Window3<'a, T> {
+    /// Returns node moved by `dx`, `dy` and `dz` from the center,
+    /// or [`None`] if it is outside of the layer and was [skipped](BoundsPolicy::Skip).
+    ///
+    /// Expects all offsets to be in `-1..=1`.
+    pub fn offset(&self, dx: isize, dy: isize, dz: isize) -> Option<&'a Node<T>> {
+        debug_assert!([dx, dy, dz].iter().all(|offset| (-1..=1).contains(offset)));
+        self.nodes[(dx + 1 + (dy + 1) * 3 + (dz + 1) * 9) as usize]
+    }
+
+    /// Returns node in the center of the window.
+    pub fn center(&self) -> &'a Node<T> {
+        // The center is always inside of the layer.
+        self.nodes[13].unwrap()
+    }
+
+    /// Returns neighbour of the center in `direction`, see [`offset`](Window3::offset).
+    pub fn neighbor(&self, direction: Direction) -> Option<&'a Node<T>> {
+        let [dx, dy, dz] = direction.offset();
+        self.offset(dx, dy, dz)
+    }
+
+    /// Returns neighbour of the center towards lower `x`.
+    pub fn left(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Left)
+    }
+
+    /// Returns neighbour of the center towards higher `x`.
+    pub fn right(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Right)
+    }
+
+    /// Returns neighbour of the center towards lower `y`.
+    pub fn bottom(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Bottom)
+    }
+
+    /// Returns neighbour of the center towards higher `y`.
+    pub fn top(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Top)
+    }
+
+    /// Returns neighbour of the center towards lower `z`.
+    pub fn front(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Front)
+    }
+
+    /// Returns neighbour of the center towards higher `z`.
+    pub fn back(&self) -> Option<&'a Node<T>> {
+        self.neighbor(Direction::Back)
+    }
+
+    /// Returns an iterator over all nodes of the window together with their [`Offset`]
+    /// from the center, `x` changes the fastest.
+    pub fn iter(&self) -> impl Iterator<Item = (Offset, Option<&'a Node<T>>)> + '_ {
+        self.nodes.iter().enumerate().map(|(i, node)| {
+            let offset = |i: usize| i as isize % 3 - 1;
+            (Offset::new(offset(i), offset(i / 3), offset(i / 9)), *node)
+        })
+    }
+}
+
+impl<T, const SIZE: usize> Tree<T, SIZE>
+where
+    Self: TreeInterface,
+    T: Debug,
+{
+    /// Returns [`Window3`] of nodes around `position` in its layer, nodes outside of the layer
+    /// are handled according to `policy`.
+    ///
+    /// Coordinates are resolved once per axis, so it is cheaper than looking up
+    /// all 27 neighbours one by one.
+    pub fn window3<P>(&self, position: P, policy: BoundsPolicy) -> Window3<'_, T>
+    where
+        P: Into<LayerPosition<Self>>,
+    {
+        let position: LayerPosition<Self> = position.into();
+        let row_size = Self::row_size(position.depth).get();
+        let start = Self::layer_start(position.depth);
+        let resolve = |coordinate: usize| {
+            [-1, 0, 1].map(|offset| match coordinate.checked_add_signed(offset) {
+                Some(coordinate) if coordinate < row_size => Some(coordinate),
+                _ => match policy {
+                    BoundsPolicy::Skip => None,
+                    BoundsPolicy::Clamp => Some(coordinate),
+                    BoundsPolicy::Wrap => {
+                        Some((coordinate + row_size).wrapping_add_signed(offset) % row_size)
+                    }
+                },
+            })
+        };
+        let [xs, ys, zs] = [position.x, position.y, position.z].map(resolve);
+        let mut nodes = [None; 27];
+        for (i, node) in nodes.iter_mut().enumerate() {
+            if let (Some(x), Some(y), Some(z)) = (xs[i % 3], ys[i / 3 % 3], zs[i / 9]) {
+                *node = Some(&self.stored[start + x + (y + z * row_size) * row_size]);
+            }
+        }
+        Window3 { nodes }
+    }
+}
+
+#[cfg(test)]
+mod window_tests {
+    use crate::{Direction, LayerPosition, Node, NodeIndex, Offset, Tree};
+
+    use super::BoundsPolicy;
+
+    type TestTree = Tree<usize, 73>;
+
+    /// Returns a tree with every leaf holding its index.
+    fn indexed_tree() -> TestTree {
+        let mut tree = TestTree::new();
+        for index in 0..64 {
+            tree.set(NodeIndex::new(index), Node::Filled(index));
+        }
+        tree
+    }
+
+    #[test]
+    fn window3() {
+        let tree = indexed_tree();
+        let window = tree.window3(LayerPosition::new(1, 2, 1, 0), BoundsPolicy::Skip);
+        assert_eq!(window.center(), &Node::Filled(25));
+        assert_eq!(window.top(), Some(&Node::Filled(29)));
+        assert_eq!(window.left(), Some(&Node::Filled(24)));
+        assert_eq!(window.back(), Some(&Node::Filled(41)));
+        assert_eq!(window.offset(1, -1, -1), Some(&Node::Filled(6)));
+        assert_eq!(window.neighbor(Direction::Front), window.offset(0, 0, -1));
+        for (offset, node) in window.iter() {
+            let expected = tree.get((LayerPosition::new(1, 2, 1, 0) + offset).unwrap());
+            assert_eq!(node, Some(expected));
+        }
+        assert_eq!(window.iter().nth(1).unwrap().0, Offset::new(0, -1, -1));
+    }
+
+    #[test]
+    fn bounds_policy() {
+        let tree = indexed_tree();
+        let position = LayerPosition::new(0, 3, 0, 0);
+
+        let window = tree.window3(position, BoundsPolicy::Skip);
+        assert_eq!(window.left(), None);
+        assert_eq!(window.top(), None);
+        assert_eq!(window.right(), Some(&Node::Filled(13)));
+        assert_eq!(window.iter().filter(|(_, node)| node.is_some()).count(), 8);
+
+        let window = tree.window3(position, BoundsPolicy::Clamp);
+        assert_eq!(window.left(), Some(&Node::Filled(12)));
+        assert_eq!(window.offset(-1, 1, -1), Some(&Node::Filled(12)));
+        assert_eq!(window.bottom(), Some(&Node::Filled(8)));
+
+        let window = tree.window3(position, BoundsPolicy::Wrap);
+        assert_eq!(window.left(), Some(&Node::Filled(15)));
+        assert_eq!(window.top(), Some(&Node::Filled(0)));
+        assert_eq!(window.offset(-1, 1, -1), Some(&Node::Filled(51)));
+
+        let root = tree.window3(LayerPosition::new(0, 0, 0, 2), BoundsPolicy::Wrap);
+        assert!(root.iter().all(|(_, node)| node == Some(root.center())));
+    }
+}